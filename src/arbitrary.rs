@@ -0,0 +1,80 @@
+// 只生成合法的UtcDatetime,年份覆盖MIN_YEAR到MAX_YEAR
+use crate::{days_of_the_month, UtcDatetime};
+
+#[cfg(feature = "proptest")]
+mod proptest_impls{
+    use super::*;
+    use proptest::prelude::*;
+
+    impl Arbitrary for UtcDatetime{
+        type Parameters=();
+        type Strategy=BoxedStrategy<UtcDatetime>;
+
+        fn arbitrary_with(_args:())->Self::Strategy{
+            (UtcDatetime::MIN_YEAR..=UtcDatetime::MAX_YEAR,1u8..=12)
+                .prop_flat_map(|(year,month)|{
+                    (Just(year),Just(month),1..=days_of_the_month(year,month),0u8..24,0u8..60,0u8..60)
+                })
+                .prop_map(|(year,month,day,hour,minute,second)|UtcDatetime{year,month,day,hour,minute,second})
+                .boxed()
+        }
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impls{
+    use super::*;
+    use quickcheck::{Arbitrary, Gen};
+
+    impl Arbitrary for UtcDatetime{
+        fn arbitrary(g:&mut Gen)->Self{
+            let year=UtcDatetime::MIN_YEAR+(u32::arbitrary(g)%(UtcDatetime::MAX_YEAR-UtcDatetime::MIN_YEAR+1) as u32) as i32;
+            let month=1+u8::arbitrary(g)%12;
+            let day=1+u8::arbitrary(g)%days_of_the_month(year,month);
+            let hour=u8::arbitrary(g)%24;
+            let minute=u8::arbitrary(g)%60;
+            let second=u8::arbitrary(g)%60;
+            UtcDatetime{year,month,day,hour,minute,second}
+        }
+
+        // 依次把年份缩小到1970,时间缩小到零点
+        fn shrink(&self)->Box<dyn Iterator<Item=Self>>{
+            let dt=*self;
+            let years=(dt.year-1970).shrink().filter(|offset|(UtcDatetime::MIN_YEAR..=UtcDatetime::MAX_YEAR).contains(&(1970+offset))).map(move |offset|{
+                let year=1970+offset;
+                let day=dt.day.min(days_of_the_month(year,dt.month));
+                UtcDatetime{year,day,..dt}
+            });
+            let hours=dt.hour.shrink().map(move |hour|UtcDatetime{hour,..dt});
+            let minutes=dt.minute.shrink().map(move |minute|UtcDatetime{minute,..dt});
+            let seconds=dt.second.shrink().map(move |second|UtcDatetime{second,..dt});
+            Box::new(years.chain(hours).chain(minutes).chain(seconds))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::UtcDatetime;
+
+    fn is_valid(dt:&UtcDatetime)->bool{
+        UtcDatetime::new(dt.year,dt.month,dt.day,dt.hour,dt.minute,dt.second).is_ok()
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest!{
+        #[test]
+        fn proptest_generates_valid(dt in proptest::prelude::any::<UtcDatetime>()){
+            proptest::prop_assert!(is_valid(&dt));
+        }
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn quickcheck_generates_valid(){
+        fn prop(dt:UtcDatetime)->bool{
+            is_valid(&dt) && quickcheck::Arbitrary::shrink(&dt).all(|s|is_valid(&s))
+        }
+        quickcheck::quickcheck(prop as fn(UtcDatetime)->bool);
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::UtcDatetime;
+
+/// A UtcDatetime that can be shared between threads without a lock.
+///
+/// The datetime is stored as [`UtcDatetime::to_packed_u64`], whose numeric order is chronological,
+/// so [`AtomicUtcDatetime::fetch_max`] keeps the latest time in a single atomic instruction.
+/// # Example
+/// ```
+/// use std::sync::atomic::Ordering;
+/// use utc_datetime::{AtomicUtcDatetime, UtcDatetime};
+/// let last_seen=AtomicUtcDatetime::new(UtcDatetime::MIN);
+/// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+/// last_seen.fetch_max(a_date,Ordering::Relaxed);
+/// last_seen.fetch_max(UtcDatetime::new(2021,1,1,0,0,0).unwrap(),Ordering::Relaxed);
+/// assert_eq!(last_seen.load(Ordering::Relaxed),a_date);
+/// ```
+pub struct AtomicUtcDatetime(AtomicU64);
+
+// 只会存入合法的日期,解码不会失败
+fn unpack(packed:u64)->UtcDatetime{
+    UtcDatetime::from_packed_u64(packed).expect("an AtomicUtcDatetime only holds valid datetimes")
+}
+
+impl AtomicUtcDatetime{
+    pub fn new(datetime:UtcDatetime)->AtomicUtcDatetime{
+        AtomicUtcDatetime(AtomicU64::new(datetime.to_packed_u64()))
+    }
+
+    pub fn load(&self,order:Ordering)->UtcDatetime{
+        unpack(self.0.load(order))
+    }
+
+    pub fn store(&self,datetime:UtcDatetime,order:Ordering){
+        self.0.store(datetime.to_packed_u64(),order)
+    }
+
+    /// Store `datetime` and return the previous value
+    pub fn swap(&self,datetime:UtcDatetime,order:Ordering)->UtcDatetime{
+        unpack(self.0.swap(datetime.to_packed_u64(),order))
+    }
+
+    /// Store the later of the current value and `datetime`, and return the previous value
+    pub fn fetch_max(&self,datetime:UtcDatetime,order:Ordering)->UtcDatetime{
+        unpack(self.0.fetch_max(datetime.to_packed_u64(),order))
+    }
+
+    /// Store the earlier of the current value and `datetime`, and return the previous value
+    pub fn fetch_min(&self,datetime:UtcDatetime,order:Ordering)->UtcDatetime{
+        unpack(self.0.fetch_min(datetime.to_packed_u64(),order))
+    }
+
+    /// Store `new` if the current value is `current`, see [`AtomicU64::compare_exchange`]
+    pub fn compare_exchange(&self,current:UtcDatetime,new:UtcDatetime,success:Ordering,failure:Ordering)->Result<UtcDatetime,UtcDatetime>{
+        self.0.compare_exchange(current.to_packed_u64(),new.to_packed_u64(),success,failure).map(unpack).map_err(unpack)
+    }
+
+    pub fn into_inner(self)->UtcDatetime{
+        unpack(self.0.into_inner())
+    }
+}
+
+impl Default for AtomicUtcDatetime{
+    /// The epoch, like [`UtcDatetime::default`]
+    fn default()->AtomicUtcDatetime{
+        AtomicUtcDatetime::new(UtcDatetime::UNIX_EPOCH)
+    }
+}
+
+impl From<UtcDatetime> for AtomicUtcDatetime{
+    fn from(datetime:UtcDatetime)->AtomicUtcDatetime{
+        AtomicUtcDatetime::new(datetime)
+    }
+}
+
+impl fmt::Debug for AtomicUtcDatetime{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        fmt::Debug::fmt(&self.load(Ordering::Relaxed),f)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use crate::{AtomicUtcDatetime, UtcDatetime};
+
+    #[test]
+    fn concurrent_max(){
+        let last=Arc::new(AtomicUtcDatetime::default());
+        assert_eq!(last.load(Ordering::SeqCst),UtcDatetime::UNIX_EPOCH);
+        let threads:Vec<_>=(0..4u32).map(|t|{
+            let last=Arc::clone(&last);
+            std::thread::spawn(move ||for i in 0..1000{
+                last.fetch_max(UtcDatetime::from_timestamp(i*4+t),Ordering::Relaxed);
+            })
+        }).collect();
+        for thread in threads{
+            thread.join().unwrap();
+        }
+        assert_eq!(last.load(Ordering::SeqCst),UtcDatetime::from_timestamp(3999));
+        assert_eq!(last.fetch_min(UtcDatetime::MAX,Ordering::SeqCst),UtcDatetime::from_timestamp(3999));
+        assert_eq!(last.swap(UtcDatetime::MAX,Ordering::SeqCst),UtcDatetime::from_timestamp(3999));
+        assert_eq!(last.compare_exchange(UtcDatetime::MIN,UtcDatetime::MIN,Ordering::SeqCst,Ordering::SeqCst),Err(UtcDatetime::MAX));
+        assert_eq!(format!("{:?}",last),format!("{:?}",UtcDatetime::MAX));
+    }
+}
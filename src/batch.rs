@@ -0,0 +1,145 @@
+use crate::{civil_from_days, days_from_civil, days_of_the_month, IllegalTimeError, UtcDatetime};
+
+const SECONDS_PER_DAY:i64=24*60*60;
+
+impl UtcDatetime{
+    /// Convert a slice of UtcDatetime to the number of seconds since January 1, 1970.
+    ///
+    /// Unlike [`UtcDatetime::timestamp`] the result is not limited to u32.
+    /// The day number of the current month is cached, so sorted or clustered input
+    /// only pays for the calendar calculation once per month.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let dates=[UtcDatetime::new(2020,2,2,2,2,2).unwrap(),UtcDatetime::new(2200,1,1,0,0,0).unwrap()];
+    /// assert_eq!(UtcDatetime::timestamps_from_slice(&dates),vec![1580608922,7258118400]);
+    /// ```
+    pub fn timestamps_from_slice(datetimes:&[UtcDatetime])->Vec<i64>{
+        let mut timestamps=Vec::with_capacity(datetimes.len());
+        // 缓存上一个(年,月)和该月1日距1970年1月1日的天数
+        let mut cached_month=(0,0);
+        let mut month_start_days=0;
+        for dt in datetimes{
+            if (dt.year,dt.month)!=cached_month{
+                cached_month=(dt.year,dt.month);
+                month_start_days=days_from_civil(dt.year as i64,dt.month,1);
+            }
+            let days=month_start_days+dt.day as i64-1;
+            timestamps.push(days*SECONDS_PER_DAY+dt.hour as i64*3600+dt.minute as i64*60+dt.second as i64);
+        }
+        timestamps
+    }
+
+    /// Convert a slice of seconds since January 1, 1970 to UtcDatetime,
+    /// the inverse of [`UtcDatetime::timestamps_from_slice`].
+    ///
+    /// Returns YearNumberError if any timestamp is before [`UtcDatetime::MIN`] or after [`UtcDatetime::MAX`].
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let dates=UtcDatetime::from_timestamps_slice(&[1580608922,7258118400]).unwrap();
+    /// assert_eq!(dates,vec![UtcDatetime::new(2020,2,2,2,2,2).unwrap(),UtcDatetime::new(2200,1,1,0,0,0).unwrap()]);
+    /// assert_eq!(UtcDatetime::from_timestamps_slice(&[-1]).unwrap(),vec![UtcDatetime::new(1969,12,31,23,59,59).unwrap()]);
+    /// assert!(UtcDatetime::from_timestamps_slice(&[i64::MIN]).is_err());
+    /// ```
+    pub fn from_timestamps_slice(timestamps:&[i64])->Result<Vec<UtcDatetime>, IllegalTimeError>{
+        let min_timestamp=UtcDatetime::MIN.unix_seconds();
+        let max_timestamp=UtcDatetime::MAX.unix_seconds();
+        let mut datetimes=Vec::with_capacity(timestamps.len());
+        // 缓存上一个月的时间戳范围[month_start,month_end)
+        let mut cached_month=(0,0);
+        let mut month_start=0;
+        let mut month_end=0;
+        for &ts in timestamps{
+            if ts<min_timestamp || ts>max_timestamp{
+                return Err(IllegalTimeError::YearNumberError)
+            }
+            if ts<month_start || ts>=month_end{
+                let (year,month,_)=civil_from_days(ts.div_euclid(SECONDS_PER_DAY));
+                cached_month=(year as i32,month);
+                month_start=days_from_civil(year,month,1)*SECONDS_PER_DAY;
+                month_end=month_start+days_of_the_month(year as i32,month) as i64*SECONDS_PER_DAY;
+            }
+            let offset=ts-month_start;
+            let seconds_of_day=offset%SECONDS_PER_DAY;
+            datetimes.push(UtcDatetime{
+                year:cached_month.0,
+                month:cached_month.1,
+                day:(offset/SECONDS_PER_DAY+1) as u8,
+                hour:(seconds_of_day/3600) as u8,
+                minute:(seconds_of_day%3600/60) as u8,
+                second:(seconds_of_day%60) as u8,
+            });
+        }
+        Ok(datetimes)
+    }
+
+    /// Sort datetime strings chronologically, returning the indices of `strings` in sorted order.
+    ///
+    /// Each string is parsed once with [`UtcDatetime::from_string`], equal times keep their input order.
+    /// If any string fails to parse, returns the index and error of every such string instead.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// assert_eq!(UtcDatetime::argsort_strings(&["2021-11-15 09:30:00","2020-02-02 02:02:02"]).unwrap(),[1,0]);
+    /// let errors=UtcDatetime::argsort_strings(&["2021-11-15 09:30:00","yesterday","2021-13-01 00:00:00"]).unwrap_err();
+    /// assert_eq!(errors.iter().map(|(i,_)|*i).collect::<Vec<_>>(),[1,2]);
+    /// ```
+    pub fn argsort_strings(strings:&[&str])->Result<Vec<usize>, Vec<(usize,IllegalTimeError)>>{
+        let mut keyed=Vec::with_capacity(strings.len());
+        let mut errors=Vec::new();
+        for (i,s) in strings.iter().enumerate(){
+            match UtcDatetime::from_string(s){
+                Ok(dt)=>keyed.push((dt,i)),
+                Err(e)=>errors.push((i,e)),
+            }
+        }
+        if !errors.is_empty(){
+            return Err(errors)
+        }
+        keyed.sort_by_key(|&(dt,_)|dt);
+        Ok(keyed.into_iter().map(|(_,i)|i).collect())
+    }
+
+    /// Sort datetime strings chronologically, see [`UtcDatetime::argsort_strings`]
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let sorted=UtcDatetime::sort_strings(&["2021-11-15T09:30:00Z","2020-02-02 02:02:02"]).unwrap();
+    /// assert_eq!(sorted,["2020-02-02 02:02:02","2021-11-15T09:30:00Z"]);
+    /// ```
+    pub fn sort_strings<'a>(strings:&[&'a str])->Result<Vec<&'a str>, Vec<(usize,IllegalTimeError)>>{
+        Ok(UtcDatetime::argsort_strings(strings)?.into_iter().map(|i|strings[i]).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::UtcDatetime;
+
+    #[test]
+    fn matches_single_conversions(){
+        let timestamps:Vec<i64>=(0..u32::MAX as i64).step_by(86_399*3+7).collect();
+        let datetimes=UtcDatetime::from_timestamps_slice(&timestamps).unwrap();
+        for (dt,&ts) in datetimes.iter().zip(&timestamps){
+            assert_eq!(*dt,UtcDatetime::from_timestamp(ts as u32));
+        }
+        assert_eq!(UtcDatetime::timestamps_from_slice(&datetimes),timestamps);
+        let timestamps:Vec<i64>=(UtcDatetime::MIN.unix_seconds()..=UtcDatetime::MAX.unix_seconds()).step_by(86_399_999_983).collect();
+        let datetimes=UtcDatetime::from_timestamps_slice(&timestamps).unwrap();
+        for (dt,&ts) in datetimes.iter().zip(&timestamps){
+            assert_eq!(*dt,UtcDatetime::from_unix_seconds(ts).unwrap());
+        }
+        assert_eq!(UtcDatetime::timestamps_from_slice(&datetimes),timestamps);
+        assert!(UtcDatetime::from_timestamps_slice(&[UtcDatetime::MIN.unix_seconds()-1]).is_err());
+    }
+
+    #[test]
+    fn sort_strings(){
+        let strings=["2021-11-15 09:30:00","1970-01-01 00:00:00","2021-11-15T09:30:00Z","2020-02-02 02:02:02"];
+        assert_eq!(UtcDatetime::argsort_strings(&strings).unwrap(),[1,3,0,2]);
+        assert!(UtcDatetime::sort_strings(&[]).unwrap().is_empty());
+        let errors=UtcDatetime::sort_strings(&["","2021-11-15 09:30:00"]).unwrap_err();
+        assert_eq!((errors.len(),errors[0].0),(1,0));
+    }
+}
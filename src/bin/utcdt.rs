@@ -0,0 +1,138 @@
+//! Convert between datetime formats, built with the `cli` feature.
+//!
+//! ```text
+//! utcdt 1580608922
+//! utcdt "2020-02-02 02:02:02" --to rfc3339
+//! utcdt now +2h -30m --format "%d/%m/%Y %H:%M"
+//! ```
+use std::process::ExitCode;
+use utc_datetime::format::FormatDescription;
+use utc_datetime::{Duration, IllegalTimeError, UtcDatetime};
+
+const USAGE:&str="\
+usage: utcdt <now|TIMESTAMP|DATETIME> [+DURATION|-DURATION]... [--to FORMAT | --format DESCRIPTION]
+
+A number is read as seconds since 1970-01-01 00:00:00, anything else is detected
+as RFC 3339, RFC 2822, SQL, compact or YYYY-MM-DD HH:MM:SS.
+Durations are written like +2h, -90s or +1d 12h, an unsigned part keeps the sign before it.
+FORMAT is one of default, rfc3339, rfc2822, rfc850, sql, compact, clf, timestamp.
+Numbers and now are printed as default, datetimes as timestamp, unless --to or --format is given.";
+
+enum Output{
+    Named(String),
+    Description(String),
+}
+
+fn main()->ExitCode{
+    let args:Vec<String>=std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|a|a=="-h" || a=="--help"){
+        println!("{}",USAGE);
+        return ExitCode::SUCCESS
+    }
+    match run(&args){
+        Ok(line)=>{
+            println!("{}",line);
+            ExitCode::SUCCESS
+        },
+        Err(message)=>{
+            eprintln!("utcdt: {}\n\n{}",message,USAGE);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn run(args:&[String])->Result<String,String>{
+    let input=&args[0];
+    let mut output=None;
+    let mut offsets=Vec::new();
+    let mut negative=None;
+    let mut rest=args[1..].iter();
+    while let Some(arg)=rest.next(){
+        match arg.as_str(){
+            "--to"=>output=Some(Output::Named(rest.next().ok_or("--to needs a format")?.clone())),
+            "--format"=>output=Some(Output::Description(rest.next().ok_or("--format needs a description")?.clone())),
+            _=>{
+                let (sign,offset)=parse_offset(arg,negative)?;
+                negative=Some(sign);
+                offsets.push(offset);
+            },
+        }
+    }
+    let is_datetime=input!="now" && input.parse::<i64>().is_err();
+    let mut datetime=parse_input(input).map_err(|e|format!("cannot read {:?}: {}",input,e))?;
+    for offset in offsets{
+        datetime=datetime.add_duration(offset).map_err(|e|format!("cannot add {}: {}",offset,e))?;
+    }
+    let output=output.unwrap_or_else(||Output::Named(if is_datetime {"timestamp"} else {"default"}.to_string()));
+    match output{
+        Output::Named(name)=>format_named(&datetime,&name),
+        Output::Description(description)=>{
+            let description=FormatDescription::new(&description).map_err(|e|e.to_string())?;
+            datetime.try_format(&description).map_err(|e|e.to_string())
+        },
+    }
+}
+
+fn parse_input(input:&str)->Result<UtcDatetime, IllegalTimeError>{
+    if input=="now"{
+        return Ok(UtcDatetime::now())
+    }
+    if let Ok(seconds)=input.parse::<i64>(){
+        return UtcDatetime::UNIX_EPOCH.add_duration(Duration::seconds(seconds))
+    }
+    UtcDatetime::parse_auto(input).map(|(datetime,_)|datetime)
+}
+
+// +2h或-30m,不带符号的部分(如`+1d 12h`中的12h)沿用前一个偏移的符号
+fn parse_offset(arg:&str,previous:Option<bool>)->Result<(bool,Duration),String>{
+    let (negative,duration)=match (arg.as_bytes().first(),previous){
+        (Some(b'+'),_)=>(false,&arg[1..]),
+        (Some(b'-'),_)=>(true,&arg[1..]),
+        (_,Some(negative))=>(negative,arg),
+        _=>return Err(format!("unexpected argument {:?}",arg)),
+    };
+    let duration:Duration=duration.parse().map_err(|_|format!("cannot read duration {:?}",arg))?;
+    Ok((negative,if negative {-duration} else {duration}))
+}
+
+fn format_named(datetime:&UtcDatetime,name:&str)->Result<String,String>{
+    Ok(match name{
+        "default"=>datetime.to_string(),
+        "rfc3339" | "iso8601"=>datetime.display_iso8601().to_string(),
+        "rfc2822"=>datetime.to_rfc2822(),
+        "rfc850"=>datetime.to_rfc850().map_err(|e|e.to_string())?,
+        "sql"=>datetime.to_sql_datetime(),
+        "compact"=>datetime.to_compact(),
+        "clf"=>datetime.to_common_log(),
+        "timestamp"=>UtcDatetime::UNIX_EPOCH.until(datetime).whole_seconds().to_string(),
+        _=>return Err(format!("unknown format {:?}",name)),
+    })
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    fn utcdt(args:&[&str])->Result<String,String>{
+        run(&args.iter().map(|a|a.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn documented_examples(){
+        assert_eq!(utcdt(&["1580608922"]).unwrap(),"2020-02-02 02:02:02");
+        assert_eq!(utcdt(&["2020-02-02 02:02:02","--to","rfc3339"]).unwrap(),"2020-02-02T02:02:02Z");
+        assert_eq!(utcdt(&["2020-02-02 02:02:02"]).unwrap(),"1580608922");
+        assert_eq!(utcdt(&["1580608922","+2h","-30m","--format","%d/%m/%Y %H:%M"]).unwrap(),"02/02/2020 03:32");
+        let later=UtcDatetime::from_string(&utcdt(&["now","+2h"]).unwrap()).unwrap();
+        assert!(later.approx_eq(&UtcDatetime::now().add_duration(Duration::hours(2)).unwrap(),Duration::seconds(5)));
+    }
+
+    #[test]
+    fn unsigned_parts_keep_the_sign(){
+        assert_eq!(utcdt(&["1580608922","+1d","12h"]).unwrap(),"2020-02-03 14:02:02");
+        assert_eq!(utcdt(&["1580608922","-1d","12h"]).unwrap(),"2020-01-31 14:02:02");
+        assert_eq!(utcdt(&["1580608922","+1d 12h"]).unwrap(),"2020-02-03 14:02:02");
+        assert!(utcdt(&["1580608922","12h"]).unwrap_err().contains("unexpected argument"));
+        assert!(utcdt(&["1580608922","--to","sql","12h"]).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{IllegalTimeError, UtcDatetime};
+
+impl UtcDatetime{
+    /// Convert to the 7-byte binary representation.
+    ///
+    /// The layout is stable and does not depend on the platform:
+    ///
+    /// | bytes | 0..2 | 2 | 3 | 4 | 5 | 6 |
+    /// |-------|------|---|---|---|---|---|
+    /// | field | year (u16, little-endian) | month | day | hour | minute | second |
+    ///
+    /// Returns YearNumberError if the year is negative or above 65535, which do not fit in the u16 field.
+    /// The `bincode` feature encodes UtcDatetime with this layout.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_bytes().unwrap(),[0xe5,0x07,11,15,9,30,0]);
+    /// assert!(UtcDatetime::new(-1,1,1,0,0,0).unwrap().to_bytes().is_err());
+    /// ```
+    pub fn to_bytes(&self)->Result<[u8;7], IllegalTimeError>{
+        let [y0,y1]=u16::try_from(self.year).map_err(|_|IllegalTimeError::YearNumberError)?.to_le_bytes();
+        Ok([y0,y1,self.month,self.day,self.hour,self.minute,self.second])
+    }
+
+    /// Create a UtcDatetime from the 7-byte binary representation,
+    /// see [`UtcDatetime::to_bytes`] for the layout.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_bytes(a_date.to_bytes().unwrap()).unwrap(),a_date);
+    /// assert!(UtcDatetime::from_bytes([0xe5,0x07,13,15,9,30,0]).is_err());
+    /// ```
+    pub fn from_bytes(bytes:[u8;7])->Result<UtcDatetime, IllegalTimeError>{
+        let [y0,y1,month,day,hour,minute,second]=bytes;
+        UtcDatetime::new(u16::from_le_bytes([y0,y1]) as i32,month,day,hour,minute,second)
+    }
+}
+
+/// Same as [`UtcDatetime::to_bytes`]
+impl TryFrom<UtcDatetime> for [u8;7]{
+    type Error=IllegalTimeError;
+
+    fn try_from(dt:UtcDatetime)->Result<[u8;7], IllegalTimeError>{
+        dt.to_bytes()
+    }
+}
+
+/// Same as [`UtcDatetime::from_bytes`]
+impl TryFrom<[u8;7]> for UtcDatetime{
+    type Error=IllegalTimeError;
+
+    fn try_from(bytes:[u8;7])->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::from_bytes(bytes)
+    }
+}
+
+/// Read the first 7 bytes of a record, returns TimeStringError if the slice is shorter
+impl TryFrom<&[u8]> for UtcDatetime{
+    type Error=IllegalTimeError;
+
+    fn try_from(bytes:&[u8])->Result<UtcDatetime, IllegalTimeError>{
+        let bytes:[u8;7]=bytes.get(..7).and_then(|b|b.try_into().ok()).ok_or(IllegalTimeError::TimeStringError)?;
+        UtcDatetime::from_bytes(bytes)
+    }
+}
+
+// 38位的年份字段以偏移二进制存储有符号的年份
+const YEAR_OFFSET:i64=1<<37;
+
+impl UtcDatetime{
+    /// Pack the fields into a u64 whose numeric order matches chronological order.
+    ///
+    /// From the most significant bit: year (38 bits), month (4 bits), day (5 bits),
+    /// hour (5 bits), minute (6 bits), second (6 bits).
+    /// The year is stored offset by 2^37 so that years before 0 sort first.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// let b=UtcDatetime::new(2021,12,1,0,0,0).unwrap();
+    /// assert!(a.to_packed_u64()<b.to_packed_u64());
+    /// assert_eq!(UtcDatetime::from_packed_u64(a.to_packed_u64()).unwrap(),a);
+    /// ```
+    pub fn to_packed_u64(&self)->u64{
+        ((self.year as i64+YEAR_OFFSET) as u64)<<26
+            | (self.month as u64)<<22
+            | (self.day as u64)<<17
+            | (self.hour as u64)<<12
+            | (self.minute as u64)<<6
+            | self.second as u64
+    }
+
+    /// Create a UtcDatetime from the value returned by [`UtcDatetime::to_packed_u64`]
+    pub fn from_packed_u64(packed:u64)->Result<UtcDatetime, IllegalTimeError>{
+        let year=i32::try_from((packed>>26) as i64-YEAR_OFFSET).map_err(|_|IllegalTimeError::YearNumberError)?;
+        UtcDatetime::new(
+            year,
+            (packed>>22&0xf) as u8,
+            (packed>>17&0x1f) as u8,
+            (packed>>12&0x1f) as u8,
+            (packed>>6&0x3f) as u8,
+            (packed&0x3f) as u8,
+        )
+    }
+}
+
+#[cfg(feature = "bincode")]
+mod bincode_impls{
+    use crate::UtcDatetime;
+    use bincode::de::Decoder;
+    use bincode::enc::Encoder;
+    use bincode::error::{DecodeError, EncodeError};
+    use bincode::{Decode, Encode};
+
+    // 固定编码为to_bytes的7个字节,不受bincode整数编码配置的影响
+    impl Encode for UtcDatetime{
+        fn encode<E:Encoder>(&self,encoder:&mut E)->Result<(),EncodeError>{
+            self.to_bytes().map_err(|e|EncodeError::OtherString(e.to_string()))?.encode(encoder)
+        }
+    }
+
+    impl<Context> Decode<Context> for UtcDatetime{
+        fn decode<D:Decoder<Context=Context>>(decoder:&mut D)->Result<Self,DecodeError>{
+            let bytes=<[u8;7]>::decode(decoder)?;
+            UtcDatetime::from_bytes(bytes).map_err(|e|DecodeError::OtherString(e.to_string()))
+        }
+    }
+
+    bincode::impl_borrow_decode!(UtcDatetime);
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::{IllegalTimeError, UtcDatetime};
+
+    #[test]
+    fn stable_layout(){
+        let bytes:[u8;7]=UtcDatetime::new(65535,12,31,23,59,59).unwrap().try_into().unwrap();
+        assert_eq!(bytes,[0xff,0xff,12,31,23,59,59]);
+        assert_eq!(<[u8;7]>::try_from(UtcDatetime::UNIX_EPOCH).unwrap(),[0xb2,0x07,1,1,0,0,0]);
+        assert!(matches!(UtcDatetime::MAX.to_bytes(),Err(IllegalTimeError::YearNumberError)));
+        assert!(matches!(UtcDatetime::MIN.to_bytes(),Err(IllegalTimeError::YearNumberError)));
+        let record=[0xe5,0x07,11,15,9,30,0,0xaa,0xbb];
+        assert_eq!(UtcDatetime::try_from(&record[..]).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+        assert!(UtcDatetime::try_from(&record[..6]).is_err());
+        assert_eq!(UtcDatetime::try_from([0xb1,0x07,1,1,0,0,0]).unwrap().year(),1969);
+        assert!(UtcDatetime::try_from([0xb1,0x07,0,1,0,0,0]).is_err());
+    }
+
+    #[test]
+    fn packed_order(){
+        let dates=[UtcDatetime::MIN,UtcDatetime::new(-1,12,31,23,59,59).unwrap(),UtcDatetime::new(0,1,1,0,0,0).unwrap(),UtcDatetime::UNIX_EPOCH,UtcDatetime::MAX];
+        for pair in dates.windows(2){
+            assert!(pair[0].to_packed_u64()<pair[1].to_packed_u64());
+        }
+        for a_date in dates{
+            assert_eq!(UtcDatetime::from_packed_u64(a_date.to_packed_u64()).unwrap(),a_date);
+        }
+        assert!(matches!(UtcDatetime::from_packed_u64(0),Err(IllegalTimeError::YearNumberError)));
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_uses_fixed_layout(){
+        let dt=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        let encoded=bincode::encode_to_vec(dt,bincode::config::standard()).unwrap();
+        assert_eq!(encoded,dt.to_bytes().unwrap());
+        assert!(bincode::encode_to_vec(UtcDatetime::MIN,bincode::config::standard()).is_err());
+        let (decoded,len):(UtcDatetime,usize)=bincode::decode_from_slice(&encoded,bincode::config::standard()).unwrap();
+        assert_eq!((decoded,len),(dt,7));
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn rkyv_access_without_deserializing(){
+        let dates=vec![UtcDatetime::new(2020,2,2,2,2,2).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap()];
+        let bytes=rkyv::to_bytes::<rkyv::rancor::Error>(&dates).unwrap();
+        let archived=rkyv::access::<rkyv::Archived<Vec<UtcDatetime>>,rkyv::rancor::Error>(&bytes).unwrap();
+        assert!(archived[0]<archived[1]);
+        assert_eq!(archived[1],dates[1]);
+        let back:Vec<UtcDatetime>=rkyv::deserialize::<_,rkyv::rancor::Error>(archived).unwrap();
+        assert_eq!(back,dates);
+    }
+}
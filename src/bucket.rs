@@ -0,0 +1,73 @@
+//! Grouping timestamps into hour, day or week buckets.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::bucket::group_by;
+//! let events=[("login",UtcDatetime::new(2021,11,15,9,30,0).unwrap()),("logout",UtcDatetime::new(2021,11,15,17,0,0).unwrap()),("login",UtcDatetime::new(2021,11,16,9,0,0).unwrap())];
+//! let by_day=group_by(events,|&(_,at)|at,UtcDatetime::bucket_by_day);
+//! assert_eq!(by_day.len(),2);
+//! assert_eq!(by_day[&UtcDatetime::new(2021,11,15,0,0,0).unwrap()].len(),2);
+//! ```
+use std::collections::BTreeMap;
+use crate::UtcDatetime;
+
+impl UtcDatetime{
+    /// The start of the hour, such as `2021-11-15 09:00:00`
+    pub fn bucket_by_hour(&self)->UtcDatetime{
+        UtcDatetime{minute:0,second:0,..*self}
+    }
+
+    /// The start of the day, such as `2021-11-15 00:00:00`
+    pub fn bucket_by_day(&self)->UtcDatetime{
+        UtcDatetime{hour:0,minute:0,second:0,..*self}
+    }
+
+    /// The start of the ISO week, the Monday at `00:00:00`.
+    ///
+    /// The first days of [`UtcDatetime::MIN_YEAR`] belong to a week that began before it, they return [`UtcDatetime::MIN`].
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,18,9,30,0).unwrap();
+    /// assert_eq!(a_date.bucket_by_week(),UtcDatetime::new(2021,11,15,0,0,0).unwrap());
+    /// assert_eq!(UtcDatetime::new(1970,1,4,0,0,0).unwrap().bucket_by_week(),UtcDatetime::new(1969,12,29,0,0,0).unwrap());
+    /// ```
+    pub fn bucket_by_week(&self)->UtcDatetime{
+        // 1970-01-01是星期四
+        let days=self.unix_seconds().div_euclid(24*60*60);
+        let monday=days-(days+3).rem_euclid(7);
+        UtcDatetime::from_unix_seconds(monday*24*60*60).unwrap_or(UtcDatetime::MIN)
+    }
+}
+
+/// Group items by the bucket of their time, such as [`UtcDatetime::bucket_by_day`].
+///
+/// Items keep their input order within a bucket.
+pub fn group_by<T,I,F,B>(items:I,time:F,bucket:B)->BTreeMap<UtcDatetime,Vec<T>>
+where I:IntoIterator<Item=T>,F:Fn(&T)->UtcDatetime,B:Fn(&UtcDatetime)->UtcDatetime{
+    let mut groups:BTreeMap<UtcDatetime,Vec<T>>=BTreeMap::new();
+    for item in items{
+        groups.entry(bucket(&time(&item))).or_default().push(item);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::UtcDatetime;
+    use super::group_by;
+
+    #[test]
+    fn buckets(){
+        let a_date=UtcDatetime::new(2021,11,15,9,30,59).unwrap();
+        assert_eq!(a_date.bucket_by_hour().to_string(),"2021-11-15 09:00:00");
+        assert_eq!(a_date.bucket_by_day().to_string(),"2021-11-15 00:00:00");
+        assert_eq!(a_date.bucket_by_week(),a_date.bucket_by_day());
+        assert_eq!(UtcDatetime::MAX.bucket_by_week().weekday(),1);
+        assert_eq!(UtcDatetime::MIN.bucket_by_week(),UtcDatetime::MIN);
+        assert_eq!(UtcDatetime::new(1970,1,5,1,0,0).unwrap().bucket_by_week().to_string(),"1970-01-05 00:00:00");
+        let hours=group_by((0..86400).step_by(600).map(UtcDatetime::from_timestamp),|dt|*dt,UtcDatetime::bucket_by_hour);
+        assert_eq!(hours.len(),24);
+        assert!(hours.values().all(|v|v.len()==6));
+    }
+}
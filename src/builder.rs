@@ -0,0 +1,105 @@
+use crate::{IllegalTimeError, UtcDatetime};
+
+/// Build a UtcDatetime from named fields, created by [`UtcDatetime::builder`].
+///
+/// The year, month and day are required, the time defaults to midnight.
+/// # Example
+/// ```
+/// use utc_datetime::{IllegalTimeError, UtcDatetime};
+/// let a_date=UtcDatetime::builder().year(2021).month(11).day(15).hour(9).minute(30).build().unwrap();
+/// assert_eq!(a_date,UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+/// assert!(matches!(UtcDatetime::builder().year(2021).day(15).build(),Err(IllegalTimeError::MonthNumberError)));
+/// ```
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct UtcDatetimeBuilder{
+    year:Option<i32>,
+    month:Option<u8>,
+    day:Option<u8>,
+    hour:u8,
+    minute:u8,
+    second:u8,
+}
+
+impl UtcDatetime{
+    /// Start building a UtcDatetime from named fields
+    pub fn builder()->UtcDatetimeBuilder{
+        UtcDatetimeBuilder::default()
+    }
+}
+
+impl UtcDatetimeBuilder{
+    pub fn year(self,year:i32)->UtcDatetimeBuilder{
+        UtcDatetimeBuilder{year:Some(year),..self}
+    }
+
+    pub fn month(self,month:u8)->UtcDatetimeBuilder{
+        UtcDatetimeBuilder{month:Some(month),..self}
+    }
+
+    pub fn day(self,day:u8)->UtcDatetimeBuilder{
+        UtcDatetimeBuilder{day:Some(day),..self}
+    }
+
+    pub fn hour(self,hour:u8)->UtcDatetimeBuilder{
+        UtcDatetimeBuilder{hour,..self}
+    }
+
+    pub fn minute(self,minute:u8)->UtcDatetimeBuilder{
+        UtcDatetimeBuilder{minute,..self}
+    }
+
+    pub fn second(self,second:u8)->UtcDatetimeBuilder{
+        UtcDatetimeBuilder{second,..self}
+    }
+
+    /// Validate the fields like [`UtcDatetime::new`].
+    ///
+    /// A missing year, month or day returns the error for that field.
+    pub fn build(self)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::new(
+            self.year.ok_or(IllegalTimeError::YearNumberError)?,
+            self.month.ok_or(IllegalTimeError::MonthNumberError)?,
+            self.day.ok_or(IllegalTimeError::DayNumberError)?,
+            self.hour,
+            self.minute,
+            self.second,
+        )
+    }
+}
+
+/// The fields to change with [`UtcDatetime::replace`], `None` keeps the current value
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct FieldChanges{
+    pub year:Option<i32>,
+    pub month:Option<u8>,
+    pub day:Option<u8>,
+    pub hour:Option<u8>,
+    pub minute:Option<u8>,
+    pub second:Option<u8>,
+}
+
+impl UtcDatetime{
+    /// Return a copy with several fields replaced at once, validated once like [`UtcDatetime::new`].
+    ///
+    /// Unlike chained `with_*` calls, the result only has to be valid after all changes,
+    /// so 2020-01-31 can become 2021-02-28 in one step.
+    /// # Example
+    /// ```
+    /// use utc_datetime::{FieldChanges, IllegalTimeError, UtcDatetime};
+    /// let a_date=UtcDatetime::new(2020,1,31,9,30,0).unwrap();
+    /// let changed=a_date.replace(FieldChanges{year:Some(2021),month:Some(2),day:Some(28),..Default::default()}).unwrap();
+    /// assert_eq!(changed,UtcDatetime::new(2021,2,28,9,30,0).unwrap());
+    /// assert!(matches!(a_date.with_month(2),Err(IllegalTimeError::DayNumberError)));
+    /// assert_eq!(a_date.replace(FieldChanges::default()).unwrap(),a_date);
+    /// ```
+    pub fn replace(&self,changes:FieldChanges)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::new(
+            changes.year.unwrap_or(self.year),
+            changes.month.unwrap_or(self.month),
+            changes.day.unwrap_or(self.day),
+            changes.hour.unwrap_or(self.hour),
+            changes.minute.unwrap_or(self.minute),
+            changes.second.unwrap_or(self.second),
+        )
+    }
+}
@@ -0,0 +1,350 @@
+//! Calendar rules: holidays, business-day arithmetic and weekly schedules.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::calendar::HolidayCalendar;
+//! // treat January 1 as a holiday
+//! struct NewYear;
+//! impl HolidayCalendar for NewYear{
+//!     fn is_holiday(&self,date:&UtcDatetime)->bool{
+//!         (date.month(),date.day())==(1,1)
+//!     }
+//! }
+//! let friday=UtcDatetime::new(2021,12,31,9,30,0).unwrap();
+//! assert_eq!(friday.add_business_days(1,&NewYear).unwrap(),UtcDatetime::new(2022,1,3,9,30,0).unwrap());
+//! assert!(!UtcDatetime::new(2022,1,1,0,0,0).unwrap().is_business_day(&NewYear));
+//! ```
+use crate::{civil_from_days, days_from_civil, iso_weeks, IllegalTimeError, UtcDatetime};
+
+#[cfg(feature = "chinese-calendar")]
+pub mod chinese;
+#[cfg(feature = "hebrew")]
+pub mod hebrew;
+#[cfg(feature = "hijri")]
+pub mod hijri;
+#[cfg(feature = "holidays")]
+pub mod holidays;
+pub mod japanese;
+
+/// Decides which days are not business days.
+///
+/// Only the date of the argument is meaningful, the time is unspecified.
+pub trait HolidayCalendar{
+    /// Returns whether the date is a holiday
+    fn is_holiday(&self,date:&UtcDatetime)->bool;
+
+    /// Returns whether the weekday (Sunday is 0, as returned by [`UtcDatetime::weekday`])
+    /// is a weekend, Saturday and Sunday by default
+    fn is_weekend(&self,weekday:u8)->bool{
+        weekday==0 || weekday==6
+    }
+}
+
+impl<C:HolidayCalendar+?Sized> HolidayCalendar for &C{
+    fn is_holiday(&self,date:&UtcDatetime)->bool{
+        (**self).is_holiday(date)
+    }
+
+    fn is_weekend(&self,weekday:u8)->bool{
+        (**self).is_weekend(weekday)
+    }
+}
+
+/// A calendar where every weekday is a business day
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct WeekendsOnly;
+
+impl HolidayCalendar for WeekendsOnly{
+    fn is_holiday(&self,_date:&UtcDatetime)->bool{
+        false
+    }
+}
+
+/// A set of weekdays, numbered like [`UtcDatetime::weekday`] with Sunday as 0
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// use utc_datetime::calendar::WeekdaySet;
+/// // every Monday, Wednesday and Friday at 09:00
+/// let days=WeekdaySet::EMPTY.with(1).with(3).with(5);
+/// let friday=UtcDatetime::new(2021,11,19,10,0,0).unwrap();
+/// assert_eq!(friday.next_matching(&days,(9,0,0)).unwrap(),UtcDatetime::new(2021,11,22,9,0,0).unwrap());
+/// assert!(days.contains(3) && !days.contains(0));
+/// assert_eq!(days.iter().collect::<Vec<_>>(),[1,3,5]);
+/// ```
+#[derive(Clone,Copy,PartialEq,Eq,Hash,Debug,Default)]
+pub struct WeekdaySet(u8);
+
+impl WeekdaySet{
+    pub const EMPTY:WeekdaySet=WeekdaySet(0);
+    pub const ALL:WeekdaySet=WeekdaySet(0x7f);
+    /// Monday to Friday
+    pub const WEEKDAYS:WeekdaySet=WeekdaySet(0x3e);
+    /// Saturday and Sunday
+    pub const WEEKEND:WeekdaySet=WeekdaySet(0x41);
+
+    /// Returns a copy with `weekday` added, weekdays above 6 are ignored
+    pub const fn with(self,weekday:u8)->WeekdaySet{
+        if weekday>6{
+            return self
+        }
+        WeekdaySet(self.0|1<<weekday)
+    }
+
+    /// Returns a copy with `weekday` removed
+    pub const fn without(self,weekday:u8)->WeekdaySet{
+        if weekday>6{
+            return self
+        }
+        WeekdaySet(self.0&!(1<<weekday))
+    }
+
+    pub const fn contains(&self,weekday:u8)->bool{
+        weekday<7 && self.0&1<<weekday!=0
+    }
+
+    pub const fn len(&self)->u32{
+        self.0.count_ones()
+    }
+
+    pub const fn is_empty(&self)->bool{
+        self.0==0
+    }
+
+    /// The weekdays in the set, from Sunday to Saturday
+    pub fn iter(&self)->impl Iterator<Item=u8>{
+        let set=*self;
+        (0..7).filter(move |&weekday|set.contains(weekday))
+    }
+}
+
+impl FromIterator<u8> for WeekdaySet{
+    fn from_iter<I:IntoIterator<Item=u8>>(iter:I)->WeekdaySet{
+        iter.into_iter().fold(WeekdaySet::EMPTY,WeekdaySet::with)
+    }
+}
+
+impl UtcDatetime{
+    /// The first datetime strictly after `self` that falls on one of `weekdays`
+    /// at `time_of_day` (hour, minute, second).
+    ///
+    /// Returns the error of [`crate::is_valid_time`] for an invalid time of day,
+    /// DayNumberError if `weekdays` is empty and YearNumberError if there is no such datetime
+    /// in the representable range.
+    pub fn next_matching(&self,weekdays:&WeekdaySet,time_of_day:(u8,u8,u8))->Result<UtcDatetime, IllegalTimeError>{
+        let (hour,minute,second)=time_of_day;
+        crate::is_valid_time(hour,minute,second)?;
+        if weekdays.is_empty(){
+            return Err(IllegalTimeError::DayNumberError)
+        }
+        let today=days_from_civil(self.year as i64,self.month,self.day);
+        // 今天的时刻已过时从明天开始找,一周之内一定能找到
+        let first=if (hour,minute,second)>(self.hour,self.minute,self.second) {today} else {today+1};
+        let day=(first..first+7).find(|&day|weekdays.contains((4+day).rem_euclid(7) as u8)).unwrap_or(first);
+        UtcDatetime{hour,minute,second,..*self}.on_day(day)
+    }
+
+    /// Returns whether the date is neither a weekend nor a holiday in `calendar`
+    pub fn is_business_day<C:HolidayCalendar+?Sized>(&self,calendar:&C)->bool{
+        !calendar.is_weekend(self.weekday()) && !calendar.is_holiday(self)
+    }
+
+    /// Move forward (or backward if `n` is negative) by `n` business days, keeping the time of day.
+    ///
+    /// The start itself does not need to be a business day, `n==0` returns it unchanged.
+    /// Returns YearNumberError if the result is outside the representable range.
+    pub fn add_business_days<C:HolidayCalendar+?Sized>(&self,n:i64,calendar:&C)->Result<UtcDatetime, IllegalTimeError>{
+        let step=n.signum();
+        let mut remaining=n.unsigned_abs();
+        let mut day=days_from_civil(self.year as i64,self.month,self.day);
+        let mut dt=*self;
+        while remaining>0{
+            day+=step;
+            dt=self.on_day(day)?;
+            if dt.is_business_day(calendar){
+                remaining-=1;
+            }
+        }
+        Ok(dt)
+    }
+
+    /// Count the business days after the date of `self` up to and including the date of `other`,
+    /// negative if `other` is earlier, so that `a.add_business_days(a.business_days_between(&b,c),c)`
+    /// falls on `b` when `b` is a business day.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// use utc_datetime::calendar::WeekendsOnly;
+    /// let monday=UtcDatetime::new(2021,11,15,0,0,0).unwrap();
+    /// let next_monday=UtcDatetime::new(2021,11,22,0,0,0).unwrap();
+    /// assert_eq!(monday.business_days_between(&next_monday,&WeekendsOnly),5);
+    /// assert_eq!(next_monday.business_days_between(&monday,&WeekendsOnly),-5);
+    /// ```
+    pub fn business_days_between<C:HolidayCalendar+?Sized>(&self,other:&UtcDatetime,calendar:&C)->i64{
+        let from=days_from_civil(self.year as i64,self.month,self.day);
+        let to=days_from_civil(other.year as i64,other.month,other.day);
+        let (low,high,sign)=if from<=to {(from,to,1)} else {(to,from,-1)};
+        let count=(low+1..=high).filter(|&day|self.on_day(day).is_ok_and(|dt|dt.is_business_day(calendar))).count();
+        count as i64*sign
+    }
+
+    /// The week of the month, 1 to 6, where weeks start on Monday and week 1 is the week containing the 1st
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// // November 1, 2021 is a Monday, August 1, 2021 is a Sunday
+    /// assert_eq!(UtcDatetime::new(2021,11,15,0,0,0).unwrap().week_of_month(),3);
+    /// assert_eq!(UtcDatetime::new(2021,8,2,0,0,0).unwrap().week_of_month(),2);
+    /// assert_eq!(UtcDatetime::new(2021,8,31,0,0,0).unwrap().week_of_month(),6);
+    /// assert_eq!(UtcDatetime::new(2021,8,2,0,0,0).unwrap().week_of_month_full_weeks(),1);
+    /// assert_eq!(UtcDatetime::new(2021,8,1,0,0,0).unwrap().week_of_month_full_weeks(),0);
+    /// ```
+    pub fn week_of_month(&self)->u8{
+        (self.day-1+self.first_of_month_offset())/7+1
+    }
+
+    /// The week of the month, 0 to 5, where week 1 starts on the first Monday of the month
+    /// and the days before it are week 0
+    pub fn week_of_month_full_weeks(&self)->u8{
+        let first_monday=1+(7-self.first_of_month_offset())%7;
+        if self.day<first_monday {0} else {(self.day-first_monday)/7+1}
+    }
+
+    /// The ISO 8601 week-numbering year and week, 1 to 53.
+    ///
+    /// The year can be one more than [`UtcDatetime::MAX_YEAR`] because the last days of 999999 belong to week 1 of 1000000.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// assert_eq!(UtcDatetime::new(2021,1,3,0,0,0).unwrap().iso_week(),(2020,53));
+    /// assert_eq!(UtcDatetime::new(2021,11,15,0,0,0).unwrap().iso_week(),(2021,46));
+    /// assert_eq!(UtcDatetime::new(2024,12,30,0,0,0).unwrap().iso_week(),(2025,1));
+    /// ```
+    pub fn iso_week(&self)->(i32,u8){
+        let year=self.year as i64;
+        let day_of_year=days_from_civil(year,self.month,self.day)-days_from_civil(year,1,1)+1;
+        let iso_weekday=(days_from_civil(year,self.month,self.day)+3).rem_euclid(7)+1;
+        let week=(day_of_year-iso_weekday+10)/7;
+        if week<1{
+            return (self.year-1,iso_weeks(year-1))
+        }
+        if week>iso_weeks(year) as i64{
+            return (self.year+1,1)
+        }
+        (self.year,week as u8)
+    }
+
+    // 本月1日距离它所在那周的星期一的天数
+    fn first_of_month_offset(&self)->u8{
+        let first=days_from_civil(self.year as i64,self.month,1);
+        (first+3).rem_euclid(7) as u8
+    }
+
+    // 返回指定日期(距1970年1月1日的天数)的同一时刻
+    fn on_day(&self,day:i64)->Result<UtcDatetime, IllegalTimeError>{
+        let (year,month,day)=civil_from_days(day);
+        let year=i32::try_from(year).ok().filter(|&y|(UtcDatetime::MIN_YEAR..=UtcDatetime::MAX_YEAR).contains(&y)).ok_or(IllegalTimeError::YearNumberError)?;
+        Ok(UtcDatetime{year,month,day,..*self})
+    }
+}
+
+/// The date of Easter Sunday in the Gregorian calendar, at midnight.
+///
+/// Good Friday, Easter Monday and Pentecost are 2 days before, 1 day after and 49 days after.
+/// Returns YearNumberError outside [`UtcDatetime::MIN_YEAR`] to [`UtcDatetime::MAX_YEAR`].
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// use utc_datetime::calendar::easter_sunday;
+/// assert_eq!(easter_sunday(2021).unwrap(),UtcDatetime::new(2021,4,4,0,0,0).unwrap());
+/// assert_eq!(easter_sunday(2038).unwrap(),UtcDatetime::new(2038,4,25,0,0,0).unwrap());
+/// ```
+pub fn easter_sunday(year:i32)->Result<UtcDatetime, IllegalTimeError>{
+    UtcDatetime::MIN.on_day(easter_day(year as i64))
+}
+
+// 格里高利历复活节(匿名算法),返回距1970年1月1日的天数
+fn easter_day(year:i64)->i64{
+    // 日期以5700000年为周期重复,平移到正数年份再计算
+    let shifted=year.rem_euclid(5_700_000);
+    let a=shifted%19;
+    let (b,c)=(shifted/100,shifted%100);
+    let (d,e)=(b/4,b%4);
+    let f=(b+8)/25;
+    let g=(b-f+1)/3;
+    let h=(19*a+b-d-g+15)%30;
+    let (i,k)=(c/4,c%4);
+    let l=(32+2*e+2*i-h-k)%7;
+    let m=(a+11*h+22*l)/451;
+    let month=(h+l-7*m+114)/31;
+    let day=(h+l-7*m+114)%31+1;
+    days_from_civil(year,month as u8,day as u8)
+}
+
+#[cfg(test)]
+mod tests{
+    use super::{HolidayCalendar, WeekdaySet, WeekendsOnly};
+    use crate::{weeks_in_iso_year, UtcDatetime};
+
+    struct Everything;
+    impl HolidayCalendar for Everything{
+        fn is_holiday(&self,_date:&UtcDatetime)->bool{
+            true
+        }
+    }
+
+    #[test]
+    fn business_days(){
+        let saturday=UtcDatetime::new(2021,11,13,12,0,0).unwrap();
+        assert_eq!(saturday.add_business_days(1,&WeekendsOnly).unwrap(),UtcDatetime::new(2021,11,15,12,0,0).unwrap());
+        assert_eq!(saturday.add_business_days(-1,&WeekendsOnly).unwrap(),UtcDatetime::new(2021,11,12,12,0,0).unwrap());
+        assert_eq!(saturday.add_business_days(0,&WeekendsOnly).unwrap(),saturday);
+        assert_eq!(saturday.add_business_days(10,&WeekendsOnly).unwrap(),UtcDatetime::new(2021,11,26,12,0,0).unwrap());
+        assert!(UtcDatetime::MIN.add_business_days(-1,&WeekendsOnly).is_err());
+        assert!(UtcDatetime::new(-999_999,2,1,0,0,0).unwrap().add_business_days(-1,&Everything).is_err());
+        assert_eq!(saturday.business_days_between(&saturday,&WeekendsOnly),0);
+    }
+
+    #[test]
+    fn easter_before_1970(){
+        assert_eq!(super::easter_sunday(1818).unwrap(),UtcDatetime::new(1818,3,22,0,0,0).unwrap());
+        for year in [-999_999,-44,0,1,1583,999_999]{
+            let easter=super::easter_sunday(year).unwrap();
+            assert_eq!((easter.year(),easter.weekday()),(year,0),"{}",year);
+            assert!((3..=4).contains(&easter.month()),"{}",year);
+        }
+        assert!(super::easter_sunday(UtcDatetime::MAX_YEAR+1).is_err());
+    }
+
+    #[test]
+    fn next_matching(){
+        let monday_9am=UtcDatetime::new(2021,11,15,9,0,0).unwrap();
+        let mondays=WeekdaySet::EMPTY.with(1);
+        assert_eq!(monday_9am.next_matching(&mondays,(9,0,0)).unwrap(),UtcDatetime::new(2021,11,22,9,0,0).unwrap());
+        assert_eq!(monday_9am.next_matching(&mondays,(9,0,1)).unwrap(),UtcDatetime::new(2021,11,15,9,0,1).unwrap());
+        assert_eq!(monday_9am.next_matching(&WeekdaySet::WEEKEND,(0,0,0)).unwrap(),UtcDatetime::new(2021,11,20,0,0,0).unwrap());
+        assert!(monday_9am.next_matching(&WeekdaySet::EMPTY,(9,0,0)).is_err());
+        assert!(monday_9am.next_matching(&mondays,(24,0,0)).is_err());
+        assert!(UtcDatetime::MAX.next_matching(&WeekdaySet::ALL,(0,0,0)).is_err());
+        assert_eq!(WeekdaySet::WEEKDAYS,(1..=5).collect());
+        assert_eq!(WeekdaySet::ALL.without(0).without(6),WeekdaySet::WEEKDAYS);
+        assert_eq!((WeekdaySet::ALL.len(),WeekdaySet::WEEKEND.with(9).len()),(7,2));
+    }
+
+    #[test]
+    fn iso_weeks_change_on_monday(){
+        let mut previous=UtcDatetime::new(1970,1,1,0,0,0).unwrap();
+        for ts in (86_400..u32::MAX).step_by(86_400){
+            let dt=UtcDatetime::from_timestamp(ts);
+            let (year,week)=dt.iso_week();
+            let (previous_year,previous_week)=previous.iso_week();
+            if dt.weekday()==1{
+                let expected=if previous_week==weeks_in_iso_year(previous_year) {(previous_year+1,1)} else {(previous_year,previous_week+1)};
+                assert_eq!((year,week),expected,"{}",dt);
+            }else{
+                assert_eq!((year,week),(previous_year,previous_week),"{}",dt);
+            }
+            previous=dt;
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Conversion to and from the Chinese lunisolar calendar, enabled with the `chinese-calendar` feature.
+//!
+//! Supported are lunar years 1900 to 2100, which cover the Gregorian dates from 1900-01-31
+//! up to 2101-01-28.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::calendar::chinese::{spring_festival, LunarDate};
+//! let mid_autumn=UtcDatetime::new(2021,9,21,0,0,0).unwrap();
+//! assert_eq!(mid_autumn.to_lunar().unwrap(),LunarDate{year:2021,month:8,day:15,is_leap_month:false});
+//! assert_eq!(spring_festival(2023).unwrap(),UtcDatetime::new(2023,1,22,0,0,0).unwrap());
+//! ```
+use crate::{civil_from_days, days_from_civil, IllegalTimeError, UtcDatetime};
+
+const FIRST_YEAR:i32=1900;
+// 农历1900年正月初一是公历1900年1月31日
+const FIRST_NEW_YEAR:(i64,u8,u8)=(1900,1,31);
+// 每年一个数:第16位为1表示闰月为大月(30天),第15到4位依次表示正月到十二月是否为大月,
+// 低4位是闰几月,0表示没有闰月
+const LUNAR_INFO:[u32;201]=[
+    0x04bd8,0x04ae0,0x0a570,0x054d5,0x0d260,0x0d950,0x16554,0x056a0,0x09ad0,0x055d2,
+    0x04ae0,0x0a5b6,0x0a4d0,0x0d250,0x1d255,0x0b540,0x0d6a0,0x0ada2,0x095b0,0x14977,
+    0x04970,0x0a4b0,0x0b4b5,0x06a50,0x06d40,0x1ab54,0x02b60,0x09570,0x052f2,0x04970,
+    0x06566,0x0d4a0,0x0ea50,0x16a95,0x05ad0,0x02b60,0x186e3,0x092e0,0x1c8d7,0x0c950,
+    0x0d4a0,0x1d8a6,0x0b550,0x056a0,0x1a5b4,0x025d0,0x092d0,0x0d2b2,0x0a950,0x0b557,
+    0x06ca0,0x0b550,0x15355,0x04da0,0x0a5b0,0x14573,0x052b0,0x0a9a8,0x0e950,0x06aa0,
+    0x0aea6,0x0ab50,0x04b60,0x0aae4,0x0a570,0x05260,0x0f263,0x0d950,0x05b57,0x056a0,
+    0x096d0,0x04dd5,0x04ad0,0x0a4d0,0x0d4d4,0x0d250,0x0d558,0x0b540,0x0b6a0,0x195a6,
+    0x095b0,0x049b0,0x0a974,0x0a4b0,0x0b27a,0x06a50,0x06d40,0x0af46,0x0ab60,0x09570,
+    0x04af5,0x04970,0x064b0,0x074a3,0x0ea50,0x06b58,0x05ac0,0x0ab60,0x096d5,0x092e0,
+    0x0c960,0x0d954,0x0d4a0,0x0da50,0x07552,0x056a0,0x0abb7,0x025d0,0x092d0,0x0cab5,
+    0x0a950,0x0b4a0,0x0baa4,0x0ad50,0x055d9,0x04ba0,0x0a5b0,0x15176,0x052b0,0x0a930,
+    0x07954,0x06aa0,0x0ad50,0x05b52,0x04b60,0x0a6e6,0x0a4e0,0x0d260,0x0ea65,0x0d530,
+    0x05aa0,0x076a3,0x096d0,0x04afb,0x04ad0,0x0a4d0,0x1d0b6,0x0d250,0x0d520,0x0dd45,
+    0x0b5a0,0x056d0,0x055b2,0x049b0,0x0a577,0x0a4b0,0x0aa50,0x1b255,0x06d20,0x0ada0,
+    0x14b63,0x09370,0x049f8,0x04970,0x064b0,0x168a6,0x0ea50,0x06b20,0x1a6c4,0x0aae0,
+    0x0a2e0,0x0d2e3,0x0c960,0x0d557,0x0d4a0,0x0da50,0x05d55,0x056a0,0x0a6d0,0x055d4,
+    0x052d0,0x0a9b8,0x0a950,0x0b4a0,0x0b6a6,0x0ad50,0x055a0,0x0aba4,0x0a5b0,0x052b0,
+    0x0b273,0x06930,0x07337,0x06aa0,0x0ad50,0x14b55,0x04b60,0x0a570,0x054e4,0x0d160,
+    0x0e968,0x0d520,0x0daa0,0x16aa6,0x056d0,0x04ae0,0x0a9d4,0x0a2d0,0x0d150,0x0f252,
+    0x0d520,
+];
+
+/// A date in the Chinese lunisolar calendar
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Hash,Debug)]
+pub struct LunarDate{
+    /// The lunar year, numbered like the Gregorian year in which it mostly falls
+    pub year:i32,
+    /// The month, 1 to 12
+    pub month:u8,
+    /// The day, 1 to 30
+    pub day:u8,
+    /// True for the intercalary month that repeats `month`
+    pub is_leap_month:bool,
+}
+
+fn info(year:i32)->Option<u32>{
+    LUNAR_INFO.get(usize::try_from(year-FIRST_YEAR).ok()?).copied()
+}
+
+// 某年的各个月份和天数,闰月排在同名的月份之后
+fn months(info:u32)->impl Iterator<Item=(u8,bool,i64)>{
+    let leap=(info&0xf) as u8;
+    (1..=12u8).flat_map(move |month|{
+        let days=if info&(0x10000>>month)!=0 {30} else {29};
+        let leap_days=if info&0x10000!=0 {30} else {29};
+        core::iter::once((month,false,days)).chain((month==leap).then_some((month,true,leap_days)))
+    })
+}
+
+fn year_days(info:u32)->i64{
+    months(info).map(|(_,_,days)|days).sum()
+}
+
+impl UtcDatetime{
+    /// Convert the date to the Chinese lunisolar calendar, ignoring the time.
+    ///
+    /// Returns YearNumberError if the date is before 1900-01-31 or after 2101-01-28.
+    pub fn to_lunar(&self)->Result<LunarDate, IllegalTimeError>{
+        let (y,m,d)=FIRST_NEW_YEAR;
+        let mut offset=days_from_civil(self.year as i64,self.month,self.day)-days_from_civil(y,m,d);
+        if offset<0{
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        for (i,&info) in LUNAR_INFO.iter().enumerate(){
+            let days=year_days(info);
+            if offset>=days{
+                offset-=days;
+                continue
+            }
+            for (month,is_leap_month,days) in months(info){
+                if offset<days{
+                    return Ok(LunarDate{year:FIRST_YEAR+i as i32,month,day:offset as u8+1,is_leap_month})
+                }
+                offset-=days;
+            }
+        }
+        Err(IllegalTimeError::YearNumberError)
+    }
+
+    /// Convert a Chinese lunisolar date to midnight UTC of the corresponding Gregorian date.
+    ///
+    /// Returns YearNumberError outside the supported years,
+    /// MonthNumberError for a leap month that the year does not have,
+    /// and DayNumberError for a day after the end of the month.
+    pub fn from_lunar(date:&LunarDate)->Result<UtcDatetime, IllegalTimeError>{
+        let year_info=info(date.year).ok_or(IllegalTimeError::YearNumberError)?;
+        let (y,m,d)=FIRST_NEW_YEAR;
+        let mut day=days_from_civil(y,m,d)+LUNAR_INFO[..(date.year-FIRST_YEAR) as usize].iter().map(|&i|year_days(i)).sum::<i64>();
+        for (month,is_leap_month,days) in months(year_info){
+            if (month,is_leap_month)==(date.month,date.is_leap_month){
+                if date.day==0 || date.day as i64>days{
+                    return Err(IllegalTimeError::DayNumberError)
+                }
+                let (year,month,day)=civil_from_days(day+date.day as i64-1);
+                return UtcDatetime::new(year as i32,month,day,0,0,0)
+            }
+            day+=days;
+        }
+        Err(IllegalTimeError::MonthNumberError)
+    }
+}
+
+/// The Gregorian date of the Spring Festival (lunar new year) of `year`, at midnight
+pub fn spring_festival(year:i32)->Result<UtcDatetime, IllegalTimeError>{
+    UtcDatetime::from_lunar(&LunarDate{year,month:1,day:1,is_leap_month:false})
+}
+
+#[cfg(test)]
+mod tests{
+    use super::{spring_festival, LunarDate};
+    use crate::UtcDatetime;
+
+    #[test]
+    fn round_trip(){
+        assert_eq!(spring_festival(1970).unwrap(),UtcDatetime::new(1970,2,6,0,0,0).unwrap());
+        assert_eq!(spring_festival(2100).unwrap(),UtcDatetime::new(2100,2,9,0,0,0).unwrap());
+        assert_eq!(UtcDatetime::UNIX_EPOCH.to_lunar().unwrap(),LunarDate{year:1969,month:11,day:24,is_leap_month:false});
+        assert_eq!(spring_festival(1900).unwrap(),UtcDatetime::new(1900,1,31,0,0,0).unwrap());
+        assert!(UtcDatetime::new(1900,1,30,0,0,0).unwrap().to_lunar().is_err());
+        assert!(UtcDatetime::MIN.to_lunar().is_err());
+        // 2023年有闰二月,2033年有闰十一月
+        let leap=LunarDate{year:2023,month:2,day:1,is_leap_month:true};
+        assert_eq!(UtcDatetime::from_lunar(&leap).unwrap(),UtcDatetime::new(2023,3,22,0,0,0).unwrap());
+        let leap=LunarDate{year:2033,month:11,day:1,is_leap_month:true};
+        assert_eq!(UtcDatetime::from_lunar(&leap).unwrap(),UtcDatetime::new(2033,12,22,0,0,0).unwrap());
+        assert!(UtcDatetime::from_lunar(&LunarDate{year:2021,month:2,day:1,is_leap_month:true}).is_err());
+        assert!(UtcDatetime::new(2101,1,29,0,0,0).unwrap().to_lunar().is_err());
+        assert_eq!(UtcDatetime::new(2101,1,28,0,0,0).unwrap().to_lunar().unwrap().year,2100);
+        let mut dt=UtcDatetime::new(1900,1,31,0,0,0).unwrap();
+        while dt.year()<2101{
+            assert_eq!(UtcDatetime::from_lunar(&dt.to_lunar().unwrap()).unwrap(),dt);
+            dt=dt.add_duration(crate::Duration::days(11)).unwrap();
+        }
+    }
+}
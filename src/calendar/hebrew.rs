@@ -0,0 +1,244 @@
+//! The Hebrew calendar, enabled with the `hebrew` feature.
+//!
+//! Years are counted from the creation epoch (anno mundi) and begin on 1 Tishri, which is
+//! moved by the traditional postponement rules. Leap years add the month Adar I, so that
+//! Adar is called Adar II in those years.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::calendar::hebrew::{HebrewDate, HebrewMonth};
+//! let a_date=UtcDatetime::new(2021,9,7,9,30,0).unwrap();
+//! assert_eq!(a_date.to_hebrew().unwrap(),HebrewDate{year:5782,month:HebrewMonth::Tishri,day:1});
+//! let purim=HebrewDate{year:5784,month:HebrewMonth::AdarII,day:14};
+//! assert_eq!(UtcDatetime::from_hebrew(&purim).unwrap(),UtcDatetime::new(2024,3,24,0,0,0).unwrap());
+//! assert_eq!(purim.month.name(),"Adar II");
+//! ```
+use crate::{civil_from_days, days_from_civil, IllegalTimeError, UtcDatetime};
+
+// 1 Tishri AM 1(儒略历公元前3761年10月7日)距1970年1月1日的天数
+const EPOCH:i64=-2_092_590;
+// 一个朔望月为29天12小时793时分,1小时分为1080时分
+const PARTS_PER_DAY:i64=25_920;
+
+/// A month of the Hebrew calendar, in the order of the civil year starting with Tishri
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Hash,Debug)]
+pub enum HebrewMonth{
+    Tishri,
+    Heshvan,
+    Kislev,
+    Tevet,
+    Shevat,
+    /// The sixth month of a common year
+    Adar,
+    /// The inserted month of a leap year
+    AdarI,
+    /// The month Adar in a leap year
+    AdarII,
+    Nisan,
+    Iyar,
+    Sivan,
+    Tammuz,
+    Av,
+    Elul,
+}
+
+const COMMON_MONTHS:[HebrewMonth;12]={
+    use HebrewMonth::*;
+    [Tishri,Heshvan,Kislev,Tevet,Shevat,Adar,Nisan,Iyar,Sivan,Tammuz,Av,Elul]
+};
+const LEAP_MONTHS:[HebrewMonth;13]={
+    use HebrewMonth::*;
+    [Tishri,Heshvan,Kislev,Tevet,Shevat,AdarI,AdarII,Nisan,Iyar,Sivan,Tammuz,Av,Elul]
+};
+
+impl HebrewMonth{
+    /// The English transliteration of the month name
+    pub fn name(&self)->&'static str{
+        match self{
+            HebrewMonth::Tishri=>"Tishri",
+            HebrewMonth::Heshvan=>"Heshvan",
+            HebrewMonth::Kislev=>"Kislev",
+            HebrewMonth::Tevet=>"Tevet",
+            HebrewMonth::Shevat=>"Shevat",
+            HebrewMonth::Adar=>"Adar",
+            HebrewMonth::AdarI=>"Adar I",
+            HebrewMonth::AdarII=>"Adar II",
+            HebrewMonth::Nisan=>"Nisan",
+            HebrewMonth::Iyar=>"Iyar",
+            HebrewMonth::Sivan=>"Sivan",
+            HebrewMonth::Tammuz=>"Tammuz",
+            HebrewMonth::Av=>"Av",
+            HebrewMonth::Elul=>"Elul",
+        }
+    }
+
+    /// The month name in Hebrew script
+    pub fn hebrew_name(&self)->&'static str{
+        match self{
+            HebrewMonth::Tishri=>"תשרי",
+            HebrewMonth::Heshvan=>"חשוון",
+            HebrewMonth::Kislev=>"כסלו",
+            HebrewMonth::Tevet=>"טבת",
+            HebrewMonth::Shevat=>"שבט",
+            HebrewMonth::Adar=>"אדר",
+            HebrewMonth::AdarI=>"אדר א׳",
+            HebrewMonth::AdarII=>"אדר ב׳",
+            HebrewMonth::Nisan=>"ניסן",
+            HebrewMonth::Iyar=>"אייר",
+            HebrewMonth::Sivan=>"סיוון",
+            HebrewMonth::Tammuz=>"תמוז",
+            HebrewMonth::Av=>"אב",
+            HebrewMonth::Elul=>"אלול",
+        }
+    }
+}
+
+/// A date in the Hebrew calendar
+#[derive(Clone,Copy,PartialEq,Eq,Hash,Debug)]
+pub struct HebrewDate{
+    pub year:u32,
+    pub month:HebrewMonth,
+    pub day:u8,
+}
+
+/// Returns whether the Hebrew year has 13 months
+pub fn is_hebrew_leap_year(year:u32)->bool{
+    (7*year as u64+1)%19<7
+}
+
+// 从纪元到该年新年的天数,已包含月相推迟规则
+fn elapsed_days(year:i64)->i64{
+    let months=(235*year-234).div_euclid(19);
+    let parts=12_084+13_753*months;
+    let days=29*months+parts.div_euclid(PARTS_PER_DAY);
+    // 新年不能在周日、周三或周五
+    if (3*(days+1)).rem_euclid(7)<3 {days+1} else {days}
+}
+
+// 保证一年的长度只能是353-355或383-385天
+fn year_length_correction(year:i64)->i64{
+    let (before,this,after)=(elapsed_days(year-1),elapsed_days(year),elapsed_days(year+1));
+    if after-this==356 {2} else if this-before==382 {1} else {0}
+}
+
+fn new_year(year:i64)->i64{
+    EPOCH+elapsed_days(year)+year_length_correction(year)
+}
+
+/// The number of days in the Hebrew year, one of 353, 354, 355, 383, 384 and 385
+pub fn days_in_hebrew_year(year:u32)->u16{
+    (new_year(year as i64+1)-new_year(year as i64)) as u16
+}
+
+/// The number of days in a month of the Hebrew year, or 0 if the month does not occur that year
+pub fn days_in_hebrew_month(year:u32,month:HebrewMonth)->u8{
+    let leap=is_hebrew_leap_year(year);
+    match month{
+        HebrewMonth::Adar if leap=>0,
+        HebrewMonth::AdarI|HebrewMonth::AdarII if !leap=>0,
+        // 355和385天的年份Heshvan有30天,353和383天的年份Kislev只有29天
+        HebrewMonth::Heshvan if days_in_hebrew_year(year)%10==5=>30,
+        HebrewMonth::Kislev if days_in_hebrew_year(year)%10==3=>29,
+        HebrewMonth::Kislev=>30,
+        HebrewMonth::Tishri|HebrewMonth::Shevat|HebrewMonth::AdarI|HebrewMonth::Nisan|HebrewMonth::Sivan|HebrewMonth::Av=>30,
+        _=>29,
+    }
+}
+
+fn months_of(year:u32)->&'static [HebrewMonth]{
+    if is_hebrew_leap_year(year) {&LEAP_MONTHS} else {&COMMON_MONTHS}
+}
+
+impl UtcDatetime{
+    /// Convert the date to the Hebrew calendar, ignoring the time.
+    ///
+    /// The Hebrew day begins at sunset of the previous evening, this returns the date
+    /// that is current at noon. Returns YearNumberError for dates before 1 Tishri AM 1.
+    pub fn to_hebrew(&self)->Result<HebrewDate, IllegalTimeError>{
+        let days=days_from_civil(self.year as i64,self.month,self.day);
+        if days<EPOCH{
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        // 平均年长约365.2468天,估算后再修正
+        let mut year=((days-EPOCH)*98_496).div_euclid(35_975_351)+1;
+        while new_year(year+1)<=days{
+            year+=1;
+        }
+        while new_year(year)>days{
+            year-=1;
+        }
+        let year=year as u32;
+        let mut rest=days-new_year(year as i64);
+        for &month in months_of(year){
+            let length=days_in_hebrew_month(year,month) as i64;
+            if rest<length{
+                return Ok(HebrewDate{year,month,day:rest as u8+1})
+            }
+            rest-=length;
+        }
+        unreachable!()
+    }
+
+    /// Convert a Hebrew date to midnight UTC of the corresponding Gregorian date.
+    ///
+    /// Returns MonthNumberError for Adar in a leap year or Adar I and Adar II in a common year,
+    /// DayNumberError for a day the month does not have, and YearNumberError for dates after [`UtcDatetime::MAX`].
+    pub fn from_hebrew(date:&HebrewDate)->Result<UtcDatetime, IllegalTimeError>{
+        let length=days_in_hebrew_month(date.year,date.month);
+        if length==0{
+            return Err(IllegalTimeError::MonthNumberError)
+        }
+        if date.day==0 || date.day>length{
+            return Err(IllegalTimeError::DayNumberError)
+        }
+        let mut days=new_year(date.year as i64)+date.day as i64-1;
+        days+=months_of(date.year).iter()
+            .take_while(|&&month|month!=date.month)
+            .map(|&month|days_in_hebrew_month(date.year,month) as i64)
+            .sum::<i64>();
+        let (year,month,day)=civil_from_days(days);
+        UtcDatetime::new(i32::try_from(year).map_err(|_|IllegalTimeError::YearNumberError)?,month,day,0,0,0)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::{days_in_hebrew_year, HebrewDate, HebrewMonth};
+    use crate::UtcDatetime;
+
+    #[test]
+    fn known_dates(){
+        let date=|y,m,d|UtcDatetime::new(y,m,d,0,0,0).unwrap();
+        assert_eq!(UtcDatetime::UNIX_EPOCH.to_hebrew().unwrap(),HebrewDate{year:5730,month:HebrewMonth::Tevet,day:23});
+        let creation=UtcDatetime::from_hebrew(&HebrewDate{year:1,month:HebrewMonth::Tishri,day:1}).unwrap();
+        assert_eq!(creation,UtcDatetime::new(-3760,9,7,0,0,0).unwrap());
+        assert!(creation.add_duration(crate::Duration::days(-1)).unwrap().to_hebrew().is_err());
+        assert!(UtcDatetime::MIN.to_hebrew().is_err());
+        for (hebrew,gregorian) in [
+            (HebrewDate{year:5781,month:HebrewMonth::Nisan,day:15},date(2021,3,28)),
+            (HebrewDate{year:5784,month:HebrewMonth::Tishri,day:10},date(2023,9,25)),
+            (HebrewDate{year:5784,month:HebrewMonth::Nisan,day:15},date(2024,4,23)),
+            (HebrewDate{year:5785,month:HebrewMonth::Tishri,day:1},date(2024,10,3)),
+        ]{
+            assert_eq!(gregorian.to_hebrew().unwrap(),hebrew);
+            assert_eq!(UtcDatetime::from_hebrew(&hebrew).unwrap(),gregorian);
+        }
+        assert_eq!(UtcDatetime::from_hebrew(&HebrewDate{year:5730,month:HebrewMonth::Tishri,day:1}).unwrap(),date(1969,9,13));
+        assert!(UtcDatetime::from_hebrew(&HebrewDate{year:5784,month:HebrewMonth::Adar,day:1}).is_err());
+        assert!(UtcDatetime::from_hebrew(&HebrewDate{year:5785,month:HebrewMonth::AdarII,day:1}).is_err());
+        assert!(UtcDatetime::from_hebrew(&HebrewDate{year:5785,month:HebrewMonth::Iyar,day:30}).is_err());
+    }
+
+    #[test]
+    fn round_trip(){
+        let mut dt=UtcDatetime::new(-3760,9,7,0,0,0).unwrap();
+        while dt.year()<2100{
+            let hebrew=dt.to_hebrew().unwrap();
+            assert!([353,354,355,383,384,385].contains(&days_in_hebrew_year(hebrew.year)));
+            assert_eq!(UtcDatetime::from_hebrew(&hebrew).unwrap(),dt);
+            dt=dt.add_duration(crate::Duration::days(if dt.year()<1900 {61} else {3})).unwrap();
+        }
+        let hebrew=UtcDatetime::MAX.to_hebrew().unwrap();
+        assert_eq!(UtcDatetime::from_hebrew(&hebrew).unwrap(),UtcDatetime::new(999_999,12,31,0,0,0).unwrap());
+    }
+}
@@ -0,0 +1,188 @@
+//! The tabular Islamic (Hijri) calendar, enabled with the `hijri` feature.
+//!
+//! Months alternate between 30 and 29 days, and the twelfth month has 30 days in the
+//! 11 leap years of each 30-year cycle. Which years are leap years and which day the
+//! calendar starts on differ between traditions and are selected with [`HijriCalendar`],
+//! the default matches ICU's `islamic-civil`.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::calendar::hijri::{HijriCalendar, HijriDate};
+//! let calendar=HijriCalendar::default();
+//! let a_date=UtcDatetime::new(2021,4,13,9,30,0).unwrap();
+//! assert_eq!(a_date.to_hijri(&calendar).unwrap(),HijriDate{year:1442,month:9,day:1});
+//! assert_eq!(UtcDatetime::from_hijri(&HijriDate{year:1442,month:9,day:1},&calendar).unwrap(),
+//!     UtcDatetime::new(2021,4,13,0,0,0).unwrap());
+//! ```
+use crate::{civil_from_days, days_from_civil, IllegalTimeError, UtcDatetime};
+
+// 30年一个周期:19个354天的平年和11个355天的闰年
+const CYCLE_DAYS:i64=30*354+11;
+// 公历622年7月19日(儒略历7月16日)距1970年1月1日的天数
+const CIVIL_EPOCH:i64=-492_148;
+
+/// Which day is 1 Muharram 1 AH
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub enum HijriEpoch{
+    /// Friday, 16 July 622 (Julian)
+    #[default]
+    Civil,
+    /// Thursday, 15 July 622 (Julian), one day earlier
+    Astronomical,
+}
+
+/// Which years of the 30-year cycle are leap years
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub enum HijriLeapScheme{
+    /// 2, 5, 7, 10, 13, 16, 18, 21, 24, 26, 29, the most common scheme
+    #[default]
+    Standard,
+    /// 2, 5, 7, 10, 13, 15, 18, 21, 24, 26, 29
+    Year15,
+    /// 2, 5, 8, 10, 13, 16, 19, 21, 24, 27, 29, used by the Fatimid calendar
+    Fatimid,
+    /// 2, 5, 8, 11, 13, 16, 19, 21, 24, 27, 30, attributed to Habash al-Hasib
+    HabashAlHasib,
+}
+
+impl HijriLeapScheme{
+    fn leap_years(&self)->[u8;11]{
+        match self{
+            HijriLeapScheme::Standard=>[2,5,7,10,13,16,18,21,24,26,29],
+            HijriLeapScheme::Year15=>[2,5,7,10,13,15,18,21,24,26,29],
+            HijriLeapScheme::Fatimid=>[2,5,8,10,13,16,19,21,24,27,29],
+            HijriLeapScheme::HabashAlHasib=>[2,5,8,11,13,16,19,21,24,27,30],
+        }
+    }
+}
+
+/// The variant of the tabular Islamic calendar
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct HijriCalendar{
+    pub epoch:HijriEpoch,
+    pub leap_scheme:HijriLeapScheme,
+}
+
+/// A date in the tabular Islamic calendar
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Hash,Debug)]
+pub struct HijriDate{
+    pub year:u32,
+    /// The month, 1 (Muharram) to 12 (Dhu al-Hijjah)
+    pub month:u8,
+    pub day:u8,
+}
+
+impl HijriCalendar{
+    fn epoch_day(&self)->i64{
+        match self.epoch{
+            HijriEpoch::Civil=>CIVIL_EPOCH,
+            HijriEpoch::Astronomical=>CIVIL_EPOCH-1,
+        }
+    }
+
+    /// Returns whether `year` has 355 days
+    pub fn is_leap_year(&self,year:u32)->bool{
+        year>0 && self.leap_scheme.leap_years().contains(&(((year-1)%30+1) as u8))
+    }
+
+    /// The number of days in a month, 29 or 30
+    pub fn days_in_month(&self,year:u32,month:u8)->u8{
+        if month%2==1 || month==12 && self.is_leap_year(year) {30} else {29}
+    }
+
+    // 某个周期内前n年的天数
+    fn days_before_year_in_cycle(&self,years:u32)->i64{
+        let leap=self.leap_scheme.leap_years().iter().filter(|&&y|(y as u32)<=years).count() as i64;
+        years as i64*354+leap
+    }
+}
+
+impl UtcDatetime{
+    /// Convert the date to the tabular Islamic calendar, ignoring the time.
+    ///
+    /// Returns YearNumberError for dates before 1 Muharram 1 AH.
+    pub fn to_hijri(&self,calendar:&HijriCalendar)->Result<HijriDate, IllegalTimeError>{
+        let days=days_from_civil(self.year as i64,self.month,self.day)-calendar.epoch_day();
+        if days<0{
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        let cycle=days.div_euclid(CYCLE_DAYS);
+        let mut rest=days.rem_euclid(CYCLE_DAYS);
+        let mut year=(cycle*30+1) as u32;
+        loop{
+            let length=if calendar.is_leap_year(year) {355} else {354};
+            if rest<length{
+                break
+            }
+            rest-=length;
+            year+=1;
+        }
+        let mut month=1;
+        loop{
+            let length=calendar.days_in_month(year,month) as i64;
+            if rest<length{
+                break
+            }
+            rest-=length;
+            month+=1;
+        }
+        Ok(HijriDate{year,month,day:rest as u8+1})
+    }
+
+    /// Convert a tabular Islamic date to midnight UTC of the corresponding Gregorian date.
+    ///
+    /// Returns the error for the first invalid field, YearNumberError also for dates after [`UtcDatetime::MAX`].
+    pub fn from_hijri(date:&HijriDate,calendar:&HijriCalendar)->Result<UtcDatetime, IllegalTimeError>{
+        if date.year==0{
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        if date.month==0 || date.month>12{
+            return Err(IllegalTimeError::MonthNumberError)
+        }
+        if date.day==0 || date.day>calendar.days_in_month(date.year,date.month){
+            return Err(IllegalTimeError::DayNumberError)
+        }
+        let cycles=(date.year-1)/30;
+        let mut days=calendar.epoch_day()+cycles as i64*CYCLE_DAYS+calendar.days_before_year_in_cycle((date.year-1)%30);
+        days+=(1..date.month).map(|month|calendar.days_in_month(date.year,month) as i64).sum::<i64>();
+        days+=date.day as i64-1;
+        let (year,month,day)=civil_from_days(days);
+        UtcDatetime::new(i32::try_from(year).map_err(|_|IllegalTimeError::YearNumberError)?,month,day,0,0,0)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::{HijriCalendar, HijriDate, HijriEpoch, HijriLeapScheme};
+    use crate::UtcDatetime;
+
+    #[test]
+    fn conversions(){
+        let civil=HijriCalendar::default();
+        assert_eq!(UtcDatetime::UNIX_EPOCH.to_hijri(&civil).unwrap(),HijriDate{year:1389,month:10,day:22});
+        assert_eq!(UtcDatetime::new(622,7,19,0,0,0).unwrap().to_hijri(&civil).unwrap(),HijriDate{year:1,month:1,day:1});
+        assert!(UtcDatetime::new(622,7,18,0,0,0).unwrap().to_hijri(&civil).is_err());
+        assert!(UtcDatetime::MIN.to_hijri(&civil).is_err());
+        let mut dt=UtcDatetime::new(622,7,19,0,0,0).unwrap();
+        while dt.year()<2200{
+            let hijri=dt.to_hijri(&civil).unwrap();
+            // Calendrical Calculations中islamic-civil的公式
+            let (y,m,d)=(hijri.year as i64,hijri.month as i64,hijri.day as i64);
+            let jdn=1948439+(y-1)*354+(3+11*y)/30+29*(m-1)+m/2+d;
+            assert_eq!(jdn-2440588,dt.unix_seconds().div_euclid(86_400));
+            assert_eq!(UtcDatetime::from_hijri(&hijri,&civil).unwrap(),dt);
+            for leap_scheme in [HijriLeapScheme::Year15,HijriLeapScheme::Fatimid,HijriLeapScheme::HabashAlHasib]{
+                let calendar=HijriCalendar{epoch:HijriEpoch::Astronomical,leap_scheme};
+                assert_eq!(UtcDatetime::from_hijri(&dt.to_hijri(&calendar).unwrap(),&calendar).unwrap(),dt);
+            }
+            dt=dt.add_duration(crate::Duration::days(if dt.year()<1900 {97} else {5})).unwrap();
+        }
+        let hijri=UtcDatetime::MAX.to_hijri(&civil).unwrap();
+        assert!(hijri.year>UtcDatetime::MAX_YEAR as u32);
+        assert_eq!(UtcDatetime::from_hijri(&hijri,&civil).unwrap(),UtcDatetime::new(999_999,12,31,0,0,0).unwrap());
+        assert!(UtcDatetime::from_hijri(&HijriDate{year:hijri.year+1,month:1,day:1},&civil).is_err());
+        assert!(UtcDatetime::from_hijri(&HijriDate{year:1442,month:2,day:30},&civil).is_err());
+        let before_1970=UtcDatetime::from_hijri(&HijriDate{year:1300,month:1,day:1},&civil).unwrap();
+        assert_eq!((before_1970.year(),before_1970.to_hijri(&civil).unwrap()),(1882,HijriDate{year:1300,month:1,day:1}));
+    }
+}
@@ -0,0 +1,148 @@
+//! Ready-made holiday calendars, enabled with the `holidays` feature.
+//!
+//! The rules are the regular ones, applied to every year. One-off holidays such as royal
+//! jubilees, and the history of when each holiday was introduced, are not modelled
+//! except where noted.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::calendar::holidays::UsFederal;
+//! // Memorial Day 2021 is Monday May 31, the last Monday in May
+//! let friday=UtcDatetime::new(2021,5,28,9,30,0).unwrap();
+//! assert_eq!(friday.add_business_days(1,&UsFederal).unwrap(),UtcDatetime::new(2021,6,1,9,30,0).unwrap());
+//! ```
+use super::{easter_day, HolidayCalendar};
+use crate::{days_from_civil, days_of_the_month, UtcDatetime};
+
+/// United States federal holidays, with a holiday on Saturday observed on the Friday before
+/// and a holiday on Sunday observed on the Monday after. Juneteenth is included from 2021.
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct UsFederal;
+
+/// Bank holidays in England and Wales, with substitute days when a holiday falls on a weekend
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct UkBankHolidays;
+
+/// Closing days of the TARGET2 payment system: New Year's Day, Good Friday, Easter Monday,
+/// 1 May, Christmas Day and 26 December
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct Target2;
+
+impl HolidayCalendar for UsFederal{
+    fn is_holiday(&self,date:&UtcDatetime)->bool{
+        let year=date.year as i64;
+        let day=day_number(date);
+        // 12月31日可能是下一年元旦的调休日
+        if observed(year+1,1,1)==day{
+            return true
+        }
+        let fixed=[(1,1),(7,4),(11,11),(12,25)];
+        fixed.iter().any(|&(month,d)|observed(year,month,d)==day)
+            || year>=2021 && observed(year,6,19)==day
+            || [
+                nth_weekday(year,1,1,3),
+                nth_weekday(year,2,1,3),
+                last_weekday(year,5,1),
+                nth_weekday(year,9,1,1),
+                nth_weekday(year,10,1,2),
+                nth_weekday(year,11,4,4),
+            ].contains(&day)
+    }
+}
+
+impl HolidayCalendar for UkBankHolidays{
+    fn is_holiday(&self,date:&UtcDatetime)->bool{
+        let year=date.year as i64;
+        let day=day_number(date);
+        let easter=easter_day(year);
+        let new_year=days_from_civil(year,1,1);
+        let christmas=days_from_civil(year,12,25);
+        // 圣诞节和节礼日落在周末时依次顺延到下一个工作日
+        let christmas_observed=substitute(christmas);
+        let boxing_day=substitute(christmas+1);
+        let boxing_day_observed=if boxing_day==christmas_observed {boxing_day+1} else {boxing_day};
+        [
+            substitute(new_year),
+            easter-2,
+            easter+1,
+            nth_weekday(year,5,1,1),
+            last_weekday(year,5,1),
+            last_weekday(year,8,1),
+            christmas_observed,
+            boxing_day_observed,
+        ].contains(&day)
+    }
+}
+
+impl HolidayCalendar for Target2{
+    fn is_holiday(&self,date:&UtcDatetime)->bool{
+        let year=date.year as i64;
+        let easter=easter_day(year);
+        matches!((date.month,date.day),(1,1)|(5,1)|(12,25)|(12,26)) || [easter-2,easter+1].contains(&day_number(date))
+    }
+}
+
+fn day_number(date:&UtcDatetime)->i64{
+    days_from_civil(date.year as i64,date.month,date.day)
+}
+
+fn weekday(day:i64)->i64{
+    (4+day).rem_euclid(7)
+}
+
+// 星期六提前到星期五,星期天推迟到星期一
+fn observed(year:i64,month:u8,day:u8)->i64{
+    let day=days_from_civil(year,month,day);
+    match weekday(day){
+        6=>day-1,
+        0=>day+1,
+        _=>day,
+    }
+}
+
+// 周末推迟到下一个星期一
+fn substitute(day:i64)->i64{
+    match weekday(day){
+        6=>day+2,
+        0=>day+1,
+        _=>day,
+    }
+}
+
+// 某月第n个星期几(星期天为0)
+fn nth_weekday(year:i64,month:u8,weekday_of:i64,n:i64)->i64{
+    let first=days_from_civil(year,month,1);
+    first+(weekday_of-weekday(first)).rem_euclid(7)+(n-1)*7
+}
+
+fn last_weekday(year:i64,month:u8,weekday_of:i64)->i64{
+    let last=days_from_civil(year,month,days_of_the_month(year as i32,month));
+    last-(weekday(last)-weekday_of).rem_euclid(7)
+}
+
+#[cfg(test)]
+mod tests{
+    use super::{Target2, UkBankHolidays, UsFederal};
+    use crate::calendar::HolidayCalendar;
+    use crate::UtcDatetime;
+
+    fn holidays(calendar:&dyn HolidayCalendar,year:i32)->Vec<(u8,u8)>{
+        let mut dt=UtcDatetime::new(year,1,1,0,0,0).unwrap();
+        let mut found=Vec::new();
+        while dt.year()==year{
+            if calendar.is_holiday(&dt){
+                found.push((dt.month(),dt.day()));
+            }
+            dt=UtcDatetime::from_timestamp(dt.timestamp().unwrap()+86_400);
+        }
+        found
+    }
+
+    #[test]
+    fn published_dates(){
+        assert_eq!(holidays(&UsFederal,2021),[(1,1),(1,18),(2,15),(5,31),(6,18),(7,5),(9,6),(10,11),(11,11),(11,25),(12,24),(12,31)]);
+        assert_eq!(holidays(&UkBankHolidays,2021),[(1,1),(4,2),(4,5),(5,3),(5,31),(8,30),(12,27),(12,28)]);
+        assert_eq!(holidays(&UkBankHolidays,2023),[(1,2),(4,7),(4,10),(5,1),(5,29),(8,28),(12,25),(12,26)]);
+        assert_eq!(holidays(&Target2,2024),[(1,1),(3,29),(4,1),(5,1),(12,25),(12,26)]);
+    }
+}
@@ -0,0 +1,86 @@
+//! Japanese era (元号) dates.
+//!
+//! The eras from Meiji to Reiwa are known. Meiji is counted from 1868, but only dates from
+//! 1873-01-01, when Japan adopted the Gregorian calendar, can be converted.
+//! A later era keeps counting years in Reiwa until it is added to the table.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::calendar::japanese::JapaneseEra;
+//! let a_date=UtcDatetime::new(2021,11,15,0,0,0).unwrap();
+//! assert_eq!(a_date.japanese_era().unwrap(),(JapaneseEra::Reiwa,3));
+//! assert_eq!(UtcDatetime::new(2019,4,30,0,0,0).unwrap().japanese_era().unwrap(),(JapaneseEra::Heisei,31));
+//! assert!(UtcDatetime::new(1872,12,31,0,0,0).unwrap().japanese_era().is_err());
+//! ```
+use crate::{IllegalTimeError, UtcDatetime};
+
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Hash,Debug)]
+pub enum JapaneseEra{
+    Meiji,
+    Taisho,
+    Showa,
+    Heisei,
+    Reiwa,
+}
+
+// 每个年号的开始日期和元年,明治只从改用公历的1873年1月1日开始
+const ERAS:[(JapaneseEra,(i32,u8,u8),i32);5]=[
+    (JapaneseEra::Meiji,(1873,1,1),1868),
+    (JapaneseEra::Taisho,(1912,7,30),1912),
+    (JapaneseEra::Showa,(1926,12,25),1926),
+    (JapaneseEra::Heisei,(1989,1,8),1989),
+    (JapaneseEra::Reiwa,(2019,5,1),2019),
+];
+
+impl JapaneseEra{
+    /// The name in kanji, such as `令和`
+    pub fn name(&self)->&'static str{
+        match self{
+            JapaneseEra::Meiji=>"明治",
+            JapaneseEra::Taisho=>"大正",
+            JapaneseEra::Showa=>"昭和",
+            JapaneseEra::Heisei=>"平成",
+            JapaneseEra::Reiwa=>"令和",
+        }
+    }
+
+    /// The first date that can be converted to the era, for Meiji this is 1873-01-01 instead of its beginning in 1868
+    pub fn start(&self)->(i32,u8,u8){
+        ERAS[*self as usize].1
+    }
+}
+
+impl UtcDatetime{
+    /// The Japanese era and the year within it, starting from 1.
+    ///
+    /// Returns YearNumberError for dates before 1873-01-01.
+    pub fn japanese_era(&self)->Result<(JapaneseEra,i32), IllegalTimeError>{
+        let date=(self.year,self.month,self.day);
+        let (era,_,first_year)=ERAS.iter().rev().copied().find(|&(_,start,_)|date>=start).ok_or(IllegalTimeError::YearNumberError)?;
+        Ok((era,self.year-first_year+1))
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::JapaneseEra;
+    use crate::{IllegalTimeError, UtcDatetime};
+
+    fn era(year:i32,month:u8,day:u8)->Result<(JapaneseEra,i32), IllegalTimeError>{
+        UtcDatetime::new(year,month,day,0,0,0).unwrap().japanese_era()
+    }
+
+    #[test]
+    fn era_boundaries(){
+        assert_eq!(era(1873,1,1).unwrap(),(JapaneseEra::Meiji,6));
+        assert_eq!(era(1912,7,29).unwrap(),(JapaneseEra::Meiji,45));
+        assert_eq!(era(1912,7,30).unwrap(),(JapaneseEra::Taisho,1));
+        assert_eq!(era(1926,12,24).unwrap(),(JapaneseEra::Taisho,15));
+        assert_eq!(era(1926,12,25).unwrap(),(JapaneseEra::Showa,1));
+        assert_eq!(era(1989,1,7).unwrap(),(JapaneseEra::Showa,64));
+        assert_eq!(era(999_999,12,31).unwrap(),(JapaneseEra::Reiwa,997_981));
+        for (year,month,day) in [(1872,12,31),(1868,10,23),(1,1,1),(-999_999,1,1)]{
+            assert!(matches!(era(year,month,day),Err(IllegalTimeError::YearNumberError)));
+        }
+    }
+}
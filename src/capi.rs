@@ -0,0 +1,126 @@
+//! C API, enabled with the `capi` feature.
+//!
+//! Build a C library with `cargo rustc --release --features capi --crate-type staticlib`
+//! (or `cdylib`), and generate the header with `cbindgen --config cbindgen.toml`.
+//! Every function returns `UTC_DATETIME_OK` (0) on success or a negative error code.
+use crate::{IllegalTimeError, UtcDatetime};
+use std::os::raw::{c_char, c_int};
+
+pub const UTC_DATETIME_OK:c_int=0;
+pub const UTC_DATETIME_YEAR_ERROR:c_int=-1;
+pub const UTC_DATETIME_MONTH_ERROR:c_int=-2;
+pub const UTC_DATETIME_DAY_ERROR:c_int=-3;
+pub const UTC_DATETIME_HOUR_ERROR:c_int=-4;
+pub const UTC_DATETIME_MINUTE_ERROR:c_int=-5;
+pub const UTC_DATETIME_SECOND_ERROR:c_int=-6;
+pub const UTC_DATETIME_TIME_STRING_ERROR:c_int=-7;
+pub const UTC_DATETIME_FORMAT_DESCRIPTION_ERROR:c_int=-8;
+pub const UTC_DATETIME_TRAILING_CHARACTERS_ERROR:c_int=-9;
+/// A pointer argument was NULL
+pub const UTC_DATETIME_NULL_POINTER:c_int=-100;
+/// The output buffer is too small
+pub const UTC_DATETIME_BUFFER_TOO_SMALL:c_int=-101;
+
+fn error_code(err:IllegalTimeError)->c_int{
+    match err{
+        IllegalTimeError::YearNumberError=>UTC_DATETIME_YEAR_ERROR,
+        IllegalTimeError::MonthNumberError=>UTC_DATETIME_MONTH_ERROR,
+        IllegalTimeError::DayNumberError=>UTC_DATETIME_DAY_ERROR,
+        IllegalTimeError::HourNumberError=>UTC_DATETIME_HOUR_ERROR,
+        IllegalTimeError::MinuteNumberError=>UTC_DATETIME_MINUTE_ERROR,
+        IllegalTimeError::SecondNumberError=>UTC_DATETIME_SECOND_ERROR,
+        IllegalTimeError::TimeStringError=>UTC_DATETIME_TIME_STRING_ERROR,
+        IllegalTimeError::FormatDescriptionError=>UTC_DATETIME_FORMAT_DESCRIPTION_ERROR,
+        IllegalTimeError::TrailingCharactersError=>UTC_DATETIME_TRAILING_CHARACTERS_ERROR,
+    }
+}
+
+/// Validate the fields and write a new datetime to `out`.
+///
+/// # Safety
+/// `out` must be NULL or point to writable memory for one `UtcDatetime`.
+#[no_mangle]
+pub unsafe extern "C" fn utc_datetime_new(year:i32,month:u8,day:u8,hour:u8,minute:u8,second:u8,out:*mut UtcDatetime)->c_int{
+    if out.is_null(){
+        return UTC_DATETIME_NULL_POINTER
+    }
+    match UtcDatetime::new(year,month,day,hour,minute,second){
+        Ok(dt)=>{
+            out.write(dt);
+            UTC_DATETIME_OK
+        }
+        Err(e)=>error_code(e)
+    }
+}
+
+/// Write the number of seconds since January 1, 1970 to `out`.
+///
+/// The seconds are a `uint32_t`, returns `UTC_DATETIME_YEAR_ERROR` before 1970 or after 2106-02-07 06:28:15.
+///
+/// # Safety
+/// `dt` must be NULL or point to a datetime created by `utc_datetime_new`,
+/// `out` must be NULL or point to a writable `uint32_t`.
+#[no_mangle]
+pub unsafe extern "C" fn utc_datetime_timestamp(dt:*const UtcDatetime,out:*mut u32)->c_int{
+    if dt.is_null() || out.is_null(){
+        return UTC_DATETIME_NULL_POINTER
+    }
+    match (*dt).timestamp(){
+        Ok(ts)=>{
+            out.write(ts);
+            UTC_DATETIME_OK
+        }
+        Err(e)=>error_code(e)
+    }
+}
+
+/// Write `YYYY-MM-DD HH:MM:SS` and a terminating NUL into `buf`.
+///
+/// Years before 0 start with `-` and years after 9999 have more digits, so the text is up to 22 bytes
+/// and `len` must be at least 23 to fit any datetime with the NUL.
+/// Returns the number of bytes written, not counting the NUL.
+///
+/// # Safety
+/// `dt` must be NULL or point to a datetime created by `utc_datetime_new`,
+/// `buf` must be NULL or point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn utc_datetime_format(dt:*const UtcDatetime,buf:*mut c_char,len:usize)->c_int{
+    if dt.is_null() || buf.is_null(){
+        return UTC_DATETIME_NULL_POINTER
+    }
+    let text=(*dt).to_string();
+    if text.len()+1>len{
+        return UTC_DATETIME_BUFFER_TOO_SMALL
+    }
+    core::ptr::copy_nonoverlapping(text.as_ptr() as *const c_char,buf,text.len());
+    buf.add(text.len()).write(0);
+    text.len() as c_int
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use core::mem::MaybeUninit;
+    use std::ffi::CStr;
+
+    #[test]
+    fn round_trip(){
+        let mut dt=MaybeUninit::<UtcDatetime>::uninit();
+        assert_eq!(unsafe{utc_datetime_new(2020,2,30,0,0,0,dt.as_mut_ptr())},UTC_DATETIME_DAY_ERROR);
+        assert_eq!(unsafe{utc_datetime_new(2020,2,2,2,2,2,dt.as_mut_ptr())},UTC_DATETIME_OK);
+        let mut ts=0;
+        assert_eq!(unsafe{utc_datetime_timestamp(dt.as_ptr(),&mut ts)},UTC_DATETIME_OK);
+        assert_eq!(ts,1580608922);
+        let mut buf=[0 as c_char;20];
+        assert_eq!(unsafe{utc_datetime_format(dt.as_ptr(),buf.as_mut_ptr(),19)},UTC_DATETIME_BUFFER_TOO_SMALL);
+        assert_eq!(unsafe{utc_datetime_format(dt.as_ptr(),buf.as_mut_ptr(),buf.len())},19);
+        let text=unsafe{CStr::from_ptr(buf.as_ptr())};
+        assert_eq!(text.to_str().unwrap(),"2020-02-02 02:02:02");
+        assert_eq!(unsafe{utc_datetime_new(-999_999,1,1,0,0,0,dt.as_mut_ptr())},UTC_DATETIME_OK);
+        assert_eq!(unsafe{utc_datetime_timestamp(dt.as_ptr(),&mut ts)},UTC_DATETIME_YEAR_ERROR);
+        let mut buf=[0 as c_char;23];
+        assert_eq!(unsafe{utc_datetime_format(dt.as_ptr(),buf.as_mut_ptr(),buf.len())},22);
+        assert_eq!(unsafe{CStr::from_ptr(buf.as_ptr())}.to_str().unwrap(),"-999999-01-01 00:00:00");
+        assert_eq!(unsafe{utc_datetime_new(1_000_000,1,1,0,0,0,dt.as_mut_ptr())},UTC_DATETIME_YEAR_ERROR);
+    }
+}
@@ -0,0 +1,161 @@
+//! CBOR standard datetimes (RFC 8949 tags 0 and 1), enabled with the `cbor` feature.
+//!
+//! Tag 0 wraps an RFC 3339 string and tag 1 the seconds since 1970 as an integer or float.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+//! assert_eq!(a_date.to_cbor_epoch(),[0xc1,0x1a,0x61,0x92,0x28,0x98]);
+//! assert_eq!(UtcDatetime::from_cbor(&a_date.to_cbor_rfc3339()).unwrap(),(a_date,22));
+//! ```
+use crate::{IllegalTimeError, UtcDatetime};
+
+const TAG_RFC3339:u8=0xc0;
+const TAG_EPOCH:u8=0xc1;
+
+// 写入主类型和参数,参数使用最短的编码
+fn write_head(bytes:&mut Vec<u8>,major:u8,argument:u64){
+    let major=major<<5;
+    match argument{
+        0..=23=>bytes.push(major|argument as u8),
+        24..=0xff=>bytes.extend([major|24,argument as u8]),
+        0x100..=0xffff=>{
+            bytes.push(major|25);
+            bytes.extend((argument as u16).to_be_bytes());
+        },
+        0x1_0000..=0xffff_ffff=>{
+            bytes.push(major|26);
+            bytes.extend((argument as u32).to_be_bytes());
+        },
+        _=>{
+            bytes.push(major|27);
+            bytes.extend(argument.to_be_bytes());
+        },
+    }
+}
+
+// 读取主类型,附加信息和参数,返回(主类型,附加信息,参数,长度)
+fn read_head(bytes:&[u8])->Option<(u8,u8,u64,usize)>{
+    let first=*bytes.first()?;
+    let (major,info)=(first>>5,first&0x1f);
+    let len=match info{
+        0..=23=>return Some((major,info,info as u64,1)),
+        24=>1,
+        25=>2,
+        26=>4,
+        27=>8,
+        _=>return None,
+    };
+    let mut argument=[0;8];
+    argument[8-len..].copy_from_slice(bytes.get(1..1+len)?);
+    Some((major,info,u64::from_be_bytes(argument),1+len))
+}
+
+// IEEE 754半精度浮点数
+fn f16_to_f64(bits:u16)->f64{
+    let exponent=(bits>>10&0x1f) as i32;
+    let mantissa=(bits&0x3ff) as f64;
+    let value=match exponent{
+        0=>mantissa*2f64.powi(-24),
+        31=>if mantissa==0.0 {f64::INFINITY} else {f64::NAN},
+        _=>(mantissa+1024.0)*2f64.powi(exponent-25),
+    };
+    if bits&0x8000!=0 {-value} else {value}
+}
+
+impl UtcDatetime{
+    /// Encode as tag 1 with the seconds since 1970 as an integer, negative before 1970
+    pub fn to_cbor_epoch(&self)->Vec<u8>{
+        let mut bytes=vec![TAG_EPOCH];
+        let seconds=self.unix_seconds();
+        // 负整数(主类型1)存储-1-n
+        if seconds<0 {write_head(&mut bytes,1,(-1-seconds) as u64)} else {write_head(&mut bytes,0,seconds as u64)}
+        bytes
+    }
+
+    /// Encode as tag 0 with an RFC 3339 string such as `2021-11-15T09:30:00Z`.
+    ///
+    /// RFC 3339 has four-digit years, prefer [`UtcDatetime::to_cbor_epoch`] for years before 0 or after 9999.
+    pub fn to_cbor_rfc3339(&self)->Vec<u8>{
+        let text=self.display_iso8601().to_string();
+        let mut bytes=vec![TAG_RFC3339];
+        write_head(&mut bytes,3,text.len() as u64);
+        bytes.extend(text.as_bytes());
+        bytes
+    }
+
+    /// Decode a tag 0 or tag 1 datetime from the start of `bytes`, returning it with the number of bytes read.
+    ///
+    /// Tag 1 accepts integers and half, single or double precision floats, fractions of a second are rounded down.
+    /// Returns TimeStringError for anything else, YearNumberError for a time outside [`UtcDatetime::MIN`] to [`UtcDatetime::MAX`].
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// // 1(1636968600.5)
+    /// let float=[0xc1,0xfb,0x41,0xd8,0x64,0x8a,0x26,0x20,0x00,0x00];
+    /// assert_eq!(UtcDatetime::from_cbor(&float).unwrap(),(UtcDatetime::new(2021,11,15,9,30,0).unwrap(),10));
+    /// ```
+    pub fn from_cbor(bytes:&[u8])->Result<(UtcDatetime,usize), IllegalTimeError>{
+        let (&tag,rest)=bytes.split_first().ok_or(IllegalTimeError::TimeStringError)?;
+        let (major,info,argument,len)=read_head(rest).ok_or(IllegalTimeError::TimeStringError)?;
+        let datetime=match (tag,major){
+            (TAG_RFC3339,3)=>{
+                let text=rest.get(len..len.saturating_add(argument as usize)).ok_or(IllegalTimeError::TimeStringError)?;
+                let dt=UtcDatetime::from_rfc3339(std::str::from_utf8(text).map_err(|_|IllegalTimeError::TimeStringError)?)?;
+                return Ok((dt,1+len+text.len()))
+            },
+            (TAG_EPOCH,0)=>UtcDatetime::from_unix_seconds(i64::try_from(argument).map_err(|_|IllegalTimeError::YearNumberError)?)?,
+            (TAG_EPOCH,1)=>{
+                let seconds=i64::try_from(argument).map_err(|_|IllegalTimeError::YearNumberError)?;
+                UtcDatetime::from_unix_seconds(-1-seconds)?
+            },
+            (TAG_EPOCH,7)=>{
+                let seconds=match info{
+                    25=>f16_to_f64(argument as u16),
+                    26=>f32::from_bits(argument as u32) as f64,
+                    27=>f64::from_bits(argument),
+                    _=>return Err(IllegalTimeError::TimeStringError),
+                };
+                if !(UtcDatetime::MIN.unix_seconds() as f64..UtcDatetime::MAX.unix_seconds() as f64+1.0).contains(&seconds){
+                    return Err(IllegalTimeError::YearNumberError)
+                }
+                UtcDatetime::from_unix_seconds(seconds.floor() as i64)?
+            },
+            _=>return Err(IllegalTimeError::TimeStringError),
+        };
+        Ok((datetime,1+len))
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::{IllegalTimeError, UtcDatetime};
+    use super::f16_to_f64;
+
+    #[test]
+    fn cbor_tags(){
+        for dt in [UtcDatetime::MIN,UtcDatetime::new(1969,12,31,23,59,59).unwrap(),UtcDatetime::UNIX_EPOCH,UtcDatetime::from_timestamp(23),UtcDatetime::from_timestamp(255),UtcDatetime::from_timestamp(65536),UtcDatetime::MAX]{
+            assert_eq!(UtcDatetime::from_cbor(&dt.to_cbor_epoch()).unwrap(),(dt,dt.to_cbor_epoch().len()));
+        }
+        let last=UtcDatetime::new(9999,12,31,23,59,59).unwrap();
+        assert_eq!(UtcDatetime::from_cbor(&last.to_cbor_rfc3339()).unwrap(),(last,22));
+        assert_eq!(UtcDatetime::UNIX_EPOCH.to_cbor_epoch(),[0xc1,0x00]);
+        assert_eq!(UtcDatetime::new(1969,12,31,23,59,59).unwrap().to_cbor_epoch(),[0xc1,0x20]);
+        assert_eq!(UtcDatetime::MIN.to_cbor_epoch()[1],0x3b);
+        assert_eq!(UtcDatetime::MAX.to_cbor_epoch()[1],0x1b);
+        // RFC 8949中的例子
+        let example=b"\xc0\x742013-03-21T20:04:00Z";
+        assert_eq!(UtcDatetime::from_cbor(example).unwrap(),(UtcDatetime::new(2013,3,21,20,4,0).unwrap(),22));
+        assert_eq!(UtcDatetime::from_cbor(&[0xc1,0x1a,0x51,0x4b,0x67,0xb0]).unwrap().0,UtcDatetime::new(2013,3,21,20,4,0).unwrap());
+        // 半精度和单精度浮点数的1(1.5)
+        assert_eq!(UtcDatetime::from_cbor(&[0xc1,0xf9,0x3e,0x00]).unwrap().0,UtcDatetime::from_timestamp(1));
+        assert_eq!(UtcDatetime::from_cbor(&[0xc1,0xfa,0x3f,0xc0,0x00,0x00]).unwrap().0,UtcDatetime::from_timestamp(1));
+        assert_eq!(UtcDatetime::from_cbor(&[0xc1,0xf9,0xb8,0x00]).unwrap().0,UtcDatetime::new(1969,12,31,23,59,59).unwrap());
+        assert!(matches!(UtcDatetime::from_cbor(&[0xc1,0x3b,0xff,0xff,0xff,0xff,0xff,0xff,0xff,0xff]),Err(IllegalTimeError::YearNumberError)));
+        assert!(matches!(UtcDatetime::from_cbor(&[0xc1,0xfb,0x7f,0xf8,0,0,0,0,0,0]),Err(IllegalTimeError::YearNumberError)));
+        for bad in [&[][..],&[0xc2,0x00],&[0xc1,0x60],&[0xc0,0x01],&[0xc0,0x65,b'2'],&[0xc1,0x1a,0x00],&[0xc1,0xf4]]{
+            assert!(matches!(UtcDatetime::from_cbor(bad),Err(IllegalTimeError::TimeStringError)),"{:?}",bad);
+        }
+        assert_eq!((f16_to_f64(0x7bff),f16_to_f64(0x0001),f16_to_f64(0xc400)),(65504.0,5.960464477539063e-8,-4.0));
+    }
+}
@@ -0,0 +1,211 @@
+//! A source of the current time that can be replaced in tests.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::clock::{Clock, SystemClock};
+//! struct Fixed(UtcDatetime);
+//! impl Clock for Fixed{
+//!     fn now(&self)->UtcDatetime{self.0}
+//! }
+//! fn is_weekend<C:Clock>(clock:&C)->bool{
+//!     matches!(UtcDatetime::now_from(clock).weekday(),0|6)
+//! }
+//! assert!(is_weekend(&Fixed(UtcDatetime::new(2021,11,14,0,0,0).unwrap())));
+//! is_weekend(&SystemClock);
+//! ```
+use std::cell::Cell;
+use std::sync::Mutex;
+use std::time::Duration;
+#[cfg(not(all(feature = "js", target_arch = "wasm32")))]
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::{IllegalTimeError, UtcDatetime};
+
+/// A source of the current time
+pub trait Clock{
+    /// Return the current time
+    fn now(&self)->UtcDatetime;
+}
+
+impl<C:Clock+?Sized> Clock for &C{
+    fn now(&self)->UtcDatetime{
+        (**self).now()
+    }
+}
+
+/// The system clock, truncated to whole seconds
+///
+/// A system time outside [`UtcDatetime::MIN`] to [`UtcDatetime::MAX`] returns the nearest of the two.
+/// Inside [`with_frozen_time`] it returns the frozen time instead.
+/// On wasm32 with the `js` feature it reads [`JsClock`], where `SystemTime::now` would panic.
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock{
+    fn now(&self)->UtcDatetime{
+        if let Some(frozen)=FROZEN.with(Cell::get){
+            return frozen
+        }
+        #[cfg(all(feature = "js", target_arch = "wasm32"))]
+        let seconds=js_seconds();
+        #[cfg(not(all(feature = "js", target_arch = "wasm32")))]
+        let seconds=match SystemTime::now().duration_since(UNIX_EPOCH){
+            Ok(d)=>i64::try_from(d.as_secs()).unwrap_or(i64::MAX),
+            Err(e)=>i64::try_from(e.duration().as_secs()).map_or(i64::MIN,|s|-s),
+        };
+        clamped(seconds)
+    }
+}
+
+// 超出范围的秒数取最近的MIN或MAX
+fn clamped(seconds:i64)->UtcDatetime{
+    let seconds=seconds.clamp(UtcDatetime::MIN.unix_seconds(),UtcDatetime::MAX.unix_seconds());
+    UtcDatetime::from_unix_seconds(seconds).expect("clamped into range")
+}
+
+/// The JavaScript clock `Date.now()`, for wasm32 in browsers and Node.js with the `js` feature.
+///
+/// Clamped to the same range as [`SystemClock`], it ignores [`with_frozen_time`].
+#[cfg(all(feature = "js", target_arch = "wasm32"))]
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct JsClock;
+
+#[cfg(all(feature = "js", target_arch = "wasm32"))]
+impl Clock for JsClock{
+    fn now(&self)->UtcDatetime{
+        clamped(js_seconds())
+    }
+}
+
+// Date.now()返回毫秒数,NaN按0处理
+#[cfg(all(feature = "js", target_arch = "wasm32"))]
+fn js_seconds()->i64{
+    (js_sys::Date::now()/1000.0).floor() as i64
+}
+
+impl UtcDatetime{
+    /// Return the current time from the [`SystemClock`]
+    pub fn now()->UtcDatetime{
+        SystemClock.now()
+    }
+
+    /// Return the current time from a [`Clock`]
+    pub fn now_from<C:Clock+?Sized>(clock:&C)->UtcDatetime{
+        clock.now()
+    }
+}
+
+/// A thread-safe clock that only moves when it is told to, for tests
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// use utc_datetime::clock::{Clock, MockClock};
+/// use std::time::Duration;
+/// let clock=MockClock::new(UtcDatetime::new(2021,11,15,23,59,59).unwrap());
+/// clock.advance(Duration::from_secs(1)).unwrap();
+/// assert_eq!(clock.now(),UtcDatetime::new(2021,11,16,0,0,0).unwrap());
+/// clock.set(UtcDatetime::new(2020,2,2,2,2,2).unwrap());
+/// assert_eq!(clock.now(),UtcDatetime::new(2020,2,2,2,2,2).unwrap());
+/// ```
+#[derive(Debug)]
+pub struct MockClock{
+    now:Mutex<UtcDatetime>,
+}
+
+impl MockClock{
+    /// Create a clock stopped at `now`
+    pub fn new(now:UtcDatetime)->MockClock{
+        MockClock{now:Mutex::new(now)}
+    }
+
+    /// Set the current time
+    pub fn set(&self,now:UtcDatetime){
+        *self.lock()=now;
+    }
+
+    /// Move the clock forward by whole seconds, the fractional part is ignored.
+    ///
+    /// Returns YearNumberError and leaves the clock unchanged if the result is after [`UtcDatetime::MAX`].
+    pub fn advance(&self,by:Duration)->Result<UtcDatetime, IllegalTimeError>{
+        let mut now=self.lock();
+        let seconds=i64::try_from(by.as_secs()).map_err(|_|IllegalTimeError::YearNumberError)?;
+        let seconds=now.unix_seconds().checked_add(seconds).ok_or(IllegalTimeError::YearNumberError)?;
+        *now=UtcDatetime::from_unix_seconds(seconds)?;
+        Ok(*now)
+    }
+
+    // 持有锁的线程panic不会破坏时间的有效性,忽略中毒
+    fn lock(&self)->std::sync::MutexGuard<'_,UtcDatetime>{
+        self.now.lock().unwrap_or_else(|e|e.into_inner())
+    }
+}
+
+impl Clock for MockClock{
+    fn now(&self)->UtcDatetime{
+        *self.lock()
+    }
+}
+
+thread_local!{
+    static FROZEN:Cell<Option<UtcDatetime>>=const{Cell::new(None)};
+}
+
+/// Run `f` with [`UtcDatetime::now`] and [`SystemClock`] returning `now` on the current thread.
+///
+/// Calls can be nested, the previous time is restored when `f` returns or panics.
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// use utc_datetime::clock::with_frozen_time;
+/// let frozen=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+/// assert_eq!(with_frozen_time(frozen,UtcDatetime::now),frozen);
+/// ```
+pub fn with_frozen_time<T>(now:UtcDatetime,f:impl FnOnce()->T)->T{
+    struct Restore(Option<UtcDatetime>);
+    impl Drop for Restore{
+        fn drop(&mut self){
+            FROZEN.with(|frozen|frozen.set(self.0));
+        }
+    }
+    let _restore=Restore(FROZEN.with(|frozen|frozen.replace(Some(now))));
+    f()
+}
+
+#[cfg(test)]
+mod tests{
+    use super::{with_frozen_time, Clock, MockClock};
+    use crate::UtcDatetime;
+    use std::time::Duration;
+
+    #[test]
+    fn mock_clock_across_threads(){
+        let clock=std::sync::Arc::new(MockClock::new(UtcDatetime::UNIX_EPOCH));
+        let handles:Vec<_>=(0..4).map(|_|{
+            let clock=clock.clone();
+            std::thread::spawn(move ||{clock.advance(Duration::from_secs(86_400)).unwrap();})
+        }).collect();
+        handles.into_iter().for_each(|h|h.join().unwrap());
+        assert_eq!(clock.now(),UtcDatetime::new(1970,1,5,0,0,0).unwrap());
+        clock.set(UtcDatetime::MAX);
+        assert!(clock.advance(Duration::from_secs(1)).is_err());
+        assert_eq!(clock.now(),UtcDatetime::MAX);
+    }
+
+    #[test]
+    fn system_time_is_clamped(){
+        assert_eq!(super::clamped(i64::MIN),UtcDatetime::MIN);
+        assert_eq!(super::clamped(i64::MAX),UtcDatetime::MAX);
+        assert_eq!(super::clamped(-1),UtcDatetime::new(1969,12,31,23,59,59).unwrap());
+    }
+
+    #[test]
+    fn frozen_time_is_restored(){
+        let a=UtcDatetime::new(2000,1,1,0,0,0).unwrap();
+        let b=UtcDatetime::new(2001,1,1,0,0,0).unwrap();
+        with_frozen_time(a,||{
+            let inner=std::panic::catch_unwind(||with_frozen_time(b,||panic!()));
+            assert!(inner.is_err());
+            assert_eq!(UtcDatetime::now(),a);
+        });
+        assert!(UtcDatetime::now()>a);
+    }
+}
@@ -0,0 +1,312 @@
+//! Standard 5-field cron expressions: minute, hour, day of month, month, day of week.
+//!
+//! Each field accepts `*`, numbers, ranges `a-b`, steps `*/n` and `a-b/n`, and comma-separated lists.
+//! Months and days of the week may also be written as `JAN`..`DEC` and `SUN`..`SAT`,
+//! Sunday is both 0 and 7. The macros `@yearly`, `@annually`, `@monthly`, `@weekly`, `@daily`
+//! and `@hourly` are accepted as well.
+//!
+//! As in Vixie cron, when both the day of month and the day of week are restricted
+//! (neither starts with `*`), a day matches if either field matches.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::cron::CronSchedule;
+//! let schedule=CronSchedule::parse("30 9 * * MON-FRI").unwrap();
+//! let saturday=UtcDatetime::new(2021,11,13,12,0,0).unwrap();
+//! assert_eq!(schedule.next_after(&saturday),Some(UtcDatetime::new(2021,11,15,9,30,0).unwrap()));
+//! assert_eq!(schedule.prev_before(&saturday),Some(UtcDatetime::new(2021,11,12,9,30,0).unwrap()));
+//! ```
+use crate::{days_from_civil, days_of_the_month, IllegalTimeError, UtcDatetime};
+
+// 日历每400年重复一次,超过这个范围还没有匹配就永远不会匹配
+const SEARCH_YEARS:i64=400;
+
+const MONTH_NAMES:[&str;12]=["JAN","FEB","MAR","APR","MAY","JUN","JUL","AUG","SEP","OCT","NOV","DEC"];
+const WEEKDAY_NAMES:[&str;7]=["SUN","MON","TUE","WED","THU","FRI","SAT"];
+
+/// A parsed cron expression
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub struct CronSchedule{
+    // 每个字段用一个位集合表示,第n位为1表示取值n匹配
+    minutes:u64,
+    hours:u64,
+    days_of_month:u64,
+    months:u64,
+    days_of_week:u64,
+    any_day_of_month:bool,
+    any_day_of_week:bool,
+}
+
+impl CronSchedule{
+    /// Parse a cron expression, returning TimeStringError if it is malformed
+    pub fn parse(expression:&str)->Result<CronSchedule, IllegalTimeError>{
+        let expression=match expression.trim(){
+            "@yearly"|"@annually"=>"0 0 1 1 *",
+            "@monthly"=>"0 0 1 * *",
+            "@weekly"=>"0 0 * * 0",
+            "@daily"|"@midnight"=>"0 0 * * *",
+            "@hourly"=>"0 * * * *",
+            other=>other,
+        };
+        let fields:Vec<&str>=expression.split_whitespace().collect();
+        let [minute,hour,day_of_month,month,day_of_week]=fields[..] else{
+            return Err(IllegalTimeError::TimeStringError)
+        };
+        let mut days_of_week=parse_field(day_of_week,0,7,&WEEKDAY_NAMES,0)?;
+        // 7也表示星期天
+        if days_of_week>>7&1==1{
+            days_of_week=(days_of_week|1)&!(1<<7);
+        }
+        Ok(CronSchedule{
+            minutes:parse_field(minute,0,59,&[],0)?,
+            hours:parse_field(hour,0,23,&[],0)?,
+            days_of_month:parse_field(day_of_month,1,31,&[],0)?,
+            months:parse_field(month,1,12,&MONTH_NAMES,1)?,
+            days_of_week,
+            any_day_of_month:day_of_month.starts_with('*'),
+            any_day_of_week:day_of_week.starts_with('*'),
+        })
+    }
+
+    /// Returns whether `dt` matches the schedule, ignoring the seconds
+    pub fn matches(&self,dt:&UtcDatetime)->bool{
+        self.months>>dt.month&1==1
+            && self.day_matches(dt.year as i64,dt.month,dt.day)
+            && self.hours>>dt.hour&1==1
+            && self.minutes>>dt.minute&1==1
+    }
+
+    /// The first matching minute strictly after `dt`,
+    /// or None if there is none before the year 999999 or within 400 years.
+    pub fn next_after(&self,dt:&UtcDatetime)->Option<UtcDatetime>{
+        let start=UtcDatetime::from_unix_seconds((dt.unix_seconds().div_euclid(60)+1)*60).ok()?;
+        let mut c=Cursor::from(start);
+        let limit=c.year+SEARCH_YEARS;
+        while c.year<=limit{
+            if self.months>>c.month&1==0{
+                c.next_month();
+            }else if !self.day_matches(c.year,c.month,c.day){
+                c.next_day();
+            }else if self.hours>>c.hour&1==0{
+                c.next_hour();
+            }else if self.minutes>>c.minute&1==0{
+                c.next_minute();
+            }else{
+                return c.to_datetime()
+            }
+        }
+        None
+    }
+
+    /// The last matching minute strictly before `dt`,
+    /// or None if there is none after the year -999999 or within 400 years.
+    pub fn prev_before(&self,dt:&UtcDatetime)->Option<UtcDatetime>{
+        let start=UtcDatetime::from_unix_seconds((dt.unix_seconds()-1).div_euclid(60)*60).ok()?;
+        let mut c=Cursor::from(start);
+        let limit=c.year-SEARCH_YEARS;
+        while c.year>=limit.max(UtcDatetime::MIN_YEAR as i64){
+            if self.months>>c.month&1==0{
+                c.prev_month();
+            }else if !self.day_matches(c.year,c.month,c.day){
+                c.prev_day();
+            }else if self.hours>>c.hour&1==0{
+                c.prev_hour();
+            }else if self.minutes>>c.minute&1==0{
+                c.prev_minute();
+            }else{
+                return c.to_datetime()
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self,year:i64,month:u8,day:u8)->bool{
+        let weekday=(4+days_from_civil(year,month,day)).rem_euclid(7);
+        let day_of_month=self.days_of_month>>day&1==1;
+        let day_of_week=self.days_of_week>>weekday&1==1;
+        match (self.any_day_of_month,self.any_day_of_week){
+            (false,false)=>day_of_month || day_of_week,
+            _=>day_of_month && day_of_week,
+        }
+    }
+}
+
+impl core::str::FromStr for CronSchedule{
+    type Err=IllegalTimeError;
+
+    fn from_str(s:&str)->Result<CronSchedule, IllegalTimeError>{
+        CronSchedule::parse(s)
+    }
+}
+
+// 解析一个字段,names[i]表示数值first_name+i
+fn parse_field(field:&str,min:u8,max:u8,names:&[&str],first_name:u8)->Result<u64, IllegalTimeError>{
+    let value=|s:&str|->Result<u8, IllegalTimeError>{
+        let n=match names.iter().position(|name|name.eq_ignore_ascii_case(s)){
+            Some(i)=>i as u8+first_name,
+            None=>s.parse().map_err(|_|IllegalTimeError::TimeStringError)?,
+        };
+        if n<min || n>max{
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        Ok(n)
+    };
+    let mut bits=0u64;
+    for part in field.split(','){
+        let (range,step)=match part.split_once('/'){
+            Some((range,step))=>(range,step.parse::<u8>().map_err(|_|IllegalTimeError::TimeStringError)?),
+            None=>(part,1),
+        };
+        if step==0{
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        let (low,high)=match range.split_once('-'){
+            _ if range=="*"=>(min,max),
+            Some((low,high))=>(value(low)?,value(high)?),
+            // 带步长的单个数字表示从它开始到最大值
+            None if part.contains('/')=>(value(range)?,max),
+            None=>{
+                let n=value(range)?;
+                (n,n)
+            }
+        };
+        if low>high{
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        for n in (low..=high).step_by(step as usize){
+            bits|=1<<n;
+        }
+    }
+    Ok(bits)
+}
+
+// 搜索时使用的分钟精度的位置,年份可以暂时超出MAX_YEAR
+struct Cursor{
+    year:i64,
+    month:u8,
+    day:u8,
+    hour:u8,
+    minute:u8,
+}
+
+impl From<UtcDatetime> for Cursor{
+    fn from(dt:UtcDatetime)->Cursor{
+        Cursor{year:dt.year as i64,month:dt.month,day:dt.day,hour:dt.hour,minute:dt.minute}
+    }
+}
+
+impl Cursor{
+    fn days_in_month(&self)->u8{
+        days_of_the_month(self.year as i32,self.month)
+    }
+
+    fn to_datetime(&self)->Option<UtcDatetime>{
+        UtcDatetime::new(i32::try_from(self.year).ok()?,self.month,self.day,self.hour,self.minute,0).ok()
+    }
+
+    fn next_month(&mut self){
+        if self.month==12{
+            self.month=1;
+            self.year+=1;
+        }else{
+            self.month+=1;
+        }
+        self.day=1;
+        self.hour=0;
+        self.minute=0;
+    }
+
+    fn next_day(&mut self){
+        if self.day==self.days_in_month(){
+            return self.next_month()
+        }
+        self.day+=1;
+        self.hour=0;
+        self.minute=0;
+    }
+
+    fn next_hour(&mut self){
+        if self.hour==23{
+            return self.next_day()
+        }
+        self.hour+=1;
+        self.minute=0;
+    }
+
+    fn next_minute(&mut self){
+        if self.minute==59{
+            return self.next_hour()
+        }
+        self.minute+=1;
+    }
+
+    fn prev_month(&mut self){
+        if self.month==1{
+            self.month=12;
+            self.year-=1;
+        }else{
+            self.month-=1;
+        }
+        self.day=self.days_in_month();
+        self.hour=23;
+        self.minute=59;
+    }
+
+    fn prev_day(&mut self){
+        if self.day==1{
+            return self.prev_month()
+        }
+        self.day-=1;
+        self.hour=23;
+        self.minute=59;
+    }
+
+    fn prev_hour(&mut self){
+        if self.hour==0{
+            return self.prev_day()
+        }
+        self.hour-=1;
+        self.minute=59;
+    }
+
+    fn prev_minute(&mut self){
+        if self.minute==0{
+            return self.prev_hour()
+        }
+        self.minute-=1;
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::CronSchedule;
+    use crate::UtcDatetime;
+
+    fn dt(year:i32,month:u8,day:u8,hour:u8,minute:u8)->UtcDatetime{
+        UtcDatetime::new(year,month,day,hour,minute,0).unwrap()
+    }
+
+    #[test]
+    fn next_and_prev(){
+        let every_15=CronSchedule::parse("*/15 * * * *").unwrap();
+        let at=UtcDatetime::new(2021,12,31,23,59,30).unwrap();
+        assert_eq!(every_15.next_after(&at),Some(dt(2022,1,1,0,0)));
+        assert_eq!(every_15.prev_before(&at),Some(dt(2021,12,31,23,45)));
+        assert_eq!(every_15.prev_before(&dt(2021,12,31,23,45)),Some(dt(2021,12,31,23,30)));
+        // 日和星期都受限制时任一匹配即可:13号或者星期五
+        let friday_13=CronSchedule::parse("0 0 13 * 5").unwrap();
+        assert_eq!(friday_13.next_after(&dt(2021,11,13,0,0)),Some(dt(2021,11,19,0,0)));
+        let leap_day=CronSchedule::parse("0 12 29 feb *").unwrap();
+        assert_eq!(leap_day.next_after(&dt(2021,1,1,0,0)),Some(dt(2024,2,29,12,0)));
+        assert_eq!(leap_day.prev_before(&dt(2021,1,1,0,0)),Some(dt(2020,2,29,12,0)));
+        assert_eq!(CronSchedule::parse("0 0 30 2 *").unwrap().next_after(&dt(2021,1,1,0,0)),None);
+        assert_eq!(CronSchedule::parse("@yearly").unwrap().prev_before(&dt(1970,6,1,0,0)),Some(dt(1970,1,1,0,0)));
+        assert_eq!(CronSchedule::parse("@yearly").unwrap().prev_before(&dt(1970,1,1,0,0)),Some(dt(1969,1,1,0,0)));
+        assert_eq!(CronSchedule::parse("@yearly").unwrap().prev_before(&UtcDatetime::MIN),None);
+        assert_eq!(CronSchedule::parse("@hourly").unwrap().next_after(&dt(1969,12,31,23,59)),Some(dt(1970,1,1,0,0)));
+        assert_eq!(CronSchedule::parse("0 0 * * 7").unwrap(),CronSchedule::parse("0 0 * * SUN").unwrap());
+        for bad in ["* * * *","60 * * * *","* * 0 * *","*/0 * * * *","5-1 * * * *","* * * FOO *"]{
+            assert!(CronSchedule::parse(bad).is_err(),"{}",bad);
+        }
+    }
+}
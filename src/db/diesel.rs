@@ -0,0 +1,133 @@
+use crate::UtcDatetime;
+
+#[cfg(feature = "diesel-postgres")]
+mod postgres{
+    use super::UtcDatetime;
+    use crate::db::{from_postgres_micros, to_postgres_micros};
+    use diesel::deserialize::{self, FromSql};
+    use diesel::pg::data_types::PgTimestamp;
+    use diesel::pg::{Pg, PgValue};
+    use diesel::serialize::{self, Output, ToSql};
+    use diesel::sql_types::Timestamp;
+
+    impl FromSql<Timestamp,Pg> for UtcDatetime{
+        fn from_sql(bytes:PgValue<'_>)->deserialize::Result<Self>{
+            let PgTimestamp(micros)=FromSql::<Timestamp,Pg>::from_sql(bytes)?;
+            Ok(from_postgres_micros(micros)?)
+        }
+    }
+
+    impl ToSql<Timestamp,Pg> for UtcDatetime{
+        fn to_sql<'b>(&'b self,out:&mut Output<'b,'_,Pg>)->serialize::Result{
+            ToSql::<Timestamp,Pg>::to_sql(&PgTimestamp(to_postgres_micros(self)?),&mut out.reborrow())
+        }
+    }
+}
+
+#[cfg(feature = "diesel-mysql")]
+mod mysql{
+    use super::UtcDatetime;
+    use diesel::deserialize::{self, FromSql};
+    use diesel::mysql::data_types::{MysqlTime, MysqlTimestampType};
+    use diesel::mysql::{Mysql, MysqlValue};
+    use diesel::serialize::{self, Output, ToSql};
+    use diesel::sql_types::Timestamp;
+
+    impl FromSql<Timestamp,Mysql> for UtcDatetime{
+        fn from_sql(bytes:MysqlValue<'_>)->deserialize::Result<Self>{
+            let time=<MysqlTime as FromSql<Timestamp,Mysql>>::from_sql(bytes)?;
+            Ok(UtcDatetime::new(
+                time.year.try_into()?,
+                time.month.try_into()?,
+                time.day.try_into()?,
+                time.hour.try_into()?,
+                time.minute.try_into()?,
+                time.second.try_into()?,
+            )?)
+        }
+    }
+
+    impl ToSql<Timestamp,Mysql> for UtcDatetime{
+        fn to_sql<'b>(&'b self,out:&mut Output<'b,'_,Mysql>)->serialize::Result{
+            // DATETIME的年份只有4位
+            if !(0..=9999).contains(&self.year){
+                return Err(crate::IllegalTimeError::YearNumberError.into())
+            }
+            let time=MysqlTime::new(
+                self.year as u32,
+                self.month.into(),
+                self.day.into(),
+                self.hour.into(),
+                self.minute.into(),
+                self.second.into(),
+                0,
+                false,
+                MysqlTimestampType::MYSQL_TIMESTAMP_DATETIME,
+                0,
+            );
+            <MysqlTime as ToSql<Timestamp,Mysql>>::to_sql(&time,&mut out.reborrow())
+        }
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+mod sqlite{
+    use super::UtcDatetime;
+    use crate::db::parse_sql_text;
+    use diesel::backend::Backend;
+    use diesel::deserialize::{self, FromSql};
+    use diesel::serialize::{self, IsNull, Output, ToSql};
+    use diesel::sql_types::{Text, Timestamp};
+    use diesel::sqlite::Sqlite;
+
+    // SQLite按惯例把时间存为TEXT,整数则视为时间戳
+    impl FromSql<Timestamp,Sqlite> for UtcDatetime{
+        fn from_sql(value:<Sqlite as Backend>::RawValue<'_>)->deserialize::Result<Self>{
+            let text=<String as FromSql<Text,Sqlite>>::from_sql(value)?;
+            if let Ok(timestamp)=text.parse::<i64>(){
+                return Ok(UtcDatetime::from_unix_seconds(timestamp)?)
+            }
+            Ok(parse_sql_text(&text)?)
+        }
+    }
+
+    impl ToSql<Timestamp,Sqlite> for UtcDatetime{
+        fn to_sql<'b>(&'b self,out:&mut Output<'b,'_,Sqlite>)->serialize::Result{
+            out.set_value(self.to_string());
+            Ok(IsNull::No)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::UtcDatetime;
+
+    #[cfg(feature = "diesel-postgres")]
+    #[test]
+    fn postgres_micros_boundaries(){
+        use crate::db::{from_postgres_micros, to_postgres_micros};
+        // u32时间戳在2106-02-07 06:28:15溢出
+        for dt in [UtcDatetime::new(2106,2,7,6,28,16).unwrap(),UtcDatetime::new(65535,12,31,23,59,59).unwrap(),UtcDatetime::new(65536,1,1,0,0,0).unwrap()]{
+            assert_eq!(from_postgres_micros(to_postgres_micros(&dt).unwrap()).unwrap(),dt);
+        }
+    }
+
+    #[cfg(feature = "diesel-sqlite")]
+    #[test]
+    fn sqlite_boundaries(){
+        use diesel::dsl::sql;
+        use diesel::sql_types::Timestamp;
+        use diesel::{Connection, RunQueryDsl, SqliteConnection};
+        let mut conn=SqliteConnection::establish(":memory:").unwrap();
+        let mut select=|expr:&str|diesel::select(sql::<Timestamp>(expr)).get_result::<UtcDatetime>(&mut conn);
+        let a_date=UtcDatetime::new(2106,2,7,6,28,16).unwrap();
+        assert_eq!(select("4294967296").unwrap(),a_date);
+        assert_eq!(select("'2106-02-07 06:28:16'").unwrap(),a_date);
+        assert_eq!(select("'2106-02-07T14:28:16+08:00'").unwrap(),a_date);
+        assert_eq!(select("'65535-12-31 23:59:59'").unwrap(),UtcDatetime::new(65535,12,31,23,59,59).unwrap());
+        assert_eq!(select("-62135596800").unwrap(),UtcDatetime::new(1,1,1,0,0,0).unwrap());
+        assert!(select("'2106-02-07 06:28:16 junk'").is_err());
+        assert!(select("9223372036854775807").is_err());
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-mysql", feature = "sqlx-sqlite"))]
+mod sqlx;
+#[cfg(any(feature = "diesel-postgres", feature = "diesel-mysql", feature = "diesel-sqlite"))]
+mod diesel;
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+#[cfg(feature = "postgres")]
+mod postgres;
+
+// 2000-01-01 00:00:00的时间戳
+#[cfg(any(feature = "sqlx-postgres", feature = "diesel-postgres", feature = "postgres"))]
+const POSTGRES_EPOCH:i64=946_684_800;
+
+// Postgres的TIMESTAMP二进制格式为2000年以来的微秒数,i64微秒只覆盖约±29万年
+#[cfg(any(feature = "sqlx-postgres", feature = "diesel-postgres", feature = "postgres"))]
+pub(crate) fn to_postgres_micros(dt:&crate::UtcDatetime)->Result<i64, crate::IllegalTimeError>{
+    dt.unix_seconds().checked_sub(POSTGRES_EPOCH)
+        .and_then(|seconds|seconds.checked_mul(1_000_000))
+        .ok_or(crate::IllegalTimeError::YearNumberError)
+}
+
+#[cfg(any(feature = "sqlx-postgres", feature = "diesel-postgres", feature = "postgres"))]
+pub(crate) fn from_postgres_micros(micros:i64)->Result<crate::UtcDatetime, crate::IllegalTimeError>{
+    let seconds=micros.div_euclid(1_000_000).checked_add(POSTGRES_EPOCH).ok_or(crate::IllegalTimeError::YearNumberError)?;
+    crate::UtcDatetime::from_unix_seconds(seconds)
+}
+
+// 数据库返回的文本形如"2021-11-15 09:30:00.123456+00",年份可以多于4位或带负号,小数秒被舍去
+// 时区可以是Z、±HH、±HHMM或±HH:MM,没有时区时按UTC处理,其他内容都拒绝
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-mysql", feature = "sqlx-sqlite", feature = "diesel-sqlite", feature = "rusqlite"))]
+pub(crate) fn parse_sql_text(text:&str)->Result<crate::UtcDatetime, crate::IllegalTimeError>{
+    use crate::text::{number, offset_seconds, skip_fraction, to_utc};
+    use crate::IllegalTimeError::TimeStringError;
+    let bytes=text.trim().as_bytes();
+    let (sign,unsigned)=match bytes{
+        [b'-',tail@..]=>(-1,tail),
+        _=>(1,bytes)
+    };
+    let digits=unsigned.iter().take_while(|b|b.is_ascii_digit()).count();
+    if !(4..=6).contains(&digits){
+        return Err(TimeStringError)
+    }
+    let year=sign*number(unsigned,0,digits)? as i32;
+    // 年份之后是"-MM-DD HH:MM:SS",日期和时间之间也可以是T
+    let time=&unsigned[digits..];
+    if time.len()<15 || (time[0],time[3],time[9],time[12])!=(b'-',b'-',b':',b':') || !matches!(time[6],b'T'|b't'|b' '){
+        return Err(TimeStringError)
+    }
+    let offset=match skip_fraction(&time[15..])?{
+        []|[b'Z'|b'z']=>0,
+        [sign,h1,h2]=>offset_seconds(&[*sign,*h1,*h2,b'0',b'0'])?,
+        [sign,h1,h2,m1,m2]|[sign,h1,h2,b':',m1,m2]=>offset_seconds(&[*sign,*h1,*h2,*m1,*m2])?,
+        _=>return Err(TimeStringError)
+    };
+    let local=crate::UtcDatetime::new(
+        year,
+        number(time,1,2)? as u8,
+        number(time,4,2)? as u8,
+        number(time,7,2)? as u8,
+        number(time,10,2)? as u8,
+        number(time,13,2)? as u8,
+    )?;
+    to_utc(local,offset)
+}
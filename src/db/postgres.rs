@@ -0,0 +1,64 @@
+use crate::db::{from_postgres_micros, to_postgres_micros};
+use crate::UtcDatetime;
+use bytes::BytesMut;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use std::error::Error;
+
+// TIMESTAMPTZ和TIMESTAMP的二进制格式相同,都是2000年以来的微秒数(大端i64)
+impl<'a> FromSql<'a> for UtcDatetime{
+    fn from_sql(_ty:&Type,raw:&'a [u8])->Result<Self,Box<dyn Error+Sync+Send>>{
+        let micros=i64::from_be_bytes(raw.try_into()?);
+        Ok(from_postgres_micros(micros)?)
+    }
+
+    accepts!(TIMESTAMPTZ,TIMESTAMP);
+}
+
+impl ToSql for UtcDatetime{
+    fn to_sql(&self,_ty:&Type,out:&mut BytesMut)->Result<IsNull,Box<dyn Error+Sync+Send>>{
+        out.extend_from_slice(&to_postgres_micros(self)?.to_be_bytes());
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMESTAMPTZ,TIMESTAMP);
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use crate::db::POSTGRES_EPOCH;
+
+    fn to_bytes(dt:&UtcDatetime)->Result<BytesMut,Box<dyn Error+Sync+Send>>{
+        let mut out=BytesMut::new();
+        dt.to_sql(&Type::TIMESTAMPTZ,&mut out)?;
+        Ok(out)
+    }
+
+    #[test]
+    fn known_timestamptz_bytes(){
+        let cases=[
+            (0,UtcDatetime::new(2000,1,1,0,0,0).unwrap()),
+            (-1_000_000,UtcDatetime::new(1999,12,31,23,59,59).unwrap()),
+            (1_000_000*(u32::MAX as i64+1-POSTGRES_EPOCH),UtcDatetime::new(2106,2,7,6,28,16).unwrap()),
+        ];
+        for (micros,dt) in cases{
+            assert_eq!(&to_bytes(&dt).unwrap()[..],&micros.to_be_bytes());
+            assert_eq!(UtcDatetime::from_sql(&Type::TIMESTAMPTZ,&micros.to_be_bytes()).unwrap(),dt);
+        }
+    }
+
+    #[test]
+    fn micros_limits(){
+        // 小数秒被舍去,负数向下取整
+        assert_eq!(UtcDatetime::from_sql(&Type::TIMESTAMPTZ,&(-1i64).to_be_bytes()).unwrap(),UtcDatetime::new(1999,12,31,23,59,59).unwrap());
+        assert_eq!(UtcDatetime::from_sql(&Type::TIMESTAMPTZ,&i64::MAX.to_be_bytes()).unwrap(),UtcDatetime::new(294277,1,9,4,0,54).unwrap());
+        assert_eq!(UtcDatetime::from_sql(&Type::TIMESTAMPTZ,&i64::MIN.to_be_bytes()).unwrap(),UtcDatetime::new(-290278,12,22,19,59,5).unwrap());
+        assert!(UtcDatetime::from_sql(&Type::TIMESTAMPTZ,&[0;4]).is_err());
+        // i64微秒放不下UtcDatetime的整个范围
+        assert!(to_bytes(&UtcDatetime::MAX).is_err());
+        assert!(to_bytes(&UtcDatetime::MIN).is_err());
+        let bytes=to_bytes(&UtcDatetime::new(294277,1,9,4,0,54).unwrap()).unwrap();
+        assert_eq!(i64::from_be_bytes(bytes[..].try_into().unwrap()),i64::MAX-775_807);
+    }
+}
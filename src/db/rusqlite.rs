@@ -0,0 +1,49 @@
+use crate::db::parse_sql_text;
+use crate::UtcDatetime;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+// 按SQLite的惯例写入"YYYY-MM-DD HH:MM:SS"文本
+impl ToSql for UtcDatetime{
+    fn to_sql(&self)->rusqlite::Result<ToSqlOutput<'_>>{
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+// 读取时接受文本(如"2021-11-15 09:30:00","2021-11-15T09:30:00Z")和整数时间戳
+impl FromSql for UtcDatetime{
+    fn column_result(value:ValueRef<'_>)->FromSqlResult<Self>{
+        match value{
+            ValueRef::Integer(i)=>UtcDatetime::from_unix_seconds(i).map_err(|_|FromSqlError::OutOfRange(i)),
+            ValueRef::Text(_)=>parse_sql_text(value.as_str()?).map_err(|e|FromSqlError::Other(Box::new(e))),
+            _=>Err(FromSqlError::InvalidType)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+    use rusqlite::{Connection, Error};
+
+    #[test]
+    fn round_trips(){
+        let conn=Connection::open_in_memory().unwrap();
+        let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        let select=|sql:&str|conn.query_row(sql,[],|row|row.get::<_,UtcDatetime>(0));
+        assert_eq!(conn.query_row("SELECT ?1",[a_date],|row|row.get::<_,String>(0)).unwrap(),"2021-11-15 09:30:00");
+        assert_eq!(conn.query_row("SELECT ?1",[a_date],|row|row.get::<_,UtcDatetime>(0)).unwrap(),a_date);
+        assert_eq!(select("SELECT '2021-11-15T09:30:00Z'").unwrap(),a_date);
+        assert_eq!(select("SELECT '2021-11-15T17:30:00.250+08:00'").unwrap(),a_date);
+        assert_eq!(select("SELECT 1636968600").unwrap(),a_date);
+        assert_eq!(select("SELECT 4294967296").unwrap(),UtcDatetime::new(2106,2,7,6,28,16).unwrap());
+        assert!(matches!(select("SELECT 'yesterday'"),Err(Error::FromSqlConversionFailure(..))));
+        assert!(matches!(select("SELECT 1.5"),Err(Error::InvalidColumnType(..))));
+    }
+
+    #[test]
+    fn integer_out_of_range(){
+        let conn=Connection::open_in_memory().unwrap();
+        let result=conn.query_row("SELECT 9223372036854775807",[],|row|row.get::<_,UtcDatetime>(0));
+        assert!(matches!(result,Err(Error::IntegralValueOutOfRange(0,i64::MAX))));
+    }
+}
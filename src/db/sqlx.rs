@@ -0,0 +1,184 @@
+use crate::UtcDatetime;
+
+#[cfg(feature = "sqlx-postgres")]
+mod postgres{
+    use super::UtcDatetime;
+    use crate::db::{from_postgres_micros, parse_sql_text, to_postgres_micros};
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::postgres::types::Oid;
+    use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+    use sqlx::{Decode, Encode, Type};
+
+    // Postgres内置类型的OID是固定的
+    const TIMESTAMP_OID:u32=1114;
+    const TIMESTAMPTZ_OID:u32=1184;
+    const TIMESTAMP_ARRAY_OID:u32=1115;
+
+    impl Type<Postgres> for UtcDatetime{
+        fn type_info()->PgTypeInfo{
+            PgTypeInfo::with_oid(Oid(TIMESTAMP_OID))
+        }
+
+        fn compatible(ty:&PgTypeInfo)->bool{
+            matches!(ty.oid(),Some(Oid(TIMESTAMP_OID|TIMESTAMPTZ_OID)))
+        }
+    }
+
+    impl PgHasArrayType for UtcDatetime{
+        fn array_type_info()->PgTypeInfo{
+            PgTypeInfo::with_oid(Oid(TIMESTAMP_ARRAY_OID))
+        }
+    }
+
+    impl Encode<'_,Postgres> for UtcDatetime{
+        fn encode_by_ref(&self,buf:&mut PgArgumentBuffer)->Result<IsNull,BoxDynError>{
+            Encode::<Postgres>::encode(to_postgres_micros(self)?,buf)
+        }
+
+        fn size_hint(&self)->usize{
+            core::mem::size_of::<i64>()
+        }
+    }
+
+    impl<'r> Decode<'r,Postgres> for UtcDatetime{
+        fn decode(value:PgValueRef<'r>)->Result<Self,BoxDynError>{
+            match value.format(){
+                PgValueFormat::Binary=>{
+                    let micros:i64=Decode::<Postgres>::decode(value)?;
+                    Ok(from_postgres_micros(micros)?)
+                }
+                PgValueFormat::Text=>Ok(parse_sql_text(value.as_str()?)?)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-mysql")]
+mod mysql{
+    use super::UtcDatetime;
+    use crate::db::parse_sql_text;
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
+    use sqlx::{Decode, Encode, Type, TypeInfo};
+
+    // sqlx没有公开DATETIME的类型信息,以字符串绑定参数,由MySQL转换为DATETIME
+    impl Type<MySql> for UtcDatetime{
+        fn type_info()->MySqlTypeInfo{
+            <str as Type<MySql>>::type_info()
+        }
+
+        fn compatible(ty:&MySqlTypeInfo)->bool{
+            matches!(ty.name(),"DATETIME"|"TIMESTAMP")
+        }
+    }
+
+    // DATETIME的年份只有4位
+    impl Encode<'_,MySql> for UtcDatetime{
+        fn encode_by_ref(&self,buf:&mut Vec<u8>)->Result<IsNull,BoxDynError>{
+            if !(0..=9999).contains(&self.year){
+                return Err(crate::IllegalTimeError::YearNumberError.into())
+            }
+            Encode::<MySql>::encode(self.to_string(),buf)
+        }
+    }
+
+    impl<'r> Decode<'r,MySql> for UtcDatetime{
+        fn decode(value:MySqlValueRef<'r>)->Result<Self,BoxDynError>{
+            let buf:&[u8]=Decode::<MySql>::decode(value)?;
+            // 文本协议返回"YYYY-MM-DD HH:MM:SS[.ffffff]"
+            // 二进制协议:长度(4,7或11),年(u16小端),月,日,时,分,秒,微秒(u32小端)
+            let (year,month,day,hour,minute,second)=match buf{
+                [b'0'..=b'9',..]=>return Ok(parse_sql_text(core::str::from_utf8(buf)?)?),
+                [4,y0,y1,month,day,..]=>(u16::from_le_bytes([*y0,*y1]) as i32,*month,*day,0,0,0),
+                [7|11,y0,y1,month,day,hour,minute,second,..]=>(u16::from_le_bytes([*y0,*y1]) as i32,*month,*day,*hour,*minute,*second),
+                _=>return Err(format!("unexpected DATETIME encoding: {:?}",buf).into())
+            };
+            Ok(UtcDatetime::new(year,month,day,hour,minute,second)?)
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlite{
+    use super::UtcDatetime;
+    use crate::db::parse_sql_text;
+    use sqlx::encode::IsNull;
+    use sqlx::error::BoxDynError;
+    use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+    use sqlx::{Decode, Encode, Type, TypeInfo, ValueRef};
+
+    // SQLite没有时间类型,按惯例存为TEXT,也接受INTEGER形式的时间戳
+    impl Type<Sqlite> for UtcDatetime{
+        fn type_info()->SqliteTypeInfo{
+            <str as Type<Sqlite>>::type_info()
+        }
+
+        fn compatible(ty:&SqliteTypeInfo)->bool{
+            matches!(ty.name(),"TEXT"|"INTEGER"|"DATETIME")
+        }
+    }
+
+    impl<'q> Encode<'q,Sqlite> for UtcDatetime{
+        fn encode_by_ref(&self,buf:&mut Vec<SqliteArgumentValue<'q>>)->Result<IsNull,BoxDynError>{
+            Encode::<Sqlite>::encode(self.to_string(),buf)
+        }
+    }
+
+    impl<'r> Decode<'r,Sqlite> for UtcDatetime{
+        fn decode(value:SqliteValueRef<'r>)->Result<Self,BoxDynError>{
+            if value.type_info().name()=="INTEGER"{
+                let timestamp:i64=Decode::<Sqlite>::decode(value)?;
+                return Ok(UtcDatetime::from_unix_seconds(timestamp)
+                    .map_err(|_|format!("timestamp out of range for UtcDatetime: {}",timestamp))?)
+            }
+            let text:&str=Decode::<Sqlite>::decode(value)?;
+            Ok(parse_sql_text(text)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::UtcDatetime;
+
+    // u32时间戳在2106-02-07 06:28:15溢出,u16年份最大为65535
+    fn boundaries()->[UtcDatetime;4]{
+        [
+            UtcDatetime::new(2106,2,7,6,28,15).unwrap(),
+            UtcDatetime::new(2106,2,7,6,28,16).unwrap(),
+            UtcDatetime::new(65535,12,31,23,59,59).unwrap(),
+            UtcDatetime::new(65536,1,1,0,0,0).unwrap(),
+        ]
+    }
+
+    #[cfg(feature = "sqlx-postgres")]
+    #[test]
+    fn postgres_micros_boundaries(){
+        use crate::db::{from_postgres_micros, to_postgres_micros};
+        for dt in boundaries(){
+            assert_eq!(from_postgres_micros(to_postgres_micros(&dt).unwrap()).unwrap(),dt);
+        }
+        assert_eq!(to_postgres_micros(&UtcDatetime::new(2106,2,7,6,28,16).unwrap()).unwrap(),3_348_282_496_000_000);
+    }
+
+    #[test]
+    fn sql_text_boundaries(){
+        use crate::db::parse_sql_text;
+        for dt in boundaries(){
+            let text=format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}",dt.year,dt.month,dt.day,dt.hour,dt.minute,dt.second);
+            assert_eq!(parse_sql_text(&text).unwrap(),dt);
+            assert_eq!(parse_sql_text(&format!("{}.999999+00",text)).unwrap(),dt);
+        }
+        let a_date=UtcDatetime::new(2106,2,7,6,28,16).unwrap();
+        assert_eq!(parse_sql_text("2106-02-07T06:28:16Z").unwrap(),a_date);
+        assert_eq!(parse_sql_text("2106-02-07 14:28:16+08").unwrap(),a_date);
+        assert_eq!(parse_sql_text("2106-02-07 11:58:16.5+05:30").unwrap(),a_date);
+        assert_eq!(parse_sql_text("2106-02-07 01:28:16-0500").unwrap(),a_date);
+        assert_eq!(parse_sql_text("-0044-03-15 12:00:00").unwrap(),UtcDatetime::new(-44,3,15,12,0,0).unwrap());
+        for text in ["2106-02-07 06:28:16 BC","2106-02-07 06:28:16+8","2106-02-07 06:28:16.","2106-02-07","106-02-07 06:28:16","2106/02/07 06:28:16"]{
+            assert!(parse_sql_text(text).is_err(),"{}",text);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use defmt::{Format, Formatter};
+use crate::{IllegalTimeError, UtcDatetime};
+
+/// Log as `2021-11-15T09:30:00Z` with defmt, enabled with the `defmt` feature.
+///
+/// The fields are sent as integers and formatted on the host, so no string is built on the device.
+/// The crate itself still requires `std`, so a `no_std` firmware cannot use this feature yet.
+impl Format for UtcDatetime{
+    fn format(&self,f:Formatter){
+        // 符号单独发送,负数年份和Display一样写作-0044
+        let sign=if self.year<0 {"-"} else {""};
+        defmt::write!(f,"{=str}{=u32:04}-{=u8:02}-{=u8:02}T{=u8:02}:{=u8:02}:{=u8:02}Z",sign,self.year.unsigned_abs(),self.month,self.day,self.hour,self.minute,self.second)
+    }
+}
+
+// 与Debug的文字相同
+impl Format for IllegalTimeError{
+    fn format(&self,f:Formatter){
+        let name=match self{
+            IllegalTimeError::YearNumberError=>"Year Number Error",
+            IllegalTimeError::MonthNumberError=>"Month Number Error",
+            IllegalTimeError::DayNumberError=>"Day Number Error",
+            IllegalTimeError::HourNumberError=>"Hour Number Error",
+            IllegalTimeError::MinuteNumberError=>"Minute Number Error",
+            IllegalTimeError::SecondNumberError=>"Second Number Error",
+            IllegalTimeError::TimeStringError=>"The format of the input time string is not standardized",
+            IllegalTimeError::FormatDescriptionError=>"The format description is invalid",
+            IllegalTimeError::TrailingCharactersError=>"The time string has unexpected characters after the seconds",
+        };
+        defmt::write!(f,"{=str}",name)
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{days_of_the_month, UtcDatetime};
+
+/// The difference between two datetimes in calendar units, returned by
+/// [`UtcDatetime::calendar_duration_since`]
+///
+/// Units borrow like written subtraction: when the day of the later date is smaller,
+/// a month is borrowed using the length of the month before the later date.
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct CalendarDuration{
+    /// True if `self` was earlier than `other`
+    pub negative:bool,
+    pub years:u32,
+    pub months:u8,
+    pub days:u8,
+    pub hours:u8,
+    pub minutes:u8,
+    pub seconds:u8,
+}
+
+impl UtcDatetime{
+    /// The calendar difference from `other` to `self`, such as an age or a tenure
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let birth=UtcDatetime::new(1990,8,31,0,0,0).unwrap();
+    /// let today=UtcDatetime::new(2021,2,28,12,0,0).unwrap();
+    /// let age=today.calendar_duration_since(&birth);
+    /// assert_eq!((age.years,age.months,age.days,age.hours),(30,5,28,12));
+    /// assert!(birth.calendar_duration_since(&today).negative);
+    /// ```
+    pub fn calendar_duration_since(&self,other:&UtcDatetime)->CalendarDuration{
+        let negative=self<other;
+        let (a,b)=if negative {(self,other)} else {(other,self)};
+        // 从秒开始逐位相减,不够减时向高位借位
+        let mut borrow=0;
+        let mut sub=|high:u8,low:u8,base:u8|{
+            let (value,next)=match high as i16-low as i16-borrow{
+                v if v<0=>((v+base as i16) as u8,1),
+                v=>(v as u8,0),
+            };
+            borrow=next;
+            value
+        };
+        let seconds=sub(b.second,a.second,60);
+        let minutes=sub(b.minute,a.minute,60);
+        let hours=sub(b.hour,a.hour,24);
+        let (year,month)=if b.month==1 {(b.year-1,12)} else {(b.year,b.month-1)};
+        let previous_month_days=days_of_the_month(year,month);
+        let days=sub(b.day,a.day,previous_month_days.max(a.day));
+        let months=sub(b.month,a.month,12);
+        let years=(b.year-a.year) as u32-borrow as u32;
+        CalendarDuration{negative,years,months,days,hours,minutes,seconds}
+    }
+
+    /// The number of whole days from `self` to `other`, negative if `other` is earlier.
+    ///
+    /// Partial days are truncated toward zero, so 23:00 to 01:00 the next day is 0 days.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a=UtcDatetime::new(2021,2,27,12,0,0).unwrap();
+    /// let b=UtcDatetime::new(2021,3,1,11,59,59).unwrap();
+    /// assert_eq!(a.days_until(&b),1);
+    /// assert_eq!(b.days_until(&a),-1);
+    /// assert_eq!(a.hours_until(&b),47);
+    /// assert_eq!(a.minutes_until(&b),2879);
+    /// ```
+    pub fn days_until(&self,other:&UtcDatetime)->i64{
+        (other.unix_seconds()-self.unix_seconds())/(24*60*60)
+    }
+
+    /// The number of whole hours from `self` to `other`, see [`UtcDatetime::days_until`]
+    pub fn hours_until(&self,other:&UtcDatetime)->i64{
+        (other.unix_seconds()-self.unix_seconds())/(60*60)
+    }
+
+    /// The number of whole minutes from `self` to `other`, see [`UtcDatetime::days_until`]
+    pub fn minutes_until(&self,other:&UtcDatetime)->i64{
+        (other.unix_seconds()-self.unix_seconds())/60
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::UtcDatetime;
+
+    fn ymd(a:(i32,u8,u8),b:(i32,u8,u8))->(u32,u8,u8){
+        let a=UtcDatetime::new(a.0,a.1,a.2,0,0,0).unwrap();
+        let b=UtcDatetime::new(b.0,b.1,b.2,0,0,0).unwrap();
+        let d=b.calendar_duration_since(&a);
+        (d.years,d.months,d.days)
+    }
+
+    #[test]
+    fn month_lengths(){
+        assert_eq!(ymd((2021,1,31),(2021,3,1)),(0,1,1));
+        assert_eq!(ymd((2020,2,29),(2021,2,28)),(0,11,30));
+        assert_eq!(ymd((2020,2,29),(2021,3,1)),(1,0,1));
+        assert_eq!(ymd((2021,11,15),(2021,11,15)),(0,0,0));
+        assert_eq!(ymd((1970,1,1),(2021,1,1)),(51,0,0));
+        let a=UtcDatetime::new(2021,12,31,23,59,59).unwrap();
+        let b=UtcDatetime::new(2022,1,1,0,0,0).unwrap();
+        let d=b.calendar_duration_since(&a);
+        assert_eq!((d.years,d.months,d.days,d.hours,d.minutes,d.seconds),(0,0,0,0,0,1));
+    }
+}
@@ -0,0 +1,147 @@
+//! Cheap wrappers that select a format inside `format!` or `write!` without
+//! allocating an intermediate String.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+//! assert_eq!(format!("[{}] [{}]",a_date.display_iso8601(),a_date.display_compact()),"[2021-11-15T09:30:00Z] [20211115T093000Z]");
+//! ```
+use std::fmt;
+use crate::format::FormatDescription;
+use crate::UtcDatetime;
+
+/// Displays as `2021-11-15T09:30:00Z`, created by [`UtcDatetime::display_iso8601`]
+#[derive(Clone,Copy,Debug)]
+pub struct Iso8601(UtcDatetime);
+
+/// Displays as `+002021-11-15T09:30:00Z` or `-000044-03-15T12:00:00Z`, created by [`UtcDatetime::display_iso8601_expanded`]
+#[derive(Clone,Copy,Debug)]
+pub struct Iso8601Expanded(UtcDatetime);
+
+/// Displays as `20211115T093000Z`, created by [`UtcDatetime::display_compact`]
+#[derive(Clone,Copy,Debug)]
+pub struct Compact(UtcDatetime);
+
+/// What [`UtcDatetime::display_with_suffix`] writes after the time
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub enum UtcSuffix{
+    /// `2021-11-15 09:30:00Z`
+    Z,
+    /// `2021-11-15 09:30:00 UTC`
+    Utc,
+    /// `2021-11-15 09:30:00`, the same as `{}`
+    #[default]
+    Omit,
+}
+
+/// Displays as `2021-11-15 09:30:00` followed by a [`UtcSuffix`], created by [`UtcDatetime::display_with_suffix`]
+#[derive(Clone,Copy,Debug)]
+pub struct WithSuffix(UtcDatetime,UtcSuffix);
+
+/// Displays with a [`FormatDescription`], created by [`UtcDatetime::display_with`]
+#[derive(Clone,Copy,Debug)]
+pub struct WithFormat<'a,'b>{
+    datetime:UtcDatetime,
+    description:&'a FormatDescription<'b>,
+}
+
+// 至少四位数字的年份,公元前的年份在数字前加负号,如`-0044`
+pub(crate) struct Year(pub(crate) i32);
+
+impl fmt::Display for Year{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        if self.0<0{
+            f.write_str("-")?;
+        }
+        write!(f,"{:04}",self.0.unsigned_abs())
+    }
+}
+
+impl fmt::Display for Iso8601{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        write!(f,"{:#}",self.0)
+    }
+}
+
+impl fmt::Display for Iso8601Expanded{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        let dt=&self.0;
+        let sign=if dt.year<0 {'-'} else {'+'};
+        write!(f,"{}{:06}-{:02}-{:02}T{:02}:{:02}:{:02}Z",sign,dt.year.unsigned_abs(),dt.month,dt.day,dt.hour,dt.minute,dt.second)
+    }
+}
+
+impl fmt::Display for Compact{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        let dt=&self.0;
+        write!(f,"{}{:02}{:02}T{:02}{:02}{:02}Z",Year(dt.year),dt.month,dt.day,dt.hour,dt.minute,dt.second)
+    }
+}
+
+impl fmt::Display for WithSuffix{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        let suffix=match self.1{
+            UtcSuffix::Z=>"Z",
+            UtcSuffix::Utc=>" UTC",
+            UtcSuffix::Omit=>"",
+        };
+        write!(f,"{}{}",self.0,suffix)
+    }
+}
+
+impl fmt::Display for WithFormat<'_,'_>{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        self.datetime.format_with_into(self.description,f)
+    }
+}
+
+impl UtcDatetime{
+    /// Display as ISO 8601, the same as `{:#}`
+    pub fn display_iso8601(&self)->Iso8601{
+        Iso8601(*self)
+    }
+
+    /// Display as ISO 8601 with an expanded year of a sign and six digits, as JavaScript writes years before 0 or after 9999
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(10000,1,1,0,0,0).unwrap();
+    /// assert_eq!(a_date.display_iso8601_expanded().to_string(),"+010000-01-01T00:00:00Z");
+    /// assert_eq!(UtcDatetime::new(-1,1,1,0,0,0).unwrap().display_iso8601_expanded().to_string(),"-000001-01-01T00:00:00Z");
+    /// ```
+    pub fn display_iso8601_expanded(&self)->Iso8601Expanded{
+        Iso8601Expanded(*self)
+    }
+
+    /// Display in the ISO 8601 basic format without separators
+    pub fn display_compact(&self)->Compact{
+        Compact(*self)
+    }
+
+    /// Display as `YYYY-MM-DD HH:MM:SS` followed by `Z`, ` UTC` or nothing
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// use utc_datetime::display::UtcSuffix;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.display_with_suffix(UtcSuffix::Z).to_string(),"2021-11-15 09:30:00Z");
+    /// assert_eq!(a_date.display_with_suffix(UtcSuffix::Utc).to_string(),"2021-11-15 09:30:00 UTC");
+    /// assert_eq!(a_date.display_with_suffix(UtcSuffix::Omit).to_string(),a_date.to_string());
+    /// ```
+    pub fn display_with_suffix(&self,suffix:UtcSuffix)->WithSuffix{
+        WithSuffix(*self,suffix)
+    }
+
+    /// Display with a compiled [`FormatDescription`]
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// use utc_datetime::format::FormatDescription;
+    /// let fd=FormatDescription::new("%d.%m.%Y").unwrap();
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(format!("on {}",a_date.display_with(&fd)),"on 15.11.2021");
+    /// ```
+    pub fn display_with<'a,'b>(&self,description:&'a FormatDescription<'b>)->WithFormat<'a,'b>{
+        WithFormat{datetime:*self,description}
+    }
+}
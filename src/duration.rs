@@ -0,0 +1,625 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use crate::{IllegalTimeError, UtcDatetime};
+
+/// A signed, exact number of seconds, returned by [`UtcDatetime::until`]
+///
+/// Unlike [`crate::CalendarDuration`] a day is always 86400 seconds.
+/// The operators panic on overflow like integer arithmetic does in debug builds.
+/// # Example
+/// ```
+/// use utc_datetime::{Duration, UtcDatetime};
+/// let a=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+/// let b=UtcDatetime::new(2021,11,16,11,0,0).unwrap();
+/// assert_eq!(a.until(&b),Duration::days(1)+Duration::hours(1)+Duration::minutes(30));
+/// assert_eq!(b.until(&a).whole_hours(),-25);
+/// assert_eq!(a.add_duration(a.until(&b)).unwrap(),b);
+/// ```
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Hash,Debug,Default)]
+pub struct Duration{
+    seconds:i64,
+}
+
+impl Duration{
+    pub const ZERO:Duration=Duration{seconds:0};
+
+    pub const fn seconds(seconds:i64)->Duration{
+        Duration{seconds}
+    }
+
+    pub const fn minutes(minutes:i64)->Duration{
+        Duration{seconds:minutes*60}
+    }
+
+    pub const fn hours(hours:i64)->Duration{
+        Duration{seconds:hours*60*60}
+    }
+
+    pub const fn days(days:i64)->Duration{
+        Duration{seconds:days*24*60*60}
+    }
+
+    pub const fn weeks(weeks:i64)->Duration{
+        Duration{seconds:weeks*7*24*60*60}
+    }
+
+    pub const fn whole_seconds(&self)->i64{
+        self.seconds
+    }
+
+    /// The number of whole minutes, truncated toward zero
+    pub const fn whole_minutes(&self)->i64{
+        self.seconds/60
+    }
+
+    /// The number of whole hours, truncated toward zero
+    pub const fn whole_hours(&self)->i64{
+        self.seconds/(60*60)
+    }
+
+    /// The number of whole days, truncated toward zero
+    pub const fn whole_days(&self)->i64{
+        self.seconds/(24*60*60)
+    }
+
+    pub const fn is_negative(&self)->bool{
+        self.seconds<0
+    }
+
+    pub const fn abs(&self)->Duration{
+        Duration{seconds:self.seconds.abs()}
+    }
+}
+
+impl Add for Duration{
+    type Output=Duration;
+
+    fn add(self,rhs:Duration)->Duration{
+        Duration{seconds:self.seconds+rhs.seconds}
+    }
+}
+
+impl Sub for Duration{
+    type Output=Duration;
+
+    fn sub(self,rhs:Duration)->Duration{
+        Duration{seconds:self.seconds-rhs.seconds}
+    }
+}
+
+impl Neg for Duration{
+    type Output=Duration;
+
+    fn neg(self)->Duration{
+        Duration{seconds:-self.seconds}
+    }
+}
+
+/// Formats as days, hours, minutes and seconds such as `1d 2h 3m 4s`, leaving out zero units
+/// # Example
+/// ```
+/// use utc_datetime::Duration;
+/// assert_eq!(Duration::minutes(90).to_string(),"1h 30m");
+/// assert_eq!((-Duration::seconds(86_401)).to_string(),"-1d 1s");
+/// assert_eq!(Duration::ZERO.to_string(),"0s");
+/// ```
+impl fmt::Display for Duration{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        if self.seconds==0{
+            return f.write_str("0s")
+        }
+        if self.seconds<0{
+            f.write_str("-")?;
+        }
+        let seconds=self.seconds.unsigned_abs();
+        let units=[(seconds/86_400,"d"),(seconds%86_400/3600,"h"),(seconds%3600/60,"m"),(seconds%60,"s")];
+        let mut first=true;
+        for (value,unit) in units{
+            if value==0{
+                continue
+            }
+            if !first{
+                f.write_str(" ")?;
+            }
+            write!(f,"{}{}",value,unit)?;
+            first=false;
+        }
+        Ok(())
+    }
+}
+
+/// Parses numbers followed by the units `w`, `d`, `h`, `m` or `s`, such as `90m`, `2h30m` or `1d 12h`,
+/// optionally preceded by `-`. The units may appear in any order and repeat, their values add up.
+///
+/// Returns TimeStringError for an empty string, a number without unit, an unknown unit or an overflow.
+/// # Example
+/// ```
+/// use utc_datetime::Duration;
+/// assert_eq!("2h30m".parse::<Duration>().unwrap(),Duration::minutes(150));
+/// assert_eq!("-1d 12h".parse::<Duration>().unwrap(),-Duration::hours(36));
+/// assert!("90".parse::<Duration>().is_err());
+/// ```
+impl FromStr for Duration{
+    type Err=IllegalTimeError;
+
+    fn from_str(s:&str)->Result<Duration, IllegalTimeError>{
+        let (negative,mut rest)=match s.trim().strip_prefix('-'){
+            Some(rest)=>(true,rest),
+            None=>(false,s.trim()),
+        };
+        if rest.is_empty(){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        let mut seconds:i64=0;
+        while !rest.is_empty(){
+            let digits=rest.bytes().take_while(u8::is_ascii_digit).count();
+            let value:i64=rest[..digits].parse().map_err(|_|IllegalTimeError::TimeStringError)?;
+            let unit=match rest.as_bytes().get(digits){
+                Some(b'w')=>7*24*60*60,
+                Some(b'd')=>24*60*60,
+                Some(b'h')=>60*60,
+                Some(b'm')=>60,
+                Some(b's')=>1,
+                _=>return Err(IllegalTimeError::TimeStringError)
+            };
+            seconds=value.checked_mul(unit).and_then(|v|seconds.checked_add(v)).ok_or(IllegalTimeError::TimeStringError)?;
+            rest=rest[digits+1..].trim_start();
+        }
+        Ok(Duration{seconds:if negative {-seconds} else {seconds}})
+    }
+}
+
+/// An ISO 8601 duration such as `P1Y2M3DT4H5M6S`, whose years and months have no fixed length
+///
+/// [`UtcDatetime::add_iso_duration`] applies it like XML Schema does: first the years and months,
+/// moving the day back to the last day of the month if the month is shorter, then the weeks, days
+/// and time as exact seconds. A negative duration subtracts the components in the same order.
+/// # Example
+/// ```
+/// use utc_datetime::{IsoDuration, UtcDatetime};
+/// let d:IsoDuration="P1M1D".parse().unwrap();
+/// assert_eq!((d.months,d.days),(1,1));
+/// let a_date=UtcDatetime::new(2021,1,31,9,30,0).unwrap();
+/// // January 31 + 1 month is February 28, then 1 day later
+/// assert_eq!(a_date.add_iso_duration(&d).unwrap(),UtcDatetime::new(2021,3,1,9,30,0).unwrap());
+/// assert_eq!(d.to_string(),"P1M1D");
+/// ```
+#[derive(Clone,Copy,PartialEq,Eq,Hash,Debug,Default)]
+pub struct IsoDuration{
+    pub negative:bool,
+    pub years:u32,
+    pub months:u32,
+    pub weeks:u32,
+    pub days:u32,
+    pub hours:u32,
+    pub minutes:u32,
+    pub seconds:u32,
+}
+
+impl IsoDuration{
+    /// The exact duration, or None if there are years or months
+    pub fn to_duration(&self)->Option<Duration>{
+        if self.years!=0 || self.months!=0{
+            return None
+        }
+        let seconds=((self.weeks as i64*7+self.days as i64)*24+self.hours as i64)*3600+self.minutes as i64*60+self.seconds as i64;
+        Some(Duration{seconds:if self.negative {-seconds} else {seconds}})
+    }
+}
+
+/// Splits into days, hours, minutes and seconds, days above u32::MAX saturate
+impl From<Duration> for IsoDuration{
+    fn from(duration:Duration)->IsoDuration{
+        let seconds=duration.seconds.unsigned_abs();
+        IsoDuration{
+            negative:duration.is_negative(),
+            days:u32::try_from(seconds/86_400).unwrap_or(u32::MAX),
+            hours:(seconds%86_400/3600) as u32,
+            minutes:(seconds%3600/60) as u32,
+            seconds:(seconds%60) as u32,
+            ..IsoDuration::default()
+        }
+    }
+}
+
+/// Formats with the zero components left out, `PT0S` if every component is zero
+impl fmt::Display for IsoDuration{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        if self.negative{
+            f.write_str("-")?;
+        }
+        f.write_str("P")?;
+        for (value,unit) in [(self.years,'Y'),(self.months,'M'),(self.weeks,'W'),(self.days,'D')]{
+            if value!=0{
+                write!(f,"{}{}",value,unit)?;
+            }
+        }
+        let time=[(self.hours,'H'),(self.minutes,'M'),(self.seconds,'S')];
+        if time.iter().any(|&(value,_)|value!=0){
+            f.write_str("T")?;
+            for (value,unit) in time{
+                if value!=0{
+                    write!(f,"{}{}",value,unit)?;
+                }
+            }
+        }else if [self.years,self.months,self.weeks,self.days].iter().all(|&value|value==0){
+            f.write_str("T0S")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses `PnYnMnWnDTnHnMnS` with any components left out and an optional leading `-`.
+///
+/// A fraction is only allowed on the seconds and is truncated.
+/// Returns TimeStringError if the components are missing, repeated or out of order.
+impl FromStr for IsoDuration{
+    type Err=IllegalTimeError;
+
+    fn from_str(s:&str)->Result<IsoDuration, IllegalTimeError>{
+        let (negative,s)=match s.strip_prefix('-'){
+            Some(rest)=>(true,rest),
+            None=>(false,s),
+        };
+        let s=s.strip_prefix('P').ok_or(IllegalTimeError::TimeStringError)?;
+        let (date,time)=match s.split_once('T'){
+            Some((_,""))=>return Err(IllegalTimeError::TimeStringError),
+            Some((date,time))=>(date,Some(time)),
+            None=>(s,None),
+        };
+        let mut duration=IsoDuration{negative,..IsoDuration::default()};
+        let mut found=false;
+        let mut date_units=[('Y',&mut duration.years),('M',&mut duration.months),('W',&mut duration.weeks),('D',&mut duration.days)];
+        found|=parse_components(date,&mut date_units,false)?;
+        if let Some(time)=time{
+            let mut time_units=[('H',&mut duration.hours),('M',&mut duration.minutes),('S',&mut duration.seconds)];
+            found|=parse_components(time,&mut time_units,true)?;
+        }
+        if !found{
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        Ok(duration)
+    }
+}
+
+// 按units的顺序读取"数字+单位"的组件,每个单位最多出现一次,返回是否读到了组件
+fn parse_components(mut s:&str,units:&mut [(char,&mut u32)],fraction_on_seconds:bool)->Result<bool, IllegalTimeError>{
+    let mut found=false;
+    let mut next_unit=0;
+    while !s.is_empty(){
+        let digits=s.bytes().take_while(u8::is_ascii_digit).count();
+        let value:u32=s[..digits].parse().map_err(|_|IllegalTimeError::TimeStringError)?;
+        let mut rest=&s[digits..];
+        // 秒可以带小数,直接截断
+        if fraction_on_seconds{
+            if let Some(fraction)=rest.strip_prefix(['.',',']){
+                let fraction_digits=fraction.bytes().take_while(u8::is_ascii_digit).count();
+                if fraction_digits==0 || !fraction[fraction_digits..].starts_with('S'){
+                    return Err(IllegalTimeError::TimeStringError)
+                }
+                rest=&fraction[fraction_digits..];
+            }
+        }
+        let unit=rest.chars().next().ok_or(IllegalTimeError::TimeStringError)?;
+        let position=units[next_unit..].iter().position(|(u,_)|*u==unit).ok_or(IllegalTimeError::TimeStringError)?;
+        next_unit+=position;
+        *units[next_unit].1=value;
+        next_unit+=1;
+        found=true;
+        s=&rest[1..];
+    }
+    Ok(found)
+}
+
+impl UtcDatetime{
+    /// Apply an ISO 8601 duration, see [`IsoDuration`] for the rules.
+    ///
+    /// Returns YearNumberError if the result is outside the representable range.
+    pub fn add_iso_duration(&self,duration:&IsoDuration)->Result<UtcDatetime, IllegalTimeError>{
+        let months=duration.years as i64*12+duration.months as i64;
+        let moved=self.add_months_clamped(if duration.negative {-months} else {months})?;
+        let exact=IsoDuration{years:0,months:0,..*duration}.to_duration().unwrap_or(Duration::ZERO);
+        moved.add_duration(exact)
+    }
+
+    /// The exact duration from `self` to `other`, negative if `other` is earlier
+    pub fn until(&self,other:&UtcDatetime)->Duration{
+        Duration{seconds:other.unix_seconds()-self.unix_seconds()}
+    }
+
+    /// Whether `self` and `other` are at most `within` apart in either direction, for
+    /// comparing timestamps from systems whose clocks are skewed
+    ///
+    /// A negative `within` never matches.
+    /// # Example
+    /// ```
+    /// use utc_datetime::{Duration, UtcDatetime};
+    /// let sent=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// let received=UtcDatetime::new(2021,11,15,9,29,58).unwrap();
+    /// assert!(received.approx_eq(&sent,Duration::seconds(2)));
+    /// assert!(!received.approx_eq(&sent,Duration::seconds(1)));
+    /// assert!(!sent.approx_eq(&sent,Duration::seconds(-2)));
+    /// ```
+    pub fn approx_eq(&self,other:&UtcDatetime,within:Duration)->bool{
+        // 两端之差最大约6.3e13秒,取绝对值不会溢出
+        within.seconds>=0 && self.until(other).seconds.abs()<=within.seconds
+    }
+
+    /// The time left from `now` until `self` as a deadline, None once it has passed
+    /// # Example
+    /// ```
+    /// use utc_datetime::{Duration, UtcDatetime};
+    /// let expires=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// let now=UtcDatetime::new(2021,11,15,9,0,0).unwrap();
+    /// assert_eq!(expires.time_remaining_until(&now),Some(Duration::minutes(30)));
+    /// assert_eq!(expires.time_remaining_until(&expires),None);
+    /// ```
+    pub fn time_remaining_until(&self,now:&UtcDatetime)->Option<Duration>{
+        Some(now.until(self)).filter(|remaining|remaining.seconds>0)
+    }
+
+    /// Whether `self` as a deadline has passed at `now`, which is from the deadline itself on
+    pub fn is_past(&self,now:&UtcDatetime)->bool{
+        self<=now
+    }
+
+    /// Move by `duration`, which may be negative.
+    ///
+    /// Returns YearNumberError if the result is outside the representable range.
+    pub fn add_duration(&self,duration:Duration)->Result<UtcDatetime, IllegalTimeError>{
+        let seconds=self.unix_seconds().checked_add(duration.seconds).ok_or(IllegalTimeError::YearNumberError)?;
+        UtcDatetime::from_unix_seconds(seconds)
+    }
+
+    /// Move by `duration`, clamping to [`UtcDatetime::MIN`] or [`UtcDatetime::MAX`] instead of failing
+    /// # Example
+    /// ```
+    /// use utc_datetime::{Duration, UtcDatetime};
+    /// let a_date=UtcDatetime::new(-999_999,1,1,9,30,0).unwrap();
+    /// assert_eq!(a_date.saturating_sub_duration(Duration::days(1)),UtcDatetime::MIN);
+    /// assert_eq!(a_date.saturating_add_duration(Duration::seconds(i64::MAX)),UtcDatetime::MAX);
+    /// assert_eq!(a_date.saturating_add_duration(Duration::hours(1)).hour(),10);
+    /// ```
+    pub fn saturating_add_duration(&self,duration:Duration)->UtcDatetime{
+        let seconds=self.unix_seconds().saturating_add(duration.seconds);
+        let seconds=seconds.clamp(UtcDatetime::MIN.unix_seconds(),UtcDatetime::MAX.unix_seconds());
+        UtcDatetime::from_unix_seconds(seconds).unwrap_or(UtcDatetime::MAX)
+    }
+
+    /// Move back by `duration`, see [`UtcDatetime::saturating_add_duration`]
+    pub fn saturating_sub_duration(&self,duration:Duration)->UtcDatetime{
+        self.saturating_add_duration(Duration{seconds:duration.seconds.saturating_neg()})
+    }
+
+    /// Add seconds to the time of day only, wrapping around midnight and keeping the date.
+    ///
+    /// Returns the new datetime and the number of days that were carried, negative when
+    /// wrapping backwards, so the date would have moved by that many days.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let late_shift=UtcDatetime::new(2021,11,15,22,0,0).unwrap();
+    /// assert_eq!(late_shift.overflowing_add_seconds(4*3600),(UtcDatetime::new(2021,11,15,2,0,0).unwrap(),1));
+    /// assert_eq!(late_shift.overflowing_add_seconds(-23*3600),(UtcDatetime::new(2021,11,15,23,0,0).unwrap(),-1));
+    /// ```
+    pub fn overflowing_add_seconds(&self,seconds:i64)->(UtcDatetime,i64){
+        let of_day=self.hour as i64*3600+self.minute as i64*60+self.second as i64;
+        let carried=(of_day as i128+seconds as i128).div_euclid(86_400) as i64;
+        let of_day=(of_day as i128+seconds as i128).rem_euclid(86_400) as i64;
+        let dt=UtcDatetime{hour:(of_day/3600) as u8,minute:(of_day%3600/60) as u8,second:(of_day%60) as u8,..*self};
+        (dt,carried)
+    }
+
+    /// Add seconds to the time of day only, see [`UtcDatetime::overflowing_add_seconds`]
+    pub fn wrapping_add_seconds(&self,seconds:i64)->UtcDatetime{
+        self.overflowing_add_seconds(seconds).0
+    }
+
+    /// Move back by `duration`, see [`UtcDatetime::add_duration`]
+    pub fn sub_duration(&self,duration:Duration)->Result<UtcDatetime, IllegalTimeError>{
+        let seconds=self.unix_seconds().checked_sub(duration.seconds).ok_or(IllegalTimeError::YearNumberError)?;
+        UtcDatetime::from_unix_seconds(seconds)
+    }
+}
+
+// 标准库的Duration只取整秒,超出i64的部分视为溢出
+fn std_seconds(duration:std::time::Duration)->Duration{
+    Duration{seconds:i64::try_from(duration.as_secs()).unwrap_or(i64::MAX)}
+}
+
+/// Panics if the result is outside the representable range, use [`UtcDatetime::add_duration`]
+/// to handle that case. Fractions of a second are truncated.
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// use std::time::Duration;
+/// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+/// assert_eq!(a_date+Duration::from_millis(90_500),UtcDatetime::new(2021,11,15,9,31,30).unwrap());
+/// assert_eq!(a_date-Duration::from_secs(3600),UtcDatetime::new(2021,11,15,8,30,0).unwrap());
+/// ```
+impl Add<std::time::Duration> for UtcDatetime{
+    type Output=UtcDatetime;
+
+    fn add(self,rhs:std::time::Duration)->UtcDatetime{
+        self.add_duration(std_seconds(rhs)).expect("overflow when adding duration to datetime")
+    }
+}
+
+/// Panics if the result is outside the representable range, see the `Add` implementation
+impl Sub<std::time::Duration> for UtcDatetime{
+    type Output=UtcDatetime;
+
+    fn sub(self,rhs:std::time::Duration)->UtcDatetime{
+        self.sub_duration(std_seconds(rhs)).expect("overflow when subtracting duration from datetime")
+    }
+}
+
+/// Panics if the result is outside the representable range, see the `Add` implementation
+impl Add<Duration> for UtcDatetime{
+    type Output=UtcDatetime;
+
+    fn add(self,rhs:Duration)->UtcDatetime{
+        self.add_duration(rhs).expect("overflow when adding duration to datetime")
+    }
+}
+
+/// Panics if the result is outside the representable range, see the `Add` implementation
+impl Sub<Duration> for UtcDatetime{
+    type Output=UtcDatetime;
+
+    fn sub(self,rhs:Duration)->UtcDatetime{
+        self.sub_duration(rhs).expect("overflow when subtracting duration from datetime")
+    }
+}
+
+/// Panics like the `Add` implementation, `self` is unchanged then
+/// # Example
+/// ```
+/// use utc_datetime::{Duration, UtcDatetime};
+/// let mut dt=UtcDatetime::new(2021,11,15,23,30,0).unwrap();
+/// dt+=Duration::hours(1);
+/// dt-=std::time::Duration::from_secs(60);
+/// assert_eq!(dt,UtcDatetime::new(2021,11,16,0,29,0).unwrap());
+/// ```
+impl AddAssign<Duration> for UtcDatetime{
+    fn add_assign(&mut self,rhs:Duration){
+        *self=*self+rhs;
+    }
+}
+
+impl SubAssign<Duration> for UtcDatetime{
+    fn sub_assign(&mut self,rhs:Duration){
+        *self=*self-rhs;
+    }
+}
+
+impl AddAssign<std::time::Duration> for UtcDatetime{
+    fn add_assign(&mut self,rhs:std::time::Duration){
+        *self=*self+rhs;
+    }
+}
+
+impl SubAssign<std::time::Duration> for UtcDatetime{
+    fn sub_assign(&mut self,rhs:std::time::Duration){
+        *self=*self-rhs;
+    }
+}
+
+impl AddAssign for Duration{
+    fn add_assign(&mut self,rhs:Duration){
+        *self=*self+rhs;
+    }
+}
+
+impl SubAssign for Duration{
+    fn sub_assign(&mut self,rhs:Duration){
+        *self=*self-rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::Duration;
+    use crate::UtcDatetime;
+
+    #[test]
+    fn until_and_back(){
+        assert_eq!(UtcDatetime::MIN.until(&UtcDatetime::MAX).whole_seconds(),UtcDatetime::MAX.unix_seconds()-UtcDatetime::MIN.unix_seconds());
+        assert_eq!(UtcDatetime::UNIX_EPOCH.until(&UtcDatetime::MAX).whole_seconds(),UtcDatetime::MAX.unix_seconds());
+        assert_eq!(UtcDatetime::MAX.until(&UtcDatetime::MIN),-UtcDatetime::MIN.until(&UtcDatetime::MAX));
+        assert!(UtcDatetime::MIN.sub_duration(Duration::seconds(1)).is_err());
+        assert!(UtcDatetime::MAX.add_duration(Duration::seconds(1)).is_err());
+        assert!(UtcDatetime::MIN.add_duration(Duration::seconds(i64::MAX)).is_err());
+        assert_eq!(UtcDatetime::MAX.sub_duration(UtcDatetime::MIN.until(&UtcDatetime::MAX)).unwrap(),UtcDatetime::MIN);
+        let d=Duration::seconds(-90);
+        assert_eq!((d.whole_minutes(),d.abs().whole_minutes(),d.is_negative()),(-1,1,true));
+        assert_eq!(Duration::weeks(1)-Duration::days(7),Duration::ZERO);
+        assert!(!UtcDatetime::MAX.approx_eq(&UtcDatetime::MAX,Duration::seconds(-1)));
+        assert!(!UtcDatetime::MIN.approx_eq(&UtcDatetime::MAX,Duration::seconds(i64::MIN)));
+        assert!(UtcDatetime::MAX.approx_eq(&UtcDatetime::MAX,Duration::ZERO));
+        assert!(UtcDatetime::MAX.approx_eq(&UtcDatetime::MIN,UtcDatetime::MIN.until(&UtcDatetime::MAX)));
+        assert!(!UtcDatetime::MAX.approx_eq(&UtcDatetime::MIN,UtcDatetime::MIN.until(&UtcDatetime::MAX)-Duration::seconds(1)));
+    }
+
+    #[test]
+    fn human_format(){
+        for d in [Duration::ZERO,Duration::seconds(1),Duration::days(400)+Duration::seconds(59),-Duration::hours(25),Duration::seconds(i64::MAX)]{
+            assert_eq!(d.to_string().parse::<Duration>().unwrap(),d,"{}",d);
+        }
+        assert_eq!("1d2h3m4s".parse::<Duration>().unwrap().to_string(),"1d 2h 3m 4s");
+        assert_eq!("2w".parse::<Duration>().unwrap(),Duration::days(14));
+        for s in ["","-","1x","h","1h 30","1.5h","- 1h","99999999999999999999s"]{
+            assert!(s.parse::<Duration>().is_err(),"{}",s);
+        }
+    }
+
+    #[test]
+    fn iso_duration(){
+        use super::IsoDuration;
+        let full:IsoDuration="P1Y2M3W4DT5H6M7.5S".parse().unwrap();
+        assert_eq!(full,IsoDuration{negative:false,years:1,months:2,weeks:3,days:4,hours:5,minutes:6,seconds:7});
+        assert_eq!(full.to_string(),"P1Y2M3W4DT5H6M7S");
+        for s in ["PT0S","P1D","-PT36H","P1Y","PT1M","P2W"]{
+            assert_eq!(s.parse::<IsoDuration>().unwrap().to_string(),s);
+        }
+        assert_eq!("PT36H".parse::<IsoDuration>().unwrap().to_duration(),Some(Duration::hours(36)));
+        assert_eq!("P1M".parse::<IsoDuration>().unwrap().to_duration(),None);
+        assert_eq!(IsoDuration::from(-Duration::seconds(90_061)).to_string(),"-P1DT1H1M1S");
+        for s in ["P","PT","P1H","PT1D","P1D1Y","P1Y1Y","1Y","P1.5Y","PT1.S","P-1D","P1DT"]{
+            assert!(s.parse::<IsoDuration>().is_err(),"{}",s);
+        }
+        let leap_day=UtcDatetime::new(2020,2,29,12,0,0).unwrap();
+        assert_eq!(leap_day.add_iso_duration(&"P1Y".parse().unwrap()).unwrap(),UtcDatetime::new(2021,2,28,12,0,0).unwrap());
+        assert_eq!(leap_day.add_iso_duration(&"-P1MT12H".parse().unwrap()).unwrap(),UtcDatetime::new(2020,1,29,0,0,0).unwrap());
+        assert!(UtcDatetime::MIN.add_iso_duration(&"-P1M".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn std_duration_overflow(){
+        let _=UtcDatetime::MAX+std::time::Duration::from_secs(u64::MAX);
+    }
+
+    #[test]
+    fn assign_operators(){
+        let mut dt=UtcDatetime::UNIX_EPOCH;
+        let mut total=Duration::ZERO;
+        for _ in 0..48{
+            dt+=Duration::minutes(30);
+            total+=Duration::minutes(30);
+        }
+        assert_eq!((dt,total),(UtcDatetime::new(1970,1,2,0,0,0).unwrap(),Duration::days(1)));
+        dt-=Duration::days(1);
+        total-=Duration::days(1);
+        assert_eq!((dt,total),(UtcDatetime::UNIX_EPOCH,Duration::ZERO));
+    }
+
+    #[test]
+    fn saturating(){
+        assert_eq!(UtcDatetime::MAX.saturating_sub_duration(Duration::seconds(i64::MIN)),UtcDatetime::MAX);
+        assert_eq!(UtcDatetime::MIN.saturating_add_duration(Duration::seconds(i64::MIN)),UtcDatetime::MIN);
+        assert_eq!(UtcDatetime::MAX.saturating_sub_duration(Duration::seconds(1)),UtcDatetime::new(999_999,12,31,23,59,58).unwrap());
+        assert_eq!(UtcDatetime::UNIX_EPOCH.saturating_sub_duration(Duration::days(1)),UtcDatetime::new(1969,12,31,0,0,0).unwrap());
+    }
+
+    #[test]
+    fn deadline(){
+        let deadline=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        assert_eq!(deadline.time_remaining_until(&UtcDatetime::MIN),Some(UtcDatetime::MIN.until(&deadline)));
+        assert_eq!(deadline.time_remaining_until(&UtcDatetime::MAX),None);
+        assert!(!deadline.is_past(&UtcDatetime::new(2021,11,15,9,29,59).unwrap()));
+        assert!(deadline.is_past(&deadline));
+        assert!(deadline.is_past(&UtcDatetime::MAX));
+    }
+
+    #[test]
+    fn wrapping_time_of_day(){
+        let dt=UtcDatetime::new(2021,11,15,0,0,0).unwrap();
+        assert_eq!(dt.overflowing_add_seconds(-1),(UtcDatetime::new(2021,11,15,23,59,59).unwrap(),-1));
+        assert_eq!(dt.overflowing_add_seconds(86_400*3),(dt,3));
+        assert_eq!(UtcDatetime::MAX.wrapping_add_seconds(1),UtcDatetime::new(999_999,12,31,0,0,0).unwrap());
+        let (wrapped,carried)=dt.overflowing_add_seconds(i64::MIN);
+        assert_eq!(carried as i128*86_400+wrapped.hour() as i128*3600+wrapped.minute() as i128*60+wrapped.second() as i128,i64::MIN as i128);
+    }
+}
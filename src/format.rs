@@ -0,0 +1,505 @@
+//! Format descriptions that are compiled once and reused for formatting and parsing.
+//!
+//! A description is written with `strftime`-like specifiers:
+//!
+//! | specifier | meaning |
+//! |-----------|---------|
+//! | `%Y` | year, at least 4 digits, negative years start with `-` |
+//! | `%m` | month, 2 digits |
+//! | `%B` | month name, such as `November` |
+//! | `%b` | abbreviated month name, such as `Nov` |
+//! | `%d` | day, 2 digits |
+//! | `%e` | day, padded with a space to 2 characters |
+//! | `%o` | day with an English ordinal suffix, such as `1st`, `2nd` or `15th` |
+//! | `%H` | hour, 2 digits |
+//! | `%M` | minute, 2 digits |
+//! | `%S` | second, 2 digits |
+//! | `%j` | day of the year, 3 digits |
+//! | `%G` | ISO 8601 week-numbering year, at least 4 digits |
+//! | `%V` | ISO 8601 week number, 2 digits |
+//! | `%u` | ISO 8601 weekday, 1 (Monday) to 7 (Sunday) |
+//! | `%EC` | Japanese era name, such as `令和` |
+//! | `%Ey` | year of the Japanese era |
+//! | `%EY` | Japanese era and year, such as `令和3年`, with `元年` for the first year |
+//! | `%%` | a literal `%` |
+//!
+//! A padded number can be prefixed with a modifier: `-` for no padding as in `%-d`,
+//! `_` for spaces as in `%_H`, and `0` for zeros as in `%0e`.
+//!
+//! Everything else is copied literally. Month names are parsed without regard to case.
+//! The `%o` and Japanese era specifiers can only be formatted, not parsed, and the era specifiers
+//! fail for dates before 1873.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::format::FormatDescription;
+//! let fd=FormatDescription::new("%d/%m/%Y %H:%M").unwrap();
+//! let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+//! assert_eq!(a_date.format(&fd),"15/11/2021 09:30");
+//! assert!(FormatDescription::new("%Q").is_err());
+//! // ISO 8601 ordinal date
+//! let ordinal=FormatDescription::new("%Y-%jT%H:%M:%SZ").unwrap();
+//! assert_eq!(a_date.format(&ordinal),"2021-319T09:30:00Z");
+//! assert_eq!(UtcDatetime::parse("2021-319T09:30:00Z",&ordinal).unwrap(),a_date);
+//! // ISO 8601 week date
+//! let week=FormatDescription::new("%G-W%V-%u").unwrap();
+//! assert_eq!(a_date.format(&week),"2021-W46-1");
+//! assert_eq!(UtcDatetime::parse("2021-W46-1",&week).unwrap(),UtcDatetime::new(2021,11,15,0,0,0).unwrap());
+//! // for email and reports
+//! let human=FormatDescription::new("%B %o, %Y").unwrap();
+//! assert_eq!(UtcDatetime::new(2021,11,21,0,0,0).unwrap().format(&human),"November 21st, 2021");
+//! // padding modifiers
+//! let legacy=FormatDescription::new("%b %e %-H:%M").unwrap();
+//! assert_eq!(UtcDatetime::new(2021,11,5,9,30,0).unwrap().format(&legacy),"Nov  5 9:30");
+//! assert_eq!(UtcDatetime::parse("Nov  5 9:30",&legacy).unwrap(),UtcDatetime::new(1970,11,5,9,30,0).unwrap());
+//! ```
+use std::borrow::Cow;
+use std::fmt;
+use crate::{civil_from_days, days_from_civil, days_of_the_year, iso_weeks, IllegalTimeError, UtcDatetime};
+
+/// One component of a [`FormatDescription`]
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub enum FormatItem<'a>{
+    Literal(&'a str),
+    Year,
+    Month,
+    /// The English month name, `%B`
+    MonthName,
+    /// The first three letters of the English month name, `%b`
+    MonthAbbr,
+    Day,
+    /// The day followed by `st`, `nd`, `rd` or `th`, `%o`
+    DayOrdinal,
+    Hour,
+    Minute,
+    Second,
+    /// The day of the year, `%j`
+    Ordinal,
+    IsoYear,
+    IsoWeek,
+    /// The ISO 8601 weekday, 1 (Monday) to 7 (Sunday)
+    IsoWeekday,
+    JapaneseEraName,
+    JapaneseEraYear,
+    /// The era name and year followed by `年`, the first year is written `元年`
+    JapaneseEraFull,
+    /// Pad the number that follows in another way, written as a modifier such as `%-d`
+    Pad(Padding),
+}
+
+/// How [`FormatItem::Pad`] pads the next number to its usual width
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub enum Padding{
+    /// No padding, `-`
+    None,
+    /// Leading spaces, `_`
+    Space,
+    /// Leading zeros, `0`, the default
+    Zero,
+}
+
+impl FormatItem<'_>{
+    fn is_numeric(&self)->bool{
+        !matches!(self,FormatItem::Literal(_)|FormatItem::MonthName|FormatItem::MonthAbbr)
+    }
+
+    // 可以补齐的数字及其宽度
+    fn padded_width(&self)->Option<usize>{
+        match self{
+            FormatItem::Year|FormatItem::IsoYear=>Some(4),
+            FormatItem::Ordinal=>Some(3),
+            FormatItem::Month|FormatItem::Day|FormatItem::Hour|FormatItem::Minute|FormatItem::Second|FormatItem::IsoWeek=>Some(2),
+            _=>None,
+        }
+    }
+}
+
+fn write_padded<W:fmt::Write>(w:&mut W,n:i64,width:usize,padding:Padding)->fmt::Result{
+    match padding{
+        Padding::None=>write!(w,"{}",n),
+        Padding::Space=>write!(w,"{:width$}",n),
+        // 负号不计入宽度,公元前44年写作-0044
+        Padding::Zero if n<0=>write!(w,"-{:0width$}",n.unsigned_abs()),
+        Padding::Zero=>write!(w,"{:0width$}",n),
+    }
+}
+
+const MONTH_NAMES:[&str;12]=["January","February","March","April","May","June","July","August","September","October","November","December"];
+
+// 英文序数词的后缀,11到13日用th
+fn ordinal_suffix(n:u8)->&'static str{
+    match (n%10,n%100){
+        (_,11..=13)=>"th",
+        (1,_)=>"st",
+        (2,_)=>"nd",
+        (3,_)=>"rd",
+        _=>"th",
+    }
+}
+
+/// A compiled format description, see the [module documentation](self) for the syntax.
+#[derive(Clone,PartialEq,Eq,Debug)]
+pub struct FormatDescription<'a>{
+    items:Cow<'a,[FormatItem<'a>]>,
+}
+
+impl<'a> FormatDescription<'a>{
+    /// Compile a format description string.
+    ///
+    /// Returns FormatDescriptionError for an unknown or unterminated `%` specifier.
+    pub fn new(description:&'a str)->Result<FormatDescription<'a>, IllegalTimeError>{
+        let mut items=Vec::new();
+        let mut literal_start=0;
+        let bytes=description.as_bytes();
+        let mut i=0;
+        while i<bytes.len(){
+            if bytes[i]!=b'%'{
+                i+=1;
+                continue
+            }
+            if literal_start<i{
+                items.push(FormatItem::Literal(&description[literal_start..i]));
+            }
+            let padding=match bytes.get(i+1){
+                Some(b'-')=>Some(Padding::None),
+                Some(b'_')=>Some(Padding::Space),
+                Some(b'0')=>Some(Padding::Zero),
+                _=>None,
+            };
+            if padding.is_some(){
+                // 修饰符多占一个字节
+                i+=1;
+            }
+            // %e是用空格补齐的日
+            let padding=match bytes.get(i+1){
+                Some(b'e')=>padding.or(Some(Padding::Space)),
+                _=>padding,
+            };
+            let item=match bytes.get(i+1){
+                Some(b'e')=>FormatItem::Day,
+                Some(b'Y')=>FormatItem::Year,
+                Some(b'm')=>FormatItem::Month,
+                Some(b'B')=>FormatItem::MonthName,
+                Some(b'b')=>FormatItem::MonthAbbr,
+                Some(b'd')=>FormatItem::Day,
+                Some(b'o')=>FormatItem::DayOrdinal,
+                Some(b'H')=>FormatItem::Hour,
+                Some(b'M')=>FormatItem::Minute,
+                Some(b'S')=>FormatItem::Second,
+                Some(b'j')=>FormatItem::Ordinal,
+                Some(b'G')=>FormatItem::IsoYear,
+                Some(b'V')=>FormatItem::IsoWeek,
+                Some(b'u')=>FormatItem::IsoWeekday,
+                Some(b'%')=>FormatItem::Literal(&description[i+1..i+2]),
+                Some(b'E')=>{
+                    let item=match bytes.get(i+2){
+                        Some(b'C')=>FormatItem::JapaneseEraName,
+                        Some(b'y')=>FormatItem::JapaneseEraYear,
+                        Some(b'Y')=>FormatItem::JapaneseEraFull,
+                        _=>return Err(IllegalTimeError::FormatDescriptionError)
+                    };
+                    // 两个字符的修饰符多占一个字节
+                    i+=1;
+                    item
+                }
+                _=>return Err(IllegalTimeError::FormatDescriptionError)
+            };
+            match padding{
+                Some(_) if item.padded_width().is_none()=>return Err(IllegalTimeError::FormatDescriptionError),
+                Some(Padding::Zero)|None=>{}
+                Some(padding)=>items.push(FormatItem::Pad(padding)),
+            }
+            items.push(item);
+            i+=2;
+            literal_start=i;
+        }
+        if literal_start<bytes.len(){
+            items.push(FormatItem::Literal(&description[literal_start..]));
+        }
+        Ok(FormatDescription{items:Cow::Owned(items)})
+    }
+
+    /// Create a format description from already compiled items, usable in a `const`.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// use utc_datetime::format::{FormatDescription, FormatItem};
+    /// const DATE:FormatDescription=FormatDescription::from_items(&[
+    ///     FormatItem::Year,FormatItem::Literal("."),FormatItem::Month,FormatItem::Literal("."),FormatItem::Day,
+    /// ]);
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.format(&DATE),"2021.11.15");
+    /// assert_eq!(DATE,FormatDescription::new("%Y.%m.%d").unwrap());
+    /// ```
+    pub const fn from_items(items:&'a [FormatItem<'a>])->FormatDescription<'a>{
+        FormatDescription{items:Cow::Borrowed(items)}
+    }
+
+    /// The compiled items
+    pub fn items(&self)->&[FormatItem<'a>]{
+        &self.items
+    }
+}
+
+impl UtcDatetime{
+    /// Format the datetime according to a compiled [`FormatDescription`].
+    ///
+    /// The Japanese era specifiers cannot be written before 1873, the output then stops there;
+    /// use [`UtcDatetime::try_format`] to get an error instead.
+    pub fn format(&self,description:&FormatDescription)->String{
+        let mut s=String::new();
+        let _=self.format_with_into(description,&mut s);
+        s
+    }
+
+    /// Format the datetime according to a compiled [`FormatDescription`], returns YearNumberError
+    /// if the description has a Japanese era specifier and the date is before 1873.
+    /// # Example
+    /// ```
+    /// use utc_datetime::{IllegalTimeError, UtcDatetime};
+    /// use utc_datetime::format::FormatDescription;
+    /// let fd=FormatDescription::new("%EY").unwrap();
+    /// assert_eq!(UtcDatetime::new(1912,7,30,0,0,0).unwrap().try_format(&fd).unwrap(),"大正元年");
+    /// assert!(matches!(UtcDatetime::new(1872,12,31,0,0,0).unwrap().try_format(&fd),Err(IllegalTimeError::YearNumberError)));
+    /// ```
+    pub fn try_format(&self,description:&FormatDescription)->Result<String, IllegalTimeError>{
+        let mut s=String::new();
+        // 写入String只会因为没有年号而失败
+        self.format_with_into(description,&mut s).map_err(|_|IllegalTimeError::YearNumberError)?;
+        Ok(s)
+    }
+
+    /// Write the datetime according to a compiled [`FormatDescription`] to a `fmt::Write`.
+    ///
+    /// Returns `fmt::Error` for a Japanese era specifier before 1873.
+    pub fn format_with_into<W:fmt::Write>(&self,description:&FormatDescription,w:&mut W)->fmt::Result{
+        let mut padding=Padding::Zero;
+        for item in description.items(){
+            match *item{
+                FormatItem::Literal(s)=>w.write_str(s)?,
+                FormatItem::Year=>write_padded(w,self.year as i64,4,padding)?,
+                FormatItem::Month=>write_padded(w,self.month as i64,2,padding)?,
+                FormatItem::MonthName=>w.write_str(MONTH_NAMES[self.month as usize-1])?,
+                FormatItem::MonthAbbr=>w.write_str(&MONTH_NAMES[self.month as usize-1][..3])?,
+                FormatItem::Day=>write_padded(w,self.day as i64,2,padding)?,
+                FormatItem::DayOrdinal=>write!(w,"{}{}",self.day,ordinal_suffix(self.day))?,
+                FormatItem::Hour=>write_padded(w,self.hour as i64,2,padding)?,
+                FormatItem::Minute=>write_padded(w,self.minute as i64,2,padding)?,
+                FormatItem::Second=>write_padded(w,self.second as i64,2,padding)?,
+                FormatItem::Ordinal=>write_padded(w,self.ordinal() as i64,3,padding)?,
+                FormatItem::IsoYear=>write_padded(w,self.iso_week().0 as i64,4,padding)?,
+                FormatItem::IsoWeek=>write_padded(w,self.iso_week().1 as i64,2,padding)?,
+                FormatItem::IsoWeekday=>write!(w,"{}",(self.weekday()+6)%7+1)?,
+                // 1873年以前没有年号,不写出负数年份
+                FormatItem::JapaneseEraName=>w.write_str(self.japanese_era().map_err(|_|fmt::Error)?.0.name())?,
+                FormatItem::JapaneseEraYear=>write!(w,"{}",self.japanese_era().map_err(|_|fmt::Error)?.1)?,
+                FormatItem::JapaneseEraFull=>match self.japanese_era().map_err(|_|fmt::Error)?{
+                    (era,1)=>write!(w,"{}元年",era.name())?,
+                    (era,year)=>write!(w,"{}{}年",era.name(),year)?,
+                },
+                FormatItem::Pad(next)=>{
+                    padding=next;
+                    continue
+                }
+            }
+            padding=Padding::Zero;
+        }
+        Ok(())
+    }
+
+    /// Parse a string that exactly matches a compiled [`FormatDescription`].
+    ///
+    /// Fields missing from the description default to January 1, 1970 00:00:00.
+    /// `%Y` reads 4 digits, or up to 6 when it is not directly followed by another number; a leading `-` marks a negative year.
+    /// `%j` takes precedence over `%m` and `%d`. If any of `%G`, `%V` and `%u` is present the date
+    /// is read as an ISO week date instead, where a missing week or weekday defaults to 1.
+    /// Returns TimeStringError if the string does not match the description,
+    /// and FormatDescriptionError if the description has specifiers that cannot be parsed.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// use utc_datetime::format::FormatDescription;
+    /// let fd=FormatDescription::new("%Y%m%d-%H%M").unwrap();
+    /// assert_eq!(UtcDatetime::parse("20211115-0930",&fd).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+    /// assert!(UtcDatetime::parse("20211115-093",&fd).is_err());
+    /// ```
+    pub fn parse(time_str:&str,description:&FormatDescription)->Result<UtcDatetime, IllegalTimeError>{
+        let mut rest=time_str.as_bytes();
+        let (mut year,mut month,mut day,mut hour,mut minute,mut second)=(1970,1,1,0,0,0);
+        let mut ordinal=None;
+        let mut week_date:Option<(u32,u32,u32)>=None;
+        let items=description.items();
+        let mut padding=Padding::Zero;
+        for (i,item) in items.iter().enumerate(){
+            let width=match item{
+                &FormatItem::Pad(next)=>{
+                    padding=next;
+                    continue
+                }
+                FormatItem::Literal(s)=>{
+                    rest=rest.strip_prefix(s.as_bytes()).ok_or(IllegalTimeError::TimeStringError)?;
+                    continue
+                }
+                FormatItem::MonthName|FormatItem::MonthAbbr=>{
+                    // 取能匹配的名称,不区分大小写
+                    let (index,len)=MONTH_NAMES.iter().enumerate().find_map(|(index,name)|{
+                        let name=if *item==FormatItem::MonthAbbr {&name[..3]} else {name};
+                        rest.get(..name.len()).filter(|s|s.eq_ignore_ascii_case(name.as_bytes())).map(|_|(index,name.len()))
+                    }).ok_or(IllegalTimeError::TimeStringError)?;
+                    month=index as u8+1;
+                    rest=&rest[len..];
+                    continue
+                }
+                // 年份后面紧跟数字时只能取4位,否则最多取6位
+                FormatItem::Year|FormatItem::IsoYear if !items.get(i+1).is_some_and(FormatItem::is_numeric)=>6,
+                FormatItem::Year|FormatItem::IsoYear=>4,
+                FormatItem::Month|FormatItem::Day|FormatItem::Hour|FormatItem::Minute|FormatItem::Second=>2,
+                FormatItem::Ordinal=>3,
+                FormatItem::IsoWeek=>2,
+                FormatItem::IsoWeekday=>1,
+                FormatItem::DayOrdinal|FormatItem::JapaneseEraName|FormatItem::JapaneseEraYear|FormatItem::JapaneseEraFull=>{
+                    return Err(IllegalTimeError::FormatDescriptionError)
+                }
+            };
+            // 公元前的年份以负号开头
+            let negative=*item==FormatItem::Year && rest.first()==Some(&b'-');
+            if negative{
+                rest=&rest[1..];
+            }
+            let min_width=if width==6 {4} else {width};
+            // 空格补齐时空格和数字合计为常规宽度,不补齐时至少有1位数字
+            let (spaces,min_width)=match std::mem::replace(&mut padding,Padding::Zero){
+                Padding::Zero=>(0,min_width),
+                Padding::None=>(0,1),
+                Padding::Space=>{
+                    let spaces=rest.iter().take(min_width-1).take_while(|b|**b==b' ').count();
+                    (spaces,min_width-spaces)
+                }
+            };
+            rest=&rest[spaces..];
+            let digits=rest.iter().take(width-spaces).take_while(|b|b.is_ascii_digit()).count();
+            if digits<min_width{
+                return Err(IllegalTimeError::TimeStringError)
+            }
+            let value=rest[..digits].iter().fold(0u32,|n,b|n*10+(b-b'0') as u32);
+            rest=&rest[digits..];
+            match item{
+                FormatItem::Year=>year=if negative {-(value as i32)} else {value as i32},
+                FormatItem::Month=>month=value as u8,
+                FormatItem::Day=>day=value as u8,
+                FormatItem::Hour=>hour=value as u8,
+                FormatItem::Minute=>minute=value as u8,
+                FormatItem::Second=>second=value as u8,
+                FormatItem::Ordinal=>ordinal=Some(value as i64),
+                FormatItem::IsoYear=>week_date.get_or_insert((1970,1,1)).0=value,
+                FormatItem::IsoWeek=>week_date.get_or_insert((1970,1,1)).1=value,
+                FormatItem::IsoWeekday=>week_date.get_or_insert((1970,1,1)).2=value,
+                _=>unreachable!(),
+            }
+        }
+        if !rest.is_empty(){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        if let Some(ordinal)=ordinal{
+            if ordinal==0 || ordinal>days_of_the_year(year) as i64{
+                return Err(IllegalTimeError::DayNumberError)
+            }
+            (_,month,day)=civil_from_days(days_from_civil(year as i64,1,1)+ordinal-1);
+        }
+        if let Some((iso_year,week,weekday))=week_date{
+            if week==0 || week>iso_weeks(iso_year as i64) as u32 || weekday==0 || weekday>7{
+                return Err(IllegalTimeError::DayNumberError)
+            }
+            // 第1周是包含1月4日的那一周
+            let jan_4=days_from_civil(iso_year as i64,1,4);
+            let monday=jan_4-(jan_4+3).rem_euclid(7);
+            let (y,m,d)=civil_from_days(monday+(week as i64-1)*7+weekday as i64-1);
+            year=y as i32;
+            (month,day)=(m,d);
+        }
+        UtcDatetime::new(year,month,day,hour,minute,second)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::FormatDescription;
+    use crate::UtcDatetime;
+
+    #[test]
+    fn round_trip(){
+        let fd=FormatDescription::new("%%%Y-%m-%dT%H:%M:%S%%").unwrap();
+        for dt in [UtcDatetime::new(1970,1,1,0,0,0).unwrap(),UtcDatetime::new(65535,12,31,23,59,59).unwrap()]{
+            let s=dt.format(&fd);
+            assert_eq!(UtcDatetime::parse(&s,&fd).unwrap(),dt);
+        }
+        assert_eq!(UtcDatetime::new(2021,11,15,9,30,0).unwrap().format(&fd),"%2021-11-15T09:30:00%");
+        assert!(FormatDescription::new("%").is_err());
+        assert!(UtcDatetime::parse("%2021-11-15T09:30:00",&fd).is_err());
+        let era=FormatDescription::new("%EY%m月%d日 (%EC %Ey)").unwrap();
+        assert_eq!(UtcDatetime::new(2019,5,1,0,0,0).unwrap().format(&era),"令和元年05月01日 (令和 1)");
+        assert_eq!(UtcDatetime::new(1989,1,7,0,0,0).unwrap().format(&era),"昭和64年01月07日 (昭和 64)");
+        assert_eq!(UtcDatetime::new(1873,1,1,0,0,0).unwrap().format(&era),"明治6年01月01日 (明治 6)");
+        assert_eq!(UtcDatetime::new(1900,1,1,0,0,0).unwrap().try_format(&FormatDescription::new("%Ey").unwrap()).unwrap(),"33");
+        assert!(UtcDatetime::new(1872,12,31,0,0,0).unwrap().try_format(&FormatDescription::new("%Ey").unwrap()).is_err());
+        assert_eq!(UtcDatetime::new(1872,1,1,0,0,0).unwrap().format(&FormatDescription::new("%Y %EC").unwrap()),"1872 ");
+        assert!(UtcDatetime::parse("令和 3",&FormatDescription::new("%EC %Ey").unwrap()).is_err());
+        assert!(FormatDescription::new("%E").is_err());
+        let ordinal=FormatDescription::new("%Y-%j").unwrap();
+        assert_eq!(UtcDatetime::parse("2020-366",&ordinal).unwrap(),UtcDatetime::new(2020,12,31,0,0,0).unwrap());
+        assert_eq!(UtcDatetime::parse("2021-060",&ordinal).unwrap(),UtcDatetime::new(2021,3,1,0,0,0).unwrap());
+        assert!(UtcDatetime::parse("2021-366",&ordinal).is_err());
+        assert!(UtcDatetime::parse("2021-000",&ordinal).is_err());
+        assert_eq!(UtcDatetime::new(2021,1,1,0,0,0).unwrap().format(&ordinal),"2021-001");
+        let week=FormatDescription::new("%G-W%V-%u").unwrap();
+        for (s,dt) in [("2020-W53-5",(2021,1,1)),("2021-W52-7",(2022,1,2)),("2025-W01-1",(2024,12,30)),("2020-W01-3",(2020,1,1))]{
+            let dt=UtcDatetime::new(dt.0,dt.1,dt.2,0,0,0).unwrap();
+            assert_eq!(dt.format(&week),s);
+            assert_eq!(UtcDatetime::parse(s,&week).unwrap(),dt);
+        }
+        assert!(UtcDatetime::parse("2021-W53-1",&week).is_err());
+        assert!(UtcDatetime::parse("2021-W10-8",&week).is_err());
+        assert_eq!(UtcDatetime::parse("2021W46",&FormatDescription::new("%GW%V").unwrap()).unwrap(),UtcDatetime::new(2021,11,15,0,0,0).unwrap());
+        let human=FormatDescription::new("%o").unwrap();
+        for (day,s) in [(1,"1st"),(2,"2nd"),(3,"3rd"),(4,"4th"),(11,"11th"),(12,"12th"),(13,"13th"),(21,"21st"),(22,"22nd"),(23,"23rd"),(31,"31st")]{
+            assert_eq!(UtcDatetime::new(2021,1,day,0,0,0).unwrap().format(&human),s);
+        }
+        assert!(UtcDatetime::parse("1st",&human).is_err());
+        let names=FormatDescription::new("%b %d %Y|%B").unwrap();
+        assert_eq!(UtcDatetime::new(2021,9,5,0,0,0).unwrap().format(&names),"Sep 05 2021|September");
+        assert_eq!(UtcDatetime::parse("SEP 05 2021|september",&names).unwrap(),UtcDatetime::new(2021,9,5,0,0,0).unwrap());
+        assert_eq!(UtcDatetime::parse("June 2021",&FormatDescription::new("%B %Y").unwrap()).unwrap(),UtcDatetime::new(2021,6,1,0,0,0).unwrap());
+        assert!(UtcDatetime::parse("Jun 2021",&FormatDescription::new("%B %Y").unwrap()).is_err());
+        let date=FormatDescription::new("%Y-%m-%d").unwrap();
+        for (dt,s) in [((-44,3,15),"-0044-03-15"),((0,1,1),"0000-01-01"),((-999_999,1,1),"-999999-01-01"),((999_999,12,31),"999999-12-31")]{
+            let dt=UtcDatetime::new(dt.0,dt.1,dt.2,0,0,0).unwrap();
+            assert_eq!(dt.format(&date),s);
+            assert_eq!(UtcDatetime::parse(s,&date).unwrap(),dt);
+        }
+        assert!(UtcDatetime::parse("--0044-03-15",&date).is_err());
+    }
+
+    #[test]
+    fn padding(){
+        use super::{FormatItem, Padding};
+        let dt=UtcDatetime::new(2021,1,5,9,3,0).unwrap();
+        for (description,s) in [("%-d/%-m %-H:%M","5/1 9:03"),("%_d|%_H|%_j|%e"," 5| 9|  5| 5"),("%0e %-e %_M","05 5  3"),("%-j %-Y","5 2021")]{
+            let fd=FormatDescription::new(description).unwrap();
+            assert_eq!(dt.format(&fd),s,"{}",description);
+        }
+        assert_eq!(FormatDescription::new("%0e").unwrap(),FormatDescription::new("%d").unwrap());
+        assert_eq!(FormatDescription::new("%-d").unwrap().items(),[FormatItem::Pad(Padding::None),FormatItem::Day]);
+        for description in ["%-","%-o","%_B","%0u","%-%","%-EY","%_q"]{
+            assert!(FormatDescription::new(description).is_err(),"{}",description);
+        }
+        let fd=FormatDescription::new("%Y%-m-%-d").unwrap();
+        assert_eq!(UtcDatetime::parse("20211-5",&fd).unwrap(),UtcDatetime::new(2021,1,5,0,0,0).unwrap());
+        assert_eq!(UtcDatetime::parse("202112-25",&fd).unwrap(),UtcDatetime::new(2021,12,25,0,0,0).unwrap());
+        let fd=FormatDescription::new("%e.%_m.%Y").unwrap();
+        assert_eq!(UtcDatetime::parse(" 5. 1.2021",&fd).unwrap(),UtcDatetime::new(2021,1,5,0,0,0).unwrap());
+        assert_eq!(UtcDatetime::parse("15.11.2021",&fd).unwrap(),UtcDatetime::new(2021,11,15,0,0,0).unwrap());
+        for s in ["5. 1.2021","  5. 1.2021"," 5.01.2021x"]{
+            assert!(UtcDatetime::parse(s,&fd).is_err(),"{}",s);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::leap_seconds::leap_seconds_before;
+use crate::{IllegalTimeError, UtcDatetime};
+
+// 1980-01-06 00:00:00的Unix时间戳
+const GPS_EPOCH:i64=315964800;
+const SECONDS_PER_WEEK:i64=7*24*60*60;
+// GPS时间开始之前的9个闰秒不计入GPS时间
+const LEAP_SECONDS_BEFORE_GPS:i64=9;
+// 传统导航电文的周数只有10位
+const ROLLOVER_WEEKS:i64=1024;
+
+impl UtcDatetime{
+    /// The GPS week number and the seconds into the week.
+    ///
+    /// GPS time started at 1980-01-06 00:00:00 UTC and does not skip leap seconds,
+    /// so it runs ahead of UTC by the leap seconds in [`crate::leap_seconds::LEAP_SECONDS`] since then.
+    /// The week is the full count, not reduced modulo 1024.
+    /// Returns YearNumberError before the GPS epoch.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_gps_week_tow().unwrap(),(2184,120618));
+    /// ```
+    pub fn to_gps_week_tow(&self)->Result<(u32,u32), IllegalTimeError>{
+        let unix=self.unix_seconds();
+        if unix<GPS_EPOCH{
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        let gps=unix-GPS_EPOCH+leap_seconds_before(unix) as i64-LEAP_SECONDS_BEFORE_GPS;
+        Ok(((gps/SECONDS_PER_WEEK) as u32,(gps%SECONDS_PER_WEEK) as u32))
+    }
+
+    /// Create a UtcDatetime from a full GPS week number and the seconds into the week.
+    ///
+    /// A leap second is returned as `23:59:59` of its day.
+    /// Returns SecondNumberError if `tow` is a week or more, and YearNumberError after the year 999999.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// assert_eq!(UtcDatetime::from_gps_week_tow(2184,120618).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+    /// assert_eq!(UtcDatetime::from_gps_week_tow(0,0).unwrap(),UtcDatetime::new(1980,1,6,0,0,0).unwrap());
+    /// ```
+    pub fn from_gps_week_tow(week:u32,tow:u32)->Result<UtcDatetime, IllegalTimeError>{
+        if tow as i64>=SECONDS_PER_WEEK{
+            return Err(IllegalTimeError::SecondNumberError)
+        }
+        let gps_unix=GPS_EPOCH+week as i64*SECONDS_PER_WEEK+tow as i64+LEAP_SECONDS_BEFORE_GPS;
+        let leaps=leap_seconds_before(gps_unix-leap_seconds_before(gps_unix) as i64) as i64;
+        let unix=gps_unix-leaps;
+        // 闰秒本身与其后的午夜得到相同的结果,前者的闰秒数少一个
+        let unix=if leap_seconds_before(unix) as i64!=leaps {unix-1} else {unix};
+        UtcDatetime::from_unix_seconds(unix)
+    }
+
+    /// Like [`UtcDatetime::from_gps_week_tow`] for the 10-bit week number of the legacy navigation message,
+    /// choosing the rollover period that puts the result nearest to `near`, such as the build date of the firmware.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let near=UtcDatetime::new(2020,1,1,0,0,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_gps_week_tow_near(2184%1024,120618,&near).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+    /// ```
+    pub fn from_gps_week_tow_near(week:u32,tow:u32,near:&UtcDatetime)->Result<UtcDatetime, IllegalTimeError>{
+        let near_week=(near.unix_seconds()-GPS_EPOCH).div_euclid(SECONDS_PER_WEEK);
+        let week=week as i64%ROLLOVER_WEEKS;
+        // 距离near最近的同余周数
+        let mut full_week=near_week-(near_week-week).rem_euclid(ROLLOVER_WEEKS);
+        if near_week-full_week>ROLLOVER_WEEKS/2{
+            full_week+=ROLLOVER_WEEKS;
+        }
+        // GPS时间开始之前没有周数,取第一个周期
+        if full_week<0{
+            full_week=week;
+        }
+        UtcDatetime::from_gps_week_tow(u32::try_from(full_week).map_err(|_|IllegalTimeError::YearNumberError)?,tow)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::{IllegalTimeError, UtcDatetime};
+
+    #[test]
+    fn gps_week(){
+        for ts in (315964800..2_000_000_000i64).step_by(999_983){
+            let dt=UtcDatetime::from_timestamp(ts as u32);
+            let (week,tow)=dt.to_gps_week_tow().unwrap();
+            assert_eq!(UtcDatetime::from_gps_week_tow(week,tow).unwrap(),dt);
+        }
+        // 2016-12-31 23:59:60在GPS时间中有自己的一秒
+        let before=UtcDatetime::new(2016,12,31,23,59,59).unwrap().to_gps_week_tow().unwrap();
+        let after=UtcDatetime::new(2017,1,1,0,0,0).unwrap().to_gps_week_tow().unwrap();
+        assert_eq!(after.1-before.1,2);
+        assert_eq!(UtcDatetime::from_gps_week_tow(before.0,before.1+1).unwrap(),UtcDatetime::new(2016,12,31,23,59,59).unwrap());
+        assert!(matches!(UtcDatetime::new(1980,1,5,23,59,59).unwrap().to_gps_week_tow(),Err(IllegalTimeError::YearNumberError)));
+        assert!(matches!(UtcDatetime::from_gps_week_tow(0,604800),Err(IllegalTimeError::SecondNumberError)));
+        // 第一次和第二次周数翻转
+        let near=UtcDatetime::new(1999,8,1,0,0,0).unwrap();
+        assert_eq!(UtcDatetime::from_gps_week_tow_near(1023,0,&near).unwrap().to_gps_week_tow().unwrap(),(1023,0));
+        assert_eq!(UtcDatetime::from_gps_week_tow_near(0,0,&near).unwrap().to_gps_week_tow().unwrap(),(1024,0));
+        assert_eq!(UtcDatetime::from_gps_week_tow_near(5,0,&UtcDatetime::MIN).unwrap().to_gps_week_tow().unwrap(),(5,0));
+        assert_eq!(UtcDatetime::from_gps_week_tow_near(2047+1024,0,&UtcDatetime::new(2019,4,7,0,0,0).unwrap()).unwrap().to_gps_week_tow().unwrap(),(2047,0));
+    }
+}
@@ -0,0 +1,62 @@
+//! Format into [`heapless::String`] without touching the heap, enabled with the `heapless` feature.
+//!
+//! These helpers avoid allocation but the crate is not `no_std` yet: it links `std`,
+//! so they only build for targets that have a standard library.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+//! assert_eq!(a_date.to_heapless_string().as_str(),"2021-11-15 09:30:00");
+//! assert_eq!(a_date.to_heapless_iso8601().as_str(),"2021-11-15T09:30:00Z");
+//! ```
+use core::fmt::{self, Write};
+use heapless::String;
+use crate::format::FormatDescription;
+use crate::UtcDatetime;
+
+impl UtcDatetime{
+    /// Format as `YYYY-MM-DD HH:MM:SS`, 22 bytes are enough for a signed six-digit year
+    pub fn to_heapless_string(&self)->String<22>{
+        let mut s=String::new();
+        self.format_into(&mut s).expect("the default format is at most 22 bytes");
+        s
+    }
+
+    /// Format as `YYYY-MM-DDTHH:MM:SSZ`, 23 bytes are enough for a signed six-digit year
+    pub fn to_heapless_iso8601(&self)->String<23>{
+        let mut s=String::new();
+        write!(s,"{:#}",self).expect("ISO 8601 is at most 23 bytes");
+        s
+    }
+
+    /// Format with a [`FormatDescription`] into a string of capacity `N`.
+    ///
+    /// Returns `fmt::Error` if the output does not fit.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// use utc_datetime::format::FormatDescription;
+    /// let fd=FormatDescription::new("%d.%m.%Y").unwrap();
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.format_heapless::<10>(&fd).unwrap().as_str(),"15.11.2021");
+    /// assert!(a_date.format_heapless::<9>(&fd).is_err());
+    /// ```
+    pub fn format_heapless<const N:usize>(&self,description:&FormatDescription)->Result<String<N>,fmt::Error>{
+        let mut s=String::new();
+        self.format_with_into(description,&mut s)?;
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::UtcDatetime;
+
+    #[test]
+    fn longest_year(){
+        for dt in [UtcDatetime::MIN,UtcDatetime::MAX]{
+            assert_eq!(dt.to_heapless_string().as_str(),dt.to_string());
+            assert_eq!(dt.to_heapless_iso8601().as_str(),format!("{:#}",dt));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt;
+use crate::{Duration, IllegalTimeError, UtcDatetime};
+
+/// A UtcDatetime stored as seconds since 1970, for code that compares or subtracts many values.
+///
+/// Comparisons, hashing and arithmetic work on a single i64, the calendar fields are only
+/// computed by [`InstantUtc::to_datetime`]. The range is the same as UtcDatetime's.
+/// # Example
+/// ```
+/// use utc_datetime::{Duration, InstantUtc, UtcDatetime};
+/// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+/// let instant=InstantUtc::from(a_date);
+/// let later=instant.checked_add(Duration::hours(1)).unwrap();
+/// assert!(instant<later);
+/// assert_eq!(later.duration_since(instant),Duration::hours(1));
+/// assert_eq!(later.to_datetime(),UtcDatetime::new(2021,11,15,10,30,0).unwrap());
+/// ```
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Hash,Debug)]
+pub struct InstantUtc(i64);
+
+impl InstantUtc{
+    pub const MIN:InstantUtc=InstantUtc(-31_619_087_596_800);
+    pub const MAX:InstantUtc=InstantUtc(31_494_784_780_799);
+
+    /// Returns YearNumberError outside the range of [`UtcDatetime::MIN`] to [`UtcDatetime::MAX`]
+    pub fn from_unix_seconds(seconds:i64)->Result<InstantUtc, IllegalTimeError>{
+        if !(InstantUtc::MIN.0..=InstantUtc::MAX.0).contains(&seconds){
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        Ok(InstantUtc(seconds))
+    }
+
+    /// The number of seconds since January 1, 1970, negative before it
+    pub fn unix_seconds(&self)->i64{
+        self.0
+    }
+
+    /// Compute the calendar fields
+    pub fn to_datetime(&self)->UtcDatetime{
+        UtcDatetime::from_unix_seconds(self.0).expect("an InstantUtc is always in range")
+    }
+
+    /// `self-earlier`, negative if `earlier` is later
+    pub fn duration_since(&self,earlier:InstantUtc)->Duration{
+        Duration::seconds(self.0-earlier.0)
+    }
+
+    /// Returns None if the result is out of range
+    pub fn checked_add(&self,duration:Duration)->Option<InstantUtc>{
+        InstantUtc::from_unix_seconds(self.0.checked_add(duration.whole_seconds())?).ok()
+    }
+
+    /// Returns None if the result is out of range
+    pub fn checked_sub(&self,duration:Duration)->Option<InstantUtc>{
+        InstantUtc::from_unix_seconds(self.0.checked_sub(duration.whole_seconds())?).ok()
+    }
+}
+
+impl From<UtcDatetime> for InstantUtc{
+    fn from(dt:UtcDatetime)->InstantUtc{
+        InstantUtc(dt.unix_seconds())
+    }
+}
+
+impl From<InstantUtc> for UtcDatetime{
+    fn from(instant:InstantUtc)->UtcDatetime{
+        instant.to_datetime()
+    }
+}
+
+/// Formats like UtcDatetime
+impl fmt::Display for InstantUtc{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        fmt::Display::fmt(&self.to_datetime(),f)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::{Duration, InstantUtc, UtcDatetime};
+
+    #[test]
+    fn instant_range(){
+        assert_eq!(InstantUtc::from(UtcDatetime::MAX),InstantUtc::MAX);
+        assert_eq!(InstantUtc::MIN.to_datetime(),UtcDatetime::MIN);
+        assert!(InstantUtc::from_unix_seconds(InstantUtc::MAX.unix_seconds()+1).is_err());
+        assert!(InstantUtc::from_unix_seconds(InstantUtc::MIN.unix_seconds()-1).is_err());
+        assert_eq!(InstantUtc::from_unix_seconds(-1).unwrap().to_datetime(),UtcDatetime::new(1969,12,31,23,59,59).unwrap());
+        assert_eq!(InstantUtc::MAX.checked_add(Duration::seconds(1)),None);
+        assert_eq!(InstantUtc::MIN.checked_sub(Duration::seconds(1)),None);
+        assert_eq!(InstantUtc::MIN.checked_sub(Duration::seconds(i64::MAX)),None);
+        assert_eq!(InstantUtc::MAX.checked_add(Duration::seconds(i64::MAX)),None);
+        assert_eq!(format!("{:#}",InstantUtc::from(UtcDatetime::UNIX_EPOCH)),"1970-01-01T00:00:00Z");
+        assert_eq!(InstantUtc::from(UtcDatetime::MIN),InstantUtc::MIN);
+        let dates=[UtcDatetime::new(2021,11,15,9,30,0).unwrap(),UtcDatetime::MIN,UtcDatetime::MAX];
+        let mut instants:Vec<InstantUtc>=dates.iter().copied().map(InstantUtc::from).collect();
+        instants.sort();
+        assert_eq!(instants.iter().map(InstantUtc::to_datetime).collect::<Vec<_>>(),[dates[1],dates[0],dates[2]]);
+    }
+}
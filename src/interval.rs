@@ -0,0 +1,58 @@
+use crate::{Duration, UtcDatetime};
+
+/// The half-open time range `[start, end)`
+/// # Example
+/// ```
+/// use utc_datetime::{Duration, UtcDatetime, UtcInterval};
+/// let start=UtcDatetime::new(2021,11,15,9,0,0).unwrap();
+/// let interval=UtcInterval::new(start,UtcDatetime::new(2021,11,15,10,0,0).unwrap());
+/// assert_eq!(interval.duration(),Duration::hours(1));
+/// assert!(interval.contains(&start) && !interval.contains(&interval.end));
+/// ```
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Hash,Debug)]
+pub struct UtcInterval{
+    pub start:UtcDatetime,
+    pub end:UtcDatetime,
+}
+
+impl UtcInterval{
+    /// The interval from `start` up to but not including `end`
+    pub fn new(start:UtcDatetime,end:UtcDatetime)->UtcInterval{
+        UtcInterval{start,end}
+    }
+
+    /// The length, negative if `end` is before `start`
+    pub fn duration(&self)->Duration{
+        self.start.until(&self.end)
+    }
+
+    /// Whether the interval contains no instant
+    pub fn is_empty(&self)->bool{
+        self.end<=self.start
+    }
+
+    /// Whether `start <= datetime < end`
+    pub fn contains(&self,datetime:&UtcDatetime)->bool{
+        self.start<=*datetime && *datetime<self.end
+    }
+
+    /// Whether the intervals share at least one instant
+    pub fn overlaps(&self,other:&UtcInterval)->bool{
+        self.start<other.end && other.start<self.end && !self.is_empty() && !other.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::{UtcDatetime, UtcInterval};
+
+    #[test]
+    fn overlaps(){
+        let at=|h|UtcDatetime::new(2021,11,15,h,0,0).unwrap();
+        let morning=UtcInterval::new(at(9),at(12));
+        assert!(morning.overlaps(&UtcInterval::new(at(11),at(13))));
+        assert!(!morning.overlaps(&UtcInterval::new(at(12),at(13))));
+        assert!(!morning.overlaps(&UtcInterval::new(at(10),at(10))));
+        assert!(UtcInterval::new(at(12),at(9)).is_empty());
+    }
+}
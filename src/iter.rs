@@ -0,0 +1,92 @@
+//! Iterators over calendar boundaries.
+use crate::UtcDatetime;
+
+/// Iterator over the first instant of each month, created by [`UtcDatetime::months_between`]
+#[derive(Clone,Debug)]
+pub struct MonthsBetween{
+    // 下一个月份,以从0年1月起的月数表示,公元前为负数
+    next:i64,
+    end:UtcDatetime,
+}
+
+/// Iterator over the first instant of each year, created by [`UtcDatetime::years_between`]
+#[derive(Clone,Debug)]
+pub struct YearsBetween{
+    next:i64,
+    end:UtcDatetime,
+}
+
+impl UtcDatetime{
+    /// Iterate over the first instant of every month that starts in `[start, end)`
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let start=UtcDatetime::new(2021,11,15,0,0,0).unwrap();
+    /// let end=UtcDatetime::new(2022,2,1,0,0,0).unwrap();
+    /// let months:Vec<String>=UtcDatetime::months_between(start,end).map(|dt|dt.to_string()).collect();
+    /// assert_eq!(months,["2021-12-01 00:00:00","2022-01-01 00:00:00"]);
+    /// ```
+    pub fn months_between(start:UtcDatetime,end:UtcDatetime)->MonthsBetween{
+        let months=start.year as i64*12+start.month as i64-1;
+        let is_first_instant=(start.day,start.hour,start.minute,start.second)==(1,0,0,0);
+        MonthsBetween{next:if is_first_instant {months} else {months+1},end}
+    }
+
+    /// Iterate over the first instant of every year that starts in `[start, end)`
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let start=UtcDatetime::new(2020,1,1,0,0,0).unwrap();
+    /// let end=UtcDatetime::new(2022,6,1,0,0,0).unwrap();
+    /// assert_eq!(UtcDatetime::years_between(start,end).map(|dt|dt.year()).collect::<Vec<_>>(),[2020,2021,2022]);
+    /// ```
+    pub fn years_between(start:UtcDatetime,end:UtcDatetime)->YearsBetween{
+        let is_first_instant=(start.month,start.day,start.hour,start.minute,start.second)==(1,1,0,0,0);
+        YearsBetween{next:if is_first_instant {start.year as i64} else {start.year as i64+1},end}
+    }
+}
+
+impl Iterator for MonthsBetween{
+    type Item=UtcDatetime;
+
+    fn next(&mut self)->Option<UtcDatetime>{
+        let year=i32::try_from(self.next.div_euclid(12)).ok()?;
+        let dt=UtcDatetime{year,month:(self.next.rem_euclid(12)+1) as u8,day:1,hour:0,minute:0,second:0};
+        if dt>=self.end{
+            return None
+        }
+        self.next+=1;
+        Some(dt)
+    }
+}
+
+impl Iterator for YearsBetween{
+    type Item=UtcDatetime;
+
+    fn next(&mut self)->Option<UtcDatetime>{
+        let year=i32::try_from(self.next).ok()?;
+        let dt=UtcDatetime{year,month:1,day:1,hour:0,minute:0,second:0};
+        if dt>=self.end{
+            return None
+        }
+        self.next+=1;
+        Some(dt)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::UtcDatetime;
+
+    #[test]
+    fn bounds(){
+        assert_eq!(UtcDatetime::months_between(UtcDatetime::MIN,UtcDatetime::MIN).count(),0);
+        let bce=UtcDatetime::new(-1,11,15,0,0,0).unwrap();
+        let months:Vec<_>=UtcDatetime::months_between(bce,UtcDatetime::new(1,2,1,0,0,0).unwrap()).collect();
+        assert_eq!((months.len(),months[0],months[1]),(14,UtcDatetime::new(-1,12,1,0,0,0).unwrap(),UtcDatetime::new(0,1,1,0,0,0).unwrap()));
+        assert_eq!(UtcDatetime::months_between(UtcDatetime::new(65535,1,1,0,0,0).unwrap(),UtcDatetime::MAX).last().unwrap().month(),12);
+        assert_eq!(UtcDatetime::years_between(UtcDatetime::MAX,UtcDatetime::MAX).count(),0);
+        assert_eq!(UtcDatetime::years_between(UtcDatetime::MIN,UtcDatetime::MAX).count(),1_999_999);
+        assert_eq!(UtcDatetime::years_between(bce,UtcDatetime::MAX).next().unwrap().year(),0);
+    }
+}
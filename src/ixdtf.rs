@@ -0,0 +1,117 @@
+//! RFC 9557 Internet Extended Date/Time Format, RFC 3339 followed by bracketed annotations.
+//!
+//! An optional time zone annotation such as `[Etc/UTC]` comes first, then tagged annotations such as `[u-ca=gregory]`.
+//! A `!` after the opening bracket marks an annotation as critical, it must not be ignored.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::ixdtf::Annotation;
+//! let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+//! let (dt,annotations)=UtcDatetime::from_rfc9557_with_annotations("2021-11-15T09:30:00Z[Etc/UTC][u-ca=gregory]").unwrap();
+//! assert_eq!(dt,a_date);
+//! assert_eq!(annotations,[
+//!     Annotation{critical:false,key:None,value:"Etc/UTC"},
+//!     Annotation{critical:false,key:Some("u-ca"),value:"gregory"},
+//! ]);
+//! ```
+use crate::{IllegalTimeError, UtcDatetime};
+
+/// An annotation of an RFC 9557 string
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+pub struct Annotation<'a>{
+    /// Whether the annotation was written with `!`
+    pub critical:bool,
+    /// The key of a tagged annotation such as `u-ca`, `None` for the time zone annotation
+    pub key:Option<&'a str>,
+    /// The time zone name or offset, or the value of a tagged annotation such as `gregory`
+    pub value:&'a str,
+}
+
+// 时区名由`/`分隔的部分组成,每部分以字母,`.`或`_`开头,也可以是`±HH:MM`形式的偏移
+fn is_time_zone(value:&str)->bool{
+    if let [b'+'|b'-',h1,h2,b':',m1,m2]=*value.as_bytes(){
+        return [h1,h2,m1,m2].iter().all(u8::is_ascii_digit)
+    }
+    value.split('/').all(|part|{
+        part.bytes().next().is_some_and(|b|b.is_ascii_alphabetic() || b==b'.' || b==b'_')
+            && part.bytes().all(|b|b.is_ascii_alphanumeric() || matches!(b,b'.'|b'_'|b'-'|b'+'))
+    })
+}
+
+// 键以小写字母或`_`开头,由小写字母,数字,`_`和`-`组成
+fn is_key(key:&str)->bool{
+    key.bytes().next().is_some_and(|b|b.is_ascii_lowercase() || b==b'_')
+        && key.bytes().all(|b|b.is_ascii_lowercase() || b.is_ascii_digit() || b==b'_' || b==b'-')
+}
+
+// 值由`-`分隔的1到8个字母或数字组成
+fn is_value(value:&str)->bool{
+    value.split('-').all(|part|(1..=8).contains(&part.len()) && part.bytes().all(|b|b.is_ascii_alphanumeric()))
+}
+
+impl UtcDatetime{
+    /// Parse an RFC 9557 string and return its annotations in order.
+    ///
+    /// The time zone annotation does not change the instant, it is the offset of the RFC 3339 part that counts.
+    /// Returns TimeStringError if the RFC 3339 part or an annotation is malformed,
+    /// or the time zone annotation is not the first one.
+    pub fn from_rfc9557_with_annotations(time_str:&str)->Result<(UtcDatetime,Vec<Annotation<'_>>), IllegalTimeError>{
+        let (datetime,mut rest)=time_str.find('[').map_or((time_str,""),|i|time_str.split_at(i));
+        let datetime=UtcDatetime::from_rfc3339(datetime)?;
+        let mut annotations=Vec::new();
+        while !rest.is_empty(){
+            let (annotation,tail)=rest.strip_prefix('[').and_then(|s|s.split_once(']')).ok_or(IllegalTimeError::TimeStringError)?;
+            let (critical,annotation)=match annotation.strip_prefix('!'){
+                Some(annotation)=>(true,annotation),
+                None=>(false,annotation),
+            };
+            let annotation=match annotation.split_once('='){
+                Some((key,value)) if is_key(key) && is_value(value)=>Annotation{critical,key:Some(key),value},
+                None if annotations.is_empty() && is_time_zone(annotation)=>Annotation{critical,key:None,value:annotation},
+                _=>return Err(IllegalTimeError::TimeStringError)
+            };
+            annotations.push(annotation);
+            rest=tail;
+        }
+        Ok((datetime,annotations))
+    }
+
+    /// Parse an RFC 9557 string such as `2021-11-15T09:30:00Z[Etc/UTC][u-ca=gregory]`, ignoring the annotations.
+    ///
+    /// Returns TimeStringError as [`UtcDatetime::from_rfc9557_with_annotations`] does,
+    /// and also for a critical annotation, which cannot be ignored.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_rfc9557("2021-11-15T17:30:00+08:00[Asia/Shanghai]").unwrap(),a_date);
+    /// assert!(UtcDatetime::from_rfc9557("2021-11-15T09:30:00Z[!u-ca=hebrew]").is_err());
+    /// ```
+    pub fn from_rfc9557(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let (datetime,annotations)=UtcDatetime::from_rfc9557_with_annotations(time_str)?;
+        if annotations.iter().any(|a|a.critical){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        Ok(datetime)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::UtcDatetime;
+    use super::Annotation;
+
+    #[test]
+    fn annotations(){
+        let dt=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        assert_eq!(UtcDatetime::from_rfc9557_with_annotations("2021-11-15T09:30:00Z").unwrap(),(dt,vec![]));
+        let (_,annotations)=UtcDatetime::from_rfc9557_with_annotations("2021-11-15T09:30:00Z[!+08:00][_foo-1=bar-baz12345]").unwrap();
+        assert_eq!(annotations,[Annotation{critical:true,key:None,value:"+08:00"},Annotation{critical:false,key:Some("_foo-1"),value:"bar-baz12345"}]);
+        assert_eq!(UtcDatetime::from_rfc9557("2021-11-15T09:30:00Z[America/Argentina/Buenos_Aires][u-ca=gregory][x=y]").unwrap(),dt);
+        for s in ["2021-11-15T09:30:00Z[","2021-11-15T09:30:00Z[]","2021-11-15T09:30:00Z[Etc/UTC]x","2021-11-15T09:30:00Z[u-ca=gregory][Etc/UTC]",
+            "2021-11-15T09:30:00Z[Etc/UTC][Etc/UTC]","2021-11-15T09:30:00Z[U-CA=gregory]","2021-11-15T09:30:00Z[u-ca=]","2021-11-15T09:30:00Z[u-ca=toolongvalue]",
+            "2021-11-15T09:30:00Z[Etc//UTC]","2021-11-15T09:30:00Z[+0800]","2021-11-15T09:30:00[Etc/UTC]","2021-11-15T09:30:00Z[!Etc/UTC]"]{
+            assert!(UtcDatetime::from_rfc9557(s).is_err(),"{}",s);
+        }
+    }
+}
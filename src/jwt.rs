@@ -0,0 +1,70 @@
+use crate::{Duration, IllegalTimeError, UtcDatetime};
+
+impl UtcDatetime{
+    /// The JWT NumericDate of this time, the seconds since 1970 used by the `exp`, `nbf` and `iat` claims
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// assert_eq!(UtcDatetime::new(2021,11,15,9,30,0).unwrap().to_numeric_date(),1636968600);
+    /// ```
+    pub fn to_numeric_date(&self)->i64{
+        self.unix_seconds()
+    }
+
+    /// Create a UtcDatetime from a JWT NumericDate.
+    ///
+    /// Negative values are before 1970, returns YearNumberError outside the range of UtcDatetime.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// assert_eq!(UtcDatetime::from_numeric_date(1636968600).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+    /// ```
+    pub fn from_numeric_date(numeric_date:i64)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::from_unix_seconds(numeric_date)
+    }
+
+    /// Whether an `exp` claim has passed at `now`, allowing `leeway` for clock skew.
+    ///
+    /// As in RFC 7519 the token is expired from the `exp` second on, the sign of `leeway` is ignored.
+    /// # Example
+    /// ```
+    /// use utc_datetime::{Duration, UtcDatetime};
+    /// let exp=UtcDatetime::from_numeric_date(1636968600).unwrap();
+    /// let now=UtcDatetime::new(2021,11,15,9,30,30).unwrap();
+    /// assert!(exp.is_expired(&now,Duration::ZERO));
+    /// assert!(!exp.is_expired(&now,Duration::minutes(1)));
+    /// ```
+    pub fn is_expired(&self,now:&UtcDatetime,leeway:Duration)->bool{
+        now.unix_seconds()>=self.unix_seconds().saturating_add(leeway.whole_seconds().saturating_abs())
+    }
+
+    /// Whether `self` is at or after the `nbf` claim and before the `exp` claim
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let nbf=UtcDatetime::from_numeric_date(1636968600).unwrap();
+    /// let exp=UtcDatetime::from_numeric_date(1636972200).unwrap();
+    /// assert!(nbf.valid_between(&nbf,&exp));
+    /// assert!(!exp.valid_between(&nbf,&exp));
+    /// ```
+    pub fn valid_between(&self,nbf:&UtcDatetime,exp:&UtcDatetime)->bool{
+        nbf<=self && self<exp
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::{Duration, UtcDatetime};
+
+    #[test]
+    fn claims(){
+        let exp=UtcDatetime::MAX;
+        assert!(!exp.is_expired(&UtcDatetime::MAX,Duration::seconds(i64::MIN)));
+        assert!(exp.is_expired(&exp,Duration::ZERO));
+        assert!(!UtcDatetime::UNIX_EPOCH.is_expired(&UtcDatetime::from_timestamp(59),Duration::seconds(-60)));
+        assert_eq!(UtcDatetime::from_numeric_date(-1).unwrap(),UtcDatetime::new(1969,12,31,23,59,59).unwrap());
+        assert!(UtcDatetime::from_numeric_date(i64::MIN).is_err());
+        assert_eq!(UtcDatetime::from_numeric_date(UtcDatetime::MAX.to_numeric_date()).unwrap(),UtcDatetime::MAX);
+        assert!(!UtcDatetime::MIN.valid_between(&UtcDatetime::MAX,&UtcDatetime::MIN));
+    }
+}
@@ -0,0 +1,145 @@
+//! Leap seconds, for data labeled with seconds that count them.
+//!
+//! UtcDatetime itself ignores leap seconds, like POSIX time. A count of elapsed seconds since
+//! 1970-01-01 00:00:00 UTC can be read in one of three [`LeapSecondMode`]s:
+//! - `Posix` ignores leap seconds, the count is a Unix timestamp.
+//! - `Utc` counts every leap second, the leap second itself is labeled `23:59:60`.
+//! - `Smear` spreads each leap second over the 24 hours from noon to noon, as Google's NTP servers do.
+//!
+//! The table holds the 27 leap seconds inserted up to 2016-12-31 and must be extended
+//! when the IERS announces another one.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::leap_seconds::LeapSecondMode;
+//! let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+//! assert_eq!(a_date.elapsed_seconds(LeapSecondMode::Utc)-a_date.elapsed_seconds(LeapSecondMode::Posix),27);
+//! // 2016-12-31 23:59:60
+//! let (dt,is_leap_second)=UtcDatetime::from_elapsed_seconds(1483228826,LeapSecondMode::Utc).unwrap();
+//! assert_eq!((dt.to_string().as_str(),is_leap_second),("2016-12-31 23:59:59",true));
+//! ```
+use crate::{IllegalTimeError, UtcDatetime};
+
+/// The Unix timestamps of the midnights that follow a leap second
+pub const LEAP_SECONDS:[i64;27]=[
+    78796800,94694400,126230400,157766400,189302400,220924800,252460800,283996800,315532800,
+    362793600,394329600,425865600,489024000,567993600,631152000,662688000,709948800,741484800,
+    773020800,820454400,867715200,915148800,1136073600,1230768000,1341100800,1435708800,1483228800,
+];
+
+// 平滑从闰秒前的中午开始,到闰秒后的中午结束
+const HALF_DAY:i64=12*60*60;
+const SMEAR:i64=2*HALF_DAY;
+
+/// How a count of elapsed seconds treats leap seconds, see the [module documentation](self)
+#[derive(Clone,Copy,PartialEq,Eq,Hash,Debug)]
+pub enum LeapSecondMode{
+    Posix,
+    Utc,
+    Smear,
+}
+
+/// Returns the number of leap seconds inserted before a Unix timestamp
+/// # Example
+/// ```
+/// use utc_datetime::leap_seconds::leap_seconds_before;
+/// assert_eq!(leap_seconds_before(0),0);
+/// assert_eq!(leap_seconds_before(1483228799),26);
+/// assert_eq!(leap_seconds_before(1483228800),27);
+/// ```
+pub fn leap_seconds_before(timestamp:i64)->u32{
+    LEAP_SECONDS.partition_point(|&t|t<=timestamp) as u32
+}
+
+impl UtcDatetime{
+    /// The number of seconds since 1970-01-01 00:00:00 UTC, counted in `mode`
+    pub fn elapsed_seconds(&self,mode:LeapSecondMode)->i64{
+        let unix=self.unix_seconds();
+        match mode{
+            LeapSecondMode::Posix=>unix,
+            LeapSecondMode::Utc=>unix+leap_seconds_before(unix) as i64,
+            LeapSecondMode::Smear=>{
+                let n=leap_seconds_before(unix+HALF_DAY) as usize;
+                let leaps=leap_seconds_before(unix) as i64;
+                match n.checked_sub(1).map(|i|LEAP_SECONDS[i]){
+                    // 平滑期间的一秒比国际单位制的一秒长1/86400
+                    Some(t) if unix<t+HALF_DAY=>{
+                        let k=unix-(t-HALF_DAY);
+                        t-HALF_DAY+(n as i64-1)+(k*(SMEAR+1)+SMEAR-1)/SMEAR
+                    },
+                    _=>unix+leaps,
+                }
+            },
+        }
+    }
+
+    /// The datetime `elapsed` seconds after 1970-01-01 00:00:00 UTC counted in `mode`, and
+    /// whether it is a leap second. A leap second is returned as `23:59:59` of its day.
+    ///
+    /// Returns YearNumberError if the result is outside the representable range.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// use utc_datetime::leap_seconds::LeapSecondMode;
+    /// let (dt,_)=UtcDatetime::from_elapsed_seconds(1483228800,LeapSecondMode::Smear).unwrap();
+    /// // 2016-12-31 23:59:34 in UTC, the smeared clock is half a second behind
+    /// assert_eq!(dt.to_string(),"2016-12-31 23:59:33");
+    /// ```
+    pub fn from_elapsed_seconds(elapsed:i64,mode:LeapSecondMode)->Result<(UtcDatetime,bool), IllegalTimeError>{
+        let mut leaps=0;
+        for (i,&t) in LEAP_SECONDS.iter().enumerate(){
+            let i=i as i64;
+            match mode{
+                LeapSecondMode::Posix=>break,
+                LeapSecondMode::Utc if elapsed==t+i=>return Ok((UtcDatetime::from_unix_seconds(t-1)?,true)),
+                LeapSecondMode::Utc if elapsed<t+i=>break,
+                LeapSecondMode::Smear if elapsed<t-HALF_DAY+i=>break,
+                LeapSecondMode::Smear if elapsed<t+HALF_DAY+i+1=>{
+                    let k=elapsed-(t-HALF_DAY+i);
+                    return Ok((UtcDatetime::from_unix_seconds(t-HALF_DAY+k*SMEAR/(SMEAR+1))?,false))
+                },
+                _=>leaps=i+1,
+            }
+        }
+        Ok((UtcDatetime::from_unix_seconds(elapsed-leaps)?,false))
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::UtcDatetime;
+    use super::{LeapSecondMode, LEAP_SECONDS};
+
+    #[test]
+    fn round_trip(){
+        for mode in [LeapSecondMode::Posix,LeapSecondMode::Utc,LeapSecondMode::Smear]{
+            for &t in &LEAP_SECONDS{
+                for unix in (t-50_000..t+50_000).step_by(7).chain([t-43_201,t-43_200,t-1,t,t+43_199,t+43_200]){
+                    let dt=UtcDatetime::from_unix_seconds(unix).unwrap();
+                    assert_eq!(UtcDatetime::from_elapsed_seconds(dt.elapsed_seconds(mode),mode).unwrap(),(dt,false),"{:?} {}",mode,dt);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn modes(){
+        let last=LEAP_SECONDS[26];
+        let mut previous=UtcDatetime::UNIX_EPOCH;
+        let mut seconds=0;
+        // 平滑期间共86401个国际单位制秒,对应86400个不同的时间
+        for elapsed in last-43_200+26..last+43_200+27{
+            let (dt,leap)=UtcDatetime::from_elapsed_seconds(elapsed,LeapSecondMode::Smear).unwrap();
+            assert!(!leap && dt>=previous);
+            seconds+=(dt!=previous) as u32;
+            previous=dt;
+        }
+        assert_eq!(seconds,86400);
+        let after=UtcDatetime::from_elapsed_seconds(last+43_200+27,LeapSecondMode::Smear).unwrap().0;
+        assert_eq!(after.elapsed_seconds(LeapSecondMode::Smear),after.elapsed_seconds(LeapSecondMode::Utc));
+        assert!(UtcDatetime::from_elapsed_seconds(78796800,LeapSecondMode::Utc).unwrap().1);
+        assert_eq!(UtcDatetime::from_elapsed_seconds(78796801,LeapSecondMode::Utc).unwrap().0.to_string(),"1972-07-01 00:00:00");
+        assert_eq!(UtcDatetime::from_elapsed_seconds(-1,LeapSecondMode::Utc).unwrap().0.to_string(),"1969-12-31 23:59:59");
+        assert!(UtcDatetime::from_elapsed_seconds(i64::MIN,LeapSecondMode::Posix).is_err());
+    }
+}
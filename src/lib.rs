@@ -1,10 +1,95 @@
-use core::panic;
 use std::fmt;
 
+mod atomic;
+mod batch;
+mod binary;
+mod builder;
+pub mod bucket;
+pub mod calendar;
+pub mod clock;
+pub mod cron;
+mod difference;
+pub mod display;
+mod duration;
+pub mod format;
+mod gps;
+mod instant;
+mod interval;
+pub mod iter;
+pub mod ixdtf;
+mod jwt;
+pub mod leap_seconds;
+pub mod parse;
+mod period;
+pub mod rrule;
+mod rtc;
+pub mod scan;
+pub mod sorted;
+mod text;
+pub mod windows;
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+mod arbitrary;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "libc")]
+mod tm;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+#[cfg(feature = "prost-types")]
+mod protobuf;
+#[cfg(feature = "pyo3")]
+mod python;
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+mod schema;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "defmt")]
+mod defmt;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "rmp")]
+pub mod msgpack;
+#[cfg(feature = "log")]
+pub mod log;
+#[cfg(feature = "toml")]
+pub mod toml;
+#[cfg(feature = "tracing")]
+pub mod tracing;
+#[cfg(any(
+    feature = "sqlx-postgres", feature = "sqlx-mysql", feature = "sqlx-sqlite",
+    feature = "diesel-postgres", feature = "diesel-mysql", feature = "diesel-sqlite",
+    feature = "rusqlite", feature = "postgres",
+))]
+mod db;
+
+pub use atomic::AtomicUtcDatetime;
+pub use builder::{FieldChanges, UtcDatetimeBuilder};
+pub use difference::CalendarDuration;
+pub use duration::{Duration, IsoDuration};
+pub use instant::InstantUtc;
+pub use interval::UtcInterval;
+pub use period::Period;
+#[cfg(feature = "embedded")]
+pub use rtc::{RtcError, UtcDateTimeAccess};
+
 // 派生比较UtcDatetime的特性(=,>,<,<=,>=,!=)
-#[derive(PartialEq,PartialOrd,Debug)]
+// 字段按年,月,日,时,分,秒的顺序声明,逐字段比较就是时间先后的比较
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Ord,Hash,Debug)]
+#[cfg_attr(
+    any(feature = "diesel-postgres", feature = "diesel-mysql", feature = "diesel-sqlite"),
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Timestamp)
+)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(derive(Debug, PartialEq, PartialOrd), compare(PartialEq))
+)]
+#[repr(C)]
 pub struct UtcDatetime{
-    year:u16,
+    year:i32,
     month:u8,
     day:u8,
     hour:u8,
@@ -12,10 +97,44 @@ pub struct UtcDatetime{
     second:u8,
 }
 
+/// Formats as `2021-11-15 09:30:00`, or as ISO 8601 `2021-11-15T09:30:00Z` with `{:#}`
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+/// assert_eq!(format!("{}",a_date),"2021-11-15 09:30:00");
+/// assert_eq!(format!("{:#}",a_date),"2021-11-15T09:30:00Z");
+/// ```
+///
+/// Width, fill, alignment and precision apply to the whole text like for `str`.
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+/// assert_eq!(format!("[{:>21}]",a_date),"[  2021-11-15 09:30:00]");
+/// assert_eq!(format!("[{:*^#24}]",a_date),"[**2021-11-15T09:30:00Z**]");
+/// assert_eq!(format!("{:.10}",a_date),"2021-11-15");
+/// ```
 impl fmt::Display for UtcDatetime{
     fn fmt(&self,f: &mut fmt::Formatter)->fmt::Result{
+        // 有宽度或精度时先生成完整的文本,再由pad处理填充和截断
+        if f.width().is_some() || f.precision().is_some(){
+            let mut text=String::with_capacity(23);
+            self.write_display(&mut text,f.alternate())?;
+            return f.pad(&text)
+        }
+        self.write_display(f,f.alternate())
+    }
+}
+
+impl UtcDatetime{
+    fn write_display<W:fmt::Write>(&self,w:&mut W,iso8601:bool)->fmt::Result{
         // 指定宽度输入数字
-        write!(f,"{}-{:02}-{:02} {:02}:{:02}:{:02}",self.year,self.month,self.day,self.hour,self.minute,self.second)
+        let year=display::Year(self.year);
+        if iso8601{
+            return write!(w,"{}-{:02}-{:02}T{:02}:{:02}:{:02}Z",year,self.month,self.day,self.hour,self.minute,self.second)
+        }
+        write!(w,"{}-{:02}-{:02} {:02}:{:02}:{:02}",year,self.month,self.day,self.hour,self.minute,self.second)
     }
 }
 
@@ -26,9 +145,19 @@ pub enum IllegalTimeError{
     HourNumberError,
     MinuteNumberError,
     SecondNumberError,
-    TimeStringError
+    TimeStringError,
+    FormatDescriptionError,
+    TrailingCharactersError
+}
+
+impl fmt::Display for IllegalTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self,f)
+    }
 }
 
+impl std::error::Error for IllegalTimeError {}
+
 impl fmt::Debug for IllegalTimeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self{
@@ -38,41 +167,206 @@ impl fmt::Debug for IllegalTimeError {
             IllegalTimeError::HourNumberError=>write!(f, "Hour Number Error"),
             IllegalTimeError::MinuteNumberError=>write!(f, "Minute Number Error"),
             IllegalTimeError::SecondNumberError=>write!(f, "Second Number Error"),
-            IllegalTimeError::TimeStringError=>write!(f,"The format of the input time string is not standardized")
+            IllegalTimeError::TimeStringError=>write!(f,"The format of the input time string is not standardized"),
+            IllegalTimeError::FormatDescriptionError=>write!(f,"The format description is invalid"),
+            IllegalTimeError::TrailingCharactersError=>write!(f,"The time string has unexpected characters after the seconds")
         }
     }
 }
 
+impl Default for UtcDatetime{
+    /// The epoch, 1970-01-01 00:00:00
+    fn default()->UtcDatetime{
+        UtcDatetime::UNIX_EPOCH
+    }
+}
+
+/// Compares with epoch seconds, such as a cutoff received from an API
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// let a_date=UtcDatetime::new(2020,2,2,2,2,2).unwrap();
+/// assert!(a_date==1580608922);
+/// assert!(a_date>1580608921 && 1580608923>a_date);
+/// ```
+impl PartialEq<i64> for UtcDatetime{
+    fn eq(&self,other:&i64)->bool{
+        self.unix_seconds()==*other
+    }
+}
+
+impl PartialOrd<i64> for UtcDatetime{
+    fn partial_cmp(&self,other:&i64)->Option<std::cmp::Ordering>{
+        self.unix_seconds().partial_cmp(other)
+    }
+}
+
+impl PartialEq<UtcDatetime> for i64{
+    fn eq(&self,other:&UtcDatetime)->bool{
+        *self==other.unix_seconds()
+    }
+}
+
+impl PartialOrd<UtcDatetime> for i64{
+    fn partial_cmp(&self,other:&UtcDatetime)->Option<std::cmp::Ordering>{
+        self.partial_cmp(&other.unix_seconds())
+    }
+}
+
+/// Split into `(year,month,day,hour,minute,second)`.
+///
+/// Returns YearNumberError if the year is negative or above 65535.
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// let fields:(u16,u8,u8,u8,u8,u8)=UtcDatetime::new(2021,11,15,9,30,0).unwrap().try_into().unwrap();
+/// assert_eq!(fields,(2021,11,15,9,30,0));
+/// ```
+impl TryFrom<UtcDatetime> for (u16,u8,u8,u8,u8,u8){
+    type Error=IllegalTimeError;
+
+    fn try_from(dt:UtcDatetime)->Result<(u16,u8,u8,u8,u8,u8), IllegalTimeError>{
+        let year=u16::try_from(dt.year).map_err(|_|IllegalTimeError::YearNumberError)?;
+        Ok((year,dt.month,dt.day,dt.hour,dt.minute,dt.second))
+    }
+}
+
+/// Create a UtcDatetime from `(year,month,day,hour,minute,second)`, validated like [`UtcDatetime::new`]
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// assert_eq!(UtcDatetime::try_from((2021,11,15,9,30,0)).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+/// assert!(UtcDatetime::try_from((2021,2,29,0,0,0)).is_err());
+/// ```
+impl TryFrom<(u16,u8,u8,u8,u8,u8)> for UtcDatetime{
+    type Error=IllegalTimeError;
+
+    fn try_from((year,month,day,hour,minute,second):(u16,u8,u8,u8,u8,u8))->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::new(year as i32,month,day,hour,minute,second)
+    }
+}
+
 impl UtcDatetime{
+    /// The earliest year accepted by [`UtcDatetime::new`], 1000000 BCE in astronomical numbering.
+    ///
+    /// Years before 1 use astronomical numbering (year 0 is 1 BCE) on the proleptic Gregorian calendar.
+    /// Narrow encodings such as [`UtcDatetime::timestamp`], [`UtcDatetime::to_bytes`], the C API
+    /// and the database codecs return YearNumberError for years they cannot represent.
+    pub const MIN_YEAR:i32=-999_999;
+    /// The latest year accepted by [`UtcDatetime::new`], see [`UtcDatetime::MIN_YEAR`]
+    pub const MAX_YEAR:i32=999_999;
+    /// The earliest representable datetime, -999999-01-01 00:00:00
+    pub const MIN:UtcDatetime=UtcDatetime{year:UtcDatetime::MIN_YEAR,month:1,day:1,hour:0,minute:0,second:0};
+    /// The latest representable datetime, 999999-12-31 23:59:59
+    pub const MAX:UtcDatetime=UtcDatetime{year:UtcDatetime::MAX_YEAR,month:12,day:31,hour:23,minute:59,second:59};
+    /// The Unix epoch, 1970-01-01 00:00:00
+    pub const UNIX_EPOCH:UtcDatetime=UtcDatetime{year:1970,month:1,day:1,hour:0,minute:0,second:0};
+
     /// Create a new UtcDateTime structure
-    pub fn new(year:u16,month:u8,day:u8,hour:u8,minute:u8,second:u8)->Result<UtcDatetime, IllegalTimeError>{
-        if year<1970{
-            // println!("年份非法");
+    pub fn new(year:i32,month:u8,day:u8,hour:u8,minute:u8,second:u8)->Result<UtcDatetime, IllegalTimeError>{
+        is_valid_date(year,month,day)?;
+        is_valid_time(hour,minute,second)?;
+        Ok(UtcDatetime{year,month,day,hour,minute,second})
+    }
+
+    /// Create a datetime like C's `mktime`, carrying fields that are out of range into the higher fields.
+    ///
+    /// Month 13 is January of the next year, day 0 is the last day of the previous month,
+    /// and negative values borrow from the higher fields.
+    /// Returns YearNumberError if the result is outside the representable range.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let dt=UtcDatetime::new_normalized(2021,13,40,25,61,61).unwrap();
+    /// assert_eq!(dt,UtcDatetime::new(2022,2,10,2,2,1).unwrap());
+    /// assert_eq!(UtcDatetime::new_normalized(2021,3,0,0,0,-1).unwrap(),UtcDatetime::new(2021,2,27,23,59,59).unwrap());
+    /// ```
+    pub fn new_normalized(year:i32,month:i64,day:i64,hour:i64,minute:i64,second:i64)->Result<UtcDatetime, IllegalTimeError>{
+        let months=year as i128*12+month as i128-1;
+        let year=i64::try_from(months.div_euclid(12)).map_err(|_|IllegalTimeError::YearNumberError)?;
+        // 超出这个范围的年份无论日期和时间怎样进位都不可能合法,也避免天数计算溢出
+        if year.unsigned_abs()>1<<40{
             return Err(IllegalTimeError::YearNumberError)
         }
-		if month==0 || month >12{
-            // println!("月份非法");
-            return Err(IllegalTimeError::MonthNumberError)
-        }
-        if day==0 || day >days_of_the_month(year,month){
-            // println!("天数非法");
-            return Err(IllegalTimeError::DayNumberError)
-        }
-        if hour >23{
-            // println!("小时数非法");
-            return Err(IllegalTimeError::HourNumberError)
-        }
-        if minute>59{
-            // println!("分钟数非法");
-            return Err(IllegalTimeError::MinuteNumberError)
-        }
-        if second>59{
-            // println!("秒数非法");
-            return Err(IllegalTimeError::SecondNumberError)
-        }
-        Ok(UtcDatetime{year,month,day,hour,minute,second})
+        let first=days_from_civil(year,(months.rem_euclid(12)+1) as u8,1) as i128;
+        let seconds=((first+day as i128-1)*24+hour as i128)*3600+minute as i128*60+second as i128;
+        UtcDatetime::from_unix_seconds(i64::try_from(seconds).map_err(|_|IllegalTimeError::YearNumberError)?)
+    }
+
+    /// The year
+    pub fn year(&self)->i32{
+        self.year
+    }
+
+    /// The month, 1 to 12
+    pub fn month(&self)->u8{
+        self.month
+    }
+
+    /// The day of the month, starting from 1
+    pub fn day(&self)->u8{
+        self.day
+    }
+
+    /// The hour, 0 to 23
+    pub fn hour(&self)->u8{
+        self.hour
+    }
+
+    /// The minute, 0 to 59
+    pub fn minute(&self)->u8{
+        self.minute
+    }
+
+    /// The second, 0 to 59
+    pub fn second(&self)->u8{
+        self.second
+    }
+
+    /// Return a copy with the year replaced, validated like [`UtcDatetime::new`].
+    ///
+    /// The other fields are never adjusted, so a day that does not exist in the new
+    /// month or year returns DayNumberError instead of being clamped.
+    /// # Example
+    /// ```
+    /// use utc_datetime::{IllegalTimeError, UtcDatetime};
+    /// let a_date=UtcDatetime::new(2020,1,30,9,30,0).unwrap();
+    /// assert_eq!(a_date.with_month(3).unwrap(),UtcDatetime::new(2020,3,30,9,30,0).unwrap());
+    /// assert!(matches!(a_date.with_month(2),Err(IllegalTimeError::DayNumberError)));
+    /// assert_eq!(a_date.with_hour(23).unwrap().hour(),23);
+    /// ```
+    pub fn with_year(&self,year:i32)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::new(year,self.month,self.day,self.hour,self.minute,self.second)
+    }
+
+    /// Return a copy with the month replaced, see [`UtcDatetime::with_year`]
+    pub fn with_month(&self,month:u8)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::new(self.year,month,self.day,self.hour,self.minute,self.second)
+    }
+
+    /// Return a copy with the day replaced, see [`UtcDatetime::with_year`]
+    pub fn with_day(&self,day:u8)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::new(self.year,self.month,day,self.hour,self.minute,self.second)
+    }
+
+    /// Return a copy with the hour replaced, see [`UtcDatetime::with_year`]
+    pub fn with_hour(&self,hour:u8)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::new(self.year,self.month,self.day,hour,self.minute,self.second)
+    }
+
+    /// Return a copy with the minute replaced, see [`UtcDatetime::with_year`]
+    pub fn with_minute(&self,minute:u8)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::new(self.year,self.month,self.day,self.hour,minute,self.second)
+    }
+
+    /// Return a copy with the second replaced, see [`UtcDatetime::with_year`]
+    pub fn with_second(&self,second:u8)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::new(self.year,self.month,self.day,self.hour,self.minute,second)
     }
     /// Returns the number of seconds since January 1, 1970
+    ///
+    /// Returns YearNumberError if the datetime is after 2106-02-07 06:28:15,
+    /// the largest timestamp that fits in a u32.
     /// # Example
     /// ```
     /// use utc_datetime::UtcDatetime;
@@ -83,28 +377,79 @@ impl UtcDatetime{
         if self.year<1970{
             return Err(IllegalTimeError::YearNumberError)
         }
-        let second=self.second as u32;
-        let minute=self.minute as u32;
-        let hour=self.hour as u32;
-        let day =self.day as u32;
-        
-        let mut total_seconds=0;
+        u32::try_from(self.unix_seconds()).map_err(|_|IllegalTimeError::YearNumberError)
+    }
 
-        // 计算1970年到去年的秒数   
-        for i in 1970..self.year{
-            total_seconds+=days_of_the_year(i)*24*60*60;
+    // 不受u32范围限制的时间戳,供内部计算使用
+    pub(crate) fn unix_seconds(&self)->i64{
+        let days=days_from_civil(self.year as i64,self.month,self.day);
+        days*24*60*60+self.hour as i64*60*60+self.minute as i64*60+self.second as i64
+    }
+
+    pub(crate) fn from_unix_seconds(seconds:i64)->Result<UtcDatetime, IllegalTimeError>{
+        // 先检查范围,避免天数计算溢出
+        if !(UtcDatetime::MIN.unix_seconds()..=UtcDatetime::MAX.unix_seconds()).contains(&seconds){
+            return Err(IllegalTimeError::YearNumberError)
         }
+        let seconds_of_day=seconds.rem_euclid(24*60*60);
+        let (year,month,day)=civil_from_days(seconds.div_euclid(24*60*60));
+        Ok(UtcDatetime{
+            year:year as i32,
+            month,
+            day,
+            hour:(seconds_of_day/3600) as u8,
+            minute:(seconds_of_day%3600/60) as u8,
+            second:(seconds_of_day%60) as u8,
+        })
+    }
 
-        // 计算今年过去的月份的秒数
-        for i in 1..self.month{
-            let days_num=days_of_the_month(self.year, i) as u32;
-            total_seconds+=days_num*24*60*60;
+    /// Create a UtcDatetime from the number of seconds since January 1, 1970
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::from_timestamp(1580608922);
+    /// assert_eq!(a_date,UtcDatetime::new(2020,2,2,2,2,2).unwrap());
+    /// ```
+    pub fn from_timestamp(timestamp:u32)->UtcDatetime{
+        let days=timestamp/(24*60*60);
+        let seconds_of_day=timestamp%(24*60*60);
+        let (year,month,day)=civil_from_days(days as i64);
+        UtcDatetime{
+            year:year as i32,
+            month,
+            day,
+            hour:(seconds_of_day/3600) as u8,
+            minute:(seconds_of_day%3600/60) as u8,
+            second:(seconds_of_day%60) as u8,
         }
+    }
 
-        // 计算这个月时间的秒数
-        total_seconds+=(day-1)*60*60*24+hour*60*60+minute*60+second;
-        
-        Ok(total_seconds)
+    /// The number of seconds since `epoch`, negative if `self` is earlier
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let j2000=UtcDatetime::new(2000,1,1,12,0,0).unwrap();
+    /// let a_date=UtcDatetime::new(2000,1,2,12,0,1).unwrap();
+    /// assert_eq!(a_date.seconds_since(&j2000),86401);
+    /// assert_eq!(j2000.seconds_since(&a_date),-86401);
+    /// ```
+    pub fn seconds_since(&self,epoch:&UtcDatetime)->i64{
+        self.unix_seconds()-epoch.unix_seconds()
+    }
+
+    /// Create a UtcDatetime from the number of seconds since `epoch`, which may be negative.
+    ///
+    /// Returns YearNumberError if the result is before [`UtcDatetime::MIN`] or after [`UtcDatetime::MAX`].
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let gps_epoch=UtcDatetime::new(1980,1,6,0,0,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_seconds_since(&gps_epoch,86400).unwrap(),UtcDatetime::new(1980,1,7,0,0,0).unwrap());
+    /// assert_eq!(UtcDatetime::from_seconds_since(&gps_epoch,-315964801).unwrap(),UtcDatetime::new(1969,12,31,23,59,59).unwrap());
+    /// assert!(UtcDatetime::from_seconds_since(&gps_epoch,i64::MIN).is_err());
+    /// ```
+    pub fn from_seconds_since(epoch:&UtcDatetime,seconds:i64)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::from_unix_seconds(epoch.unix_seconds().checked_add(seconds).ok_or(IllegalTimeError::YearNumberError)?)
     }
 
     // 返回今天是星期几:星期一到星期六依次返回1到6，星期天返回0
@@ -116,21 +461,121 @@ impl UtcDatetime{
     /// assert_eq!(a_date.weekday(),1);
     /// ```
     pub fn weekday(&self)->u8{
-        let ts=self.timestamp().unwrap();
-        //7*24*3600 为7天的秒数
-        let this_week_seconds=ts%(7*24*3600);
-        // 24*3600为一天的秒数
-        let this_week_days=this_week_seconds/(24*3600);
+        let days=days_from_civil(self.year as i64,self.month,self.day);
         // 1970年1月1日是周四
-        let week_number=(4+this_week_days)%7;
-        week_number as u8
+        (4+days).rem_euclid(7) as u8
+    }
+
+    /// Returns the day of the year, 1 to 366
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,0,0,0).unwrap();
+    /// assert_eq!(a_date.ordinal(),319);
+    /// ```
+    pub fn ordinal(&self)->u16{
+        (days_from_civil(self.year as i64,self.month,self.day)-days_from_civil(self.year as i64,1,1)+1) as u16
+    }
+
+    /// Returns the number of seconds since midnight, 0 to 86399
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.seconds_from_midnight(),34200);
+    /// ```
+    pub fn seconds_from_midnight(&self)->u32{
+        self.hour as u32*3600+self.minute as u32*60+self.second as u32
+    }
+
+    /// The time `seconds` after midnight of this date.
+    ///
+    /// As in GTFS schedules, 86400 seconds or more fall on the following days.
+    /// Returns YearNumberError if the result is outside the representable range.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.at_seconds_from_midnight(3600).unwrap(),UtcDatetime::new(2021,11,15,1,0,0).unwrap());
+    /// // 25:10:00
+    /// assert_eq!(a_date.at_seconds_from_midnight(90600).unwrap(),UtcDatetime::new(2021,11,16,1,10,0).unwrap());
+    /// ```
+    pub fn at_seconds_from_midnight(&self,seconds:u32)->Result<UtcDatetime, IllegalTimeError>{
+        let midnight=days_from_civil(self.year as i64,self.month,self.day)*86_400;
+        UtcDatetime::from_unix_seconds(midnight+seconds as i64)
+    }
+
+    /// The next instant after `self` whose time of day is a multiple of `interval_seconds`,
+    /// such as the next quarter hour for 900.
+    ///
+    /// If the interval does not divide a day, the schedule starts again at midnight.
+    /// Returns SecondNumberError if `interval_seconds` is 0, YearNumberError if the result is outside the representable range.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,37,12).unwrap();
+    /// assert_eq!(a_date.next_aligned(900).unwrap(),UtcDatetime::new(2021,11,15,9,45,0).unwrap());
+    /// assert_eq!(a_date.next_aligned(900).unwrap().next_aligned(900).unwrap(),UtcDatetime::new(2021,11,15,10,0,0).unwrap());
+    /// ```
+    pub fn next_aligned(&self,interval_seconds:u32)->Result<UtcDatetime, IllegalTimeError>{
+        if interval_seconds==0{
+            return Err(IllegalTimeError::SecondNumberError)
+        }
+        let next=(self.seconds_from_midnight()/interval_seconds+1) as u64*interval_seconds as u64;
+        self.at_seconds_from_midnight(next.min(86_400) as u32)
+    }
+
+    /// Write `YYYY-MM-DD HH:MM:SS` into any `fmt::Write` without allocating
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// use std::fmt::Write;
+    /// let mut line=String::with_capacity(64);
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// a_date.format_into(&mut line).unwrap();
+    /// write!(line," INFO started").unwrap();
+    /// assert_eq!(line,"2021-11-15 09:30:00 INFO started");
+    /// ```
+    pub fn format_into<W:fmt::Write>(&self,w:&mut W)->fmt::Result{
+        match self.to_ascii(){
+            Ok(bytes)=>w.write_str(core::str::from_utf8(&bytes).unwrap()),
+            Err(_)=>write!(w,"{}",self)
+        }
+    }
+
+    /// Render `YYYY-MM-DD HH:MM:SS` into a fixed-size ASCII array.
+    ///
+    /// Returns YearNumberError if the year is negative or has more than four digits.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(&a_date.to_ascii().unwrap(),b"2021-11-15 09:30:00");
+    /// assert!(UtcDatetime::new(-1,1,1,0,0,0).unwrap().to_ascii().is_err());
+    /// ```
+    pub fn to_ascii(&self)->Result<[u8;19],IllegalTimeError>{
+        if !(0..=9999).contains(&self.year){
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        let mut buf=*b"0000-00-00 00:00:00";
+        write_digits(&mut buf[0..4],self.year as u32);
+        write_digits(&mut buf[5..7],self.month as u32);
+        write_digits(&mut buf[8..10],self.day as u32);
+        write_digits(&mut buf[11..13],self.hour as u32);
+        write_digits(&mut buf[14..16],self.minute as u32);
+        write_digits(&mut buf[17..19],self.second as u32);
+        Ok(buf)
     }
     // 输入一个时间字符串(如"2002-04-01 00:00:01") 返回一个时间对象
     /// Convert a string containing time to UtcDatetime.
     /// 
     /// Time strings must be sorted by year, month, day, hour, minute, and second,
     /// and Non-arabic numbers can be used as separators.
-    /// 
+    /// Only ASCII `0-9` are digits, so full-width and other Unicode digits are separators too.
+    /// Any UTF-8 input returns an error instead of panicking.
+    /// A `-` at the very start of the string, directly before the year, marks a negative year
+    /// as written by the Display implementation.
+    ///
     /// Parsable string example:"2020-12-31 23:59:59","2020z12z31z23z59z59".
     /// # Example
     /// ```
@@ -139,25 +584,119 @@ impl UtcDatetime{
     /// assert_eq!(datetime,UtcDatetime::new(2020,12,31,23,59,59).unwrap());
     /// ```
     pub fn from_string(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
-		// 能转换的字符串的日期必须为阿拉伯数字，且顺序必须按照年,月,日,小时,分,秒的顺序
-		// 只保留字符串中的阿拉伯数字
-		// '0'-'9'的ascii码为48-57
-        let mut time_string_array:Vec<&str>=time_str.split(|x| (x as u8) < 48 || x as u8  >57).collect();
-        // retain non-empty items in time_string_array
-        time_string_array.retain(|&x|x.len()!=0);
-        if time_string_array.len()!=6{
-            return Err(IllegalTimeError::TimeStringError)
-        }   
-        let year=time_string_array[0].parse::<u16>().unwrap();
-        let month=time_string_array[1].parse::<u8>().unwrap();
-        let day=time_string_array[2].parse::<u8>().unwrap();
-        let hour=time_string_array[3].parse::<u8>().unwrap();
-        let minute=time_string_array[4].parse::<u8>().unwrap();
-        let second=time_string_array[5].parse::<u8>().unwrap();
-        UtcDatetime::new(year,month,day,hour,minute,second)
+        UtcDatetime::from_string_with(time_str,&parse::ParseOptions::new())
+    }
+
+    /// Like [`UtcDatetime::from_string`], but returns TrailingCharactersError
+    /// if anything follows the seconds.
+    /// # Example
+    /// ```
+    /// use utc_datetime::{IllegalTimeError, UtcDatetime};
+    /// assert!(UtcDatetime::from_string_exact("时间:2020年12月31日23点59分59").is_ok());
+    /// let err=UtcDatetime::from_string_exact("2021-11-15 09:30:00 and more digits 123");
+    /// assert!(matches!(err,Err(IllegalTimeError::TrailingCharactersError)));
+    /// ```
+    pub fn from_string_exact(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::from_string_with(time_str,&parse::ParseOptions::new().exact())
     }
 }
 
+// 把数字从右往左写入buf,位数不足时补0
+fn write_digits(buf:&mut [u8],mut value:u32){
+    for byte in buf.iter_mut().rev(){
+        *byte=b'0'+(value%10) as u8;
+        value/=10;
+    }
+}
+
+// 平年和闰年中每个月的天数
+const DAYS_IN_MONTH:[[u8;12];2]=[
+    [31,28,31,30,31,30,31,31,30,31,30,31],
+    [31,29,31,30,31,30,31,31,30,31,30,31],
+];
+
+// 平年和闰年中每个月1日之前的天数
+const DAYS_BEFORE_MONTH:[[u16;12];2]=[
+    [0,31,59,90,120,151,181,212,243,273,304,334],
+    [0,31,60,91,121,152,182,213,244,274,305,335],
+];
+
+// 公元0年到year之前(不含year)的闰年个数
+fn leap_years_before(year:i64)->i64{
+    let y=year-1;
+    y.div_euclid(4)-y.div_euclid(100)+y.div_euclid(400)
+}
+
+// 计算从1970年1月1日到指定日期的天数,年份部分用闭式计算,月份部分查表
+// month必须在1到12之间
+pub(crate) fn days_from_civil(year:i64,month:u8,day:u8)->i64{
+    let leap=((year%4==0 && year%100!=0) || year%400==0) as usize;
+    // 与days_between_years相同,但年份不限于i32
+    365*(year-1970)+leap_years_before(year)-leap_years_before(1970)+DAYS_BEFORE_MONTH[leap][month as usize-1] as i64+day as i64-1
+}
+
+// days_from_civil的逆运算,返回(年,月,日)
+pub(crate) fn civil_from_days(days:i64)->(i64,u8,u8){
+    let days=days+719468;
+    let era=days.div_euclid(146097);
+    let day_of_era=days-era*146097;
+    let year_of_era=(day_of_era-day_of_era/1460+day_of_era/36524-day_of_era/146096)/365;
+    let day_of_year=day_of_era-(365*year_of_era+year_of_era/4-year_of_era/100);
+    let mp=(5*day_of_year+2)/153;
+    let day=(day_of_year-(153*mp+2)/5+1) as u8;
+    let month=(if mp<10{mp+3}else{mp-9}) as u8;
+    let year=year_of_era+era*400;
+    (if month<=2{year+1}else{year},month,day)
+}
+
+/// Check a date without constructing a UtcDatetime,
+/// returning the same error as [`UtcDatetime::new`] for the first invalid field.
+/// # Example
+/// ```
+/// use utc_datetime::{is_valid_date, IllegalTimeError};
+/// assert!(is_valid_date(2020,2,29).is_ok());
+/// assert!(matches!(is_valid_date(2021,2,29),Err(IllegalTimeError::DayNumberError)));
+/// ```
+pub fn is_valid_date(year:i32,month:u8,day:u8)->Result<(), IllegalTimeError>{
+    if !(UtcDatetime::MIN_YEAR..=UtcDatetime::MAX_YEAR).contains(&year){
+        // println!("年份非法");
+        return Err(IllegalTimeError::YearNumberError)
+    }
+    if month==0 || month >12{
+        // println!("月份非法");
+        return Err(IllegalTimeError::MonthNumberError)
+    }
+    if day==0 || day >days_of_the_month(year,month){
+        // println!("天数非法");
+        return Err(IllegalTimeError::DayNumberError)
+    }
+    Ok(())
+}
+
+/// Check a time of day without constructing a UtcDatetime,
+/// returning the same error as [`UtcDatetime::new`] for the first invalid field.
+/// # Example
+/// ```
+/// use utc_datetime::{is_valid_time, IllegalTimeError};
+/// assert!(is_valid_time(23,59,59).is_ok());
+/// assert!(matches!(is_valid_time(23,60,0),Err(IllegalTimeError::MinuteNumberError)));
+/// ```
+pub fn is_valid_time(hour:u8,minute:u8,second:u8)->Result<(), IllegalTimeError>{
+    if hour >23{
+        // println!("小时数非法");
+        return Err(IllegalTimeError::HourNumberError)
+    }
+    if minute>59{
+        // println!("分钟数非法");
+        return Err(IllegalTimeError::MinuteNumberError)
+    }
+    if second>59{
+        // println!("秒数非法");
+        return Err(IllegalTimeError::SecondNumberError)
+    }
+    Ok(())
+}
+
 /// Conditions for judging leap years
 /// 1. Divisible by 4, but not divisible by 100
 /// 2. Divisible by 400
@@ -168,7 +707,7 @@ impl UtcDatetime{
 /// assert_eq!(leap_year(2021),false);
 /// assert_eq!(leap_year(1900),false);
 /// ```
-pub fn leap_year(year:u16)->bool{
+pub fn leap_year(year:i32)->bool{
 	// 判断闰年的条件
     // 1.能被4整除,但不能被100整除 
 	// 2.能被400整除
@@ -176,34 +715,121 @@ pub fn leap_year(year:u16)->bool{
 }
 
 /// Returns the number of days in a year
-pub fn days_of_the_year(year:u16)->u32{
+pub fn days_of_the_year(year:i32)->u32{
     if leap_year(year){366}else{365}
 }
 
+/// Returns the number of leap years in `start_year..end_year`, negative if `end_year` is earlier
+/// # Example
+/// ```
+/// use utc_datetime::leap_years_between;
+/// assert_eq!(leap_years_between(1970,2021),13);
+/// assert_eq!(leap_years_between(1900,2000),24);
+/// assert_eq!(leap_years_between(2000,1900),-24);
+/// ```
+pub fn leap_years_between(start_year:i32,end_year:i32)->i64{
+    leap_years_before(end_year as i64)-leap_years_before(start_year as i64)
+}
+
+/// Returns the number of days from January 1 of `start_year` to January 1 of `end_year`
+/// # Example
+/// ```
+/// use utc_datetime::days_between_years;
+/// assert_eq!(days_between_years(1970,2021),18628);
+/// assert_eq!(days_between_years(2021,2020),-366);
+/// ```
+pub fn days_between_years(start_year:i32,end_year:i32)->i64{
+    365*(end_year as i64-start_year as i64)+leap_years_between(start_year,end_year)
+}
+
+/// Returns the number of days from date `a` to date `b`, both given as (year, month, day).
+///
+/// Any year is accepted. Returns MonthNumberError or DayNumberError for a date that does not exist.
+/// # Example
+/// ```
+/// use utc_datetime::days_between;
+/// assert_eq!(days_between((2021,11,15),(2021,12,25)).unwrap(),40);
+/// assert_eq!(days_between((2021,1,1),(1900,1,1)).unwrap(),-44195);
+/// assert!(days_between((2021,2,29),(2021,3,1)).is_err());
+/// ```
+pub fn days_between(a:(i32,u8,u8),b:(i32,u8,u8))->Result<i64, IllegalTimeError>{
+    let days=|(year,month,day):(i32,u8,u8)|{
+        let days_in_month=checked_days_of_the_month(year,month).ok_or(IllegalTimeError::MonthNumberError)?;
+        if day==0 || day>days_in_month{
+            return Err(IllegalTimeError::DayNumberError)
+        }
+        Ok(days_from_civil(year as i64,month,day))
+    };
+    Ok(days(b)?-days(a)?)
+}
+
+/// Returns the number of ISO 8601 weeks in a year, 52 or 53
+/// # Example
+/// ```
+/// use utc_datetime::weeks_in_iso_year;
+/// assert_eq!(weeks_in_iso_year(2020),53);
+/// assert_eq!(weeks_in_iso_year(2021),52);
+/// ```
+pub fn weeks_in_iso_year(year:i32)->u8{
+    iso_weeks(year as i64)
+}
+
+// 1月1日是星期四,或者闰年的1月1日是星期三时,这一年有53周
+pub(crate) fn iso_weeks(year:i64)->u8{
+    let jan_1=(4+days_from_civil(year,1,1)).rem_euclid(7);
+    let leap=(year%4==0 && year%100!=0) || year%400==0;
+    if jan_1==4 || leap && jan_1==3 {53} else {52}
+}
+
 /// Returns the number of days in this month
+///
+/// Panics if the month is not between 1 and 12, see [`checked_days_of_the_month`].
 /// # Example
 /// ```
 /// use utc_datetime::days_of_the_month;
 /// assert_eq!(days_of_the_month(2020,2),29);
 /// assert_eq!(days_of_the_month(2020,3),31)
 /// ```
-pub fn days_of_the_month(year:u16,month:u8)->u8{
-    match month{
-        1|3|5|7|8|10|12=>31,
-        4|6|9|11=>30,
-        2=>{
-            if leap_year(year){
-                return 29
-            }
-            28
-        }
-        _=>panic!("Illegal number of days in the month.")
-    }
+pub fn days_of_the_month(year:i32,month:u8)->u8{
+    checked_days_of_the_month(year,month).expect("Illegal number of days in the month.")
+}
+
+/// Returns the number of days in this month, or None if the month is not between 1 and 12
+/// # Example
+/// ```
+/// use utc_datetime::checked_days_of_the_month;
+/// assert_eq!(checked_days_of_the_month(2021,2),Some(28));
+/// assert_eq!(checked_days_of_the_month(2021,13),None);
+/// ```
+pub fn checked_days_of_the_month(year:i32,month:u8)->Option<u8>{
+    DAYS_IN_MONTH[leap_year(year) as usize].get((month as usize).checked_sub(1)?).copied()
+}
+
+/// Returns the earliest datetime, or None if `datetimes` is empty.
+///
+/// Use `Ord::clamp` to bound a single datetime to a window.
+/// # Example
+/// ```
+/// use utc_datetime::{earliest, latest, UtcDatetime};
+/// let dates=[UtcDatetime::new(2021,11,15,9,30,0).unwrap(),UtcDatetime::new(2020,2,2,2,2,2).unwrap()];
+/// assert_eq!(earliest(dates),Some(dates[1]));
+/// assert_eq!(latest(dates),Some(dates[0]));
+/// assert_eq!(earliest([]),None);
+/// let window=(UtcDatetime::new(2021,1,1,0,0,0).unwrap(),UtcDatetime::new(2021,12,31,23,59,59).unwrap());
+/// assert_eq!(dates[1].clamp(window.0,window.1),window.0);
+/// ```
+pub fn earliest<I:IntoIterator<Item=UtcDatetime>>(datetimes:I)->Option<UtcDatetime>{
+    datetimes.into_iter().min()
+}
+
+/// Returns the latest datetime, or None if `datetimes` is empty
+pub fn latest<I:IntoIterator<Item=UtcDatetime>>(datetimes:I)->Option<UtcDatetime>{
+    datetimes.into_iter().max()
 }
 
 #[cfg(test)]
 mod tests{
-    use super::UtcDatetime;
+    use super::{checked_days_of_the_month, civil_from_days, days_between, days_between_years, days_from_civil, days_of_the_month, days_of_the_year, leap_year, leap_years_between, Duration, IllegalTimeError, UtcDatetime};
     #[test]
     fn test1() {
         let a_utc_datetime=UtcDatetime::from_string("时间:2021年2月28日23点59分0秒").unwrap();
@@ -229,4 +855,160 @@ mod tests{
         let dt_2=UtcDatetime::new(2020,4,28,12,12,29).unwrap();
         assert!(dt_1>dt_2);
     }
-}
+
+    #[test]
+    fn test5(){
+        for ts in (0..u32::MAX).step_by(604_813){
+            assert_eq!(UtcDatetime::from_timestamp(ts).timestamp().unwrap(),ts);
+        }
+        assert_eq!(UtcDatetime::from_timestamp(u32::MAX),UtcDatetime::new(2106,2,7,6,28,15).unwrap());
+    }
+
+    // 逐年逐月累加的实现,用来验证days_from_civil算法
+    fn reference_timestamp(dt:&UtcDatetime)->u64{
+        let mut total_seconds=0;
+        for i in 1970..dt.year{
+            total_seconds+=days_of_the_year(i) as u64*24*60*60;
+        }
+        for i in 1..dt.month{
+            total_seconds+=days_of_the_month(dt.year,i) as u64*24*60*60;
+        }
+        total_seconds+(dt.day as u64-1)*24*60*60+dt.hour as u64*60*60+dt.minute as u64*60+dt.second as u64
+    }
+
+    #[test]
+    fn test6(){
+        for year in (1970..2106).chain([2400,9999,65535]){
+            for month in 1..=12{
+                for day in 1..=days_of_the_month(year,month){
+                    let dt=UtcDatetime::new(year,month,day,23,59,59).unwrap();
+                    let expected=reference_timestamp(&dt);
+                    assert_eq!(dt.timestamp().ok().map(u64::from),u32::try_from(expected).ok().map(u64::from));
+                    assert_eq!(dt.weekday() as u64,(4+expected/(24*60*60))%7);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test7(){
+        assert!(matches!(UtcDatetime::from_string("1000000-1-1 1:1:1"),Err(IllegalTimeError::YearNumberError)));
+        assert_eq!(UtcDatetime::from_string("99999-1-1 1:1:1").unwrap().year(),99999);
+        assert!(matches!(UtcDatetime::from_string("2021-1-1 1:1:99999999999"),Err(IllegalTimeError::SecondNumberError)));
+        assert!(matches!(UtcDatetime::from_string("2021-1-1 1:1:1 1"),Err(IllegalTimeError::TimeStringError)));
+        assert!(matches!(UtcDatetime::from_string("2021-1-1 1:1"),Err(IllegalTimeError::TimeStringError)));
+        assert_eq!(UtcDatetime::from_string("2021-1-1 1:1:1").unwrap(),UtcDatetime::new(2021,1,1,1,1,1).unwrap());
+    }
+
+    #[test]
+    fn test8(){
+        assert_eq!(UtcDatetime::MIN,UtcDatetime::new(-999_999,1,1,0,0,0).unwrap());
+        assert_eq!(UtcDatetime::MAX,UtcDatetime::new(999_999,12,31,23,59,59).unwrap());
+        assert_eq!(UtcDatetime::UNIX_EPOCH,UtcDatetime::new(1970,1,1,0,0,0).unwrap());
+        assert_eq!(UtcDatetime::default().timestamp().unwrap(),0);
+        assert!(matches!(UtcDatetime::new(UtcDatetime::MAX_YEAR+1,1,1,0,0,0),Err(IllegalTimeError::YearNumberError)));
+        assert!(matches!(UtcDatetime::new(UtcDatetime::MIN_YEAR-1,12,31,23,59,59),Err(IllegalTimeError::YearNumberError)));
+        // 公元前1年和公元1年之间的年份0是闰年
+        let bce=UtcDatetime::new(-1,12,31,23,59,59).unwrap();
+        assert_eq!(bce.add_duration(Duration::seconds(1)).unwrap(),UtcDatetime::new(0,1,1,0,0,0).unwrap());
+        assert_eq!(UtcDatetime::new(0,2,29,0,0,0).unwrap().unix_seconds(),-62162121600);
+        assert!(matches!(bce.timestamp(),Err(IllegalTimeError::YearNumberError)));
+        assert_eq!(format!("{} {:#}",bce,UtcDatetime::new(-44,3,15,12,0,0).unwrap()),"-0001-12-31 23:59:59 -0044-03-15T12:00:00Z");
+        for a_date in [UtcDatetime::MIN,bce,UtcDatetime::MAX]{
+            assert_eq!(UtcDatetime::from_unix_seconds(a_date.unix_seconds()).unwrap(),a_date);
+        }
+        assert!(UtcDatetime::from_unix_seconds(UtcDatetime::MIN.unix_seconds()-1).is_err());
+        assert!(UtcDatetime::from_unix_seconds(i64::MIN).is_err());
+        assert!(UtcDatetime::from_unix_seconds(i64::MAX).is_err());
+        assert!(leap_year(-4) && !leap_year(-100) && leap_year(-400));
+    }
+
+    #[test]
+    fn test9(){
+        let mut expected=days_from_civil(-1000,1,1);
+        for year in -1000..3000{
+            for month in 1..=12{
+                assert_eq!(checked_days_of_the_month(year as i32,month),Some(days_of_the_month(year as i32,month)));
+                for day in 1..=days_of_the_month(year as i32,month){
+                    assert_eq!(days_from_civil(year,month,day),expected);
+                    assert_eq!(civil_from_days(expected),(year,month,day));
+                    expected+=1;
+                }
+            }
+        }
+        assert_eq!(days_from_civil(1970,1,1),0);
+        assert_eq!(UtcDatetime::new_normalized(1969,13,1,0,0,0).unwrap(),UtcDatetime::UNIX_EPOCH);
+        assert_eq!(UtcDatetime::new_normalized(1970,1,1,0,0,i32::MAX as i64).unwrap(),UtcDatetime::from_timestamp(i32::MAX as u32));
+        assert!(UtcDatetime::new_normalized(i32::MAX,i64::MAX,i64::MAX,i64::MAX,i64::MAX,i64::MAX).is_err());
+        assert_eq!(UtcDatetime::new_normalized(1970,1,1,0,0,-1).unwrap(),UtcDatetime::new(1969,12,31,23,59,59).unwrap());
+        assert!(UtcDatetime::new_normalized(UtcDatetime::MIN_YEAR,1,1,0,0,-1).is_err());
+        assert_eq!(UtcDatetime::new_normalized(1900,1,days_from_civil(2021,11,15)-days_from_civil(1900,1,1)+1,9,30,0).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+        assert_eq!(UtcDatetime::new_normalized(2021,i32::MAX as i64,1,0,0,0).unwrap_err().to_string(),"Year Number Error");
+        assert_eq!(checked_days_of_the_month(2021,0),None);
+    }
+
+    #[test]
+    fn test10(){
+        let cutoffs=[0,1636968600,UtcDatetime::MAX.unix_seconds()];
+        let dates=[UtcDatetime::UNIX_EPOCH,UtcDatetime::new(2021,11,15,9,30,0).unwrap(),UtcDatetime::MAX];
+        for (date,cutoff) in dates.iter().zip(cutoffs){
+            assert!(*date==cutoff);
+            assert!(cutoff==*date);
+            assert!(*date<cutoff+1 && *date>cutoff-1);
+            assert!(cutoff-1<*date);
+            assert!(*date!=cutoff+1);
+        }
+        assert!(UtcDatetime::UNIX_EPOCH>-1 && UtcDatetime::MIN<0 && UtcDatetime::MAX<i64::MAX);
+    }
+
+    #[test]
+    fn test11(){
+        let a_date=UtcDatetime::new(2021,11,15,23,59,59).unwrap();
+        assert_eq!(a_date.next_aligned(1).unwrap(),UtcDatetime::new(2021,11,16,0,0,0).unwrap());
+        assert_eq!(a_date.next_aligned(7*3600).unwrap(),UtcDatetime::new(2021,11,16,0,0,0).unwrap());
+        assert_eq!(UtcDatetime::MIN.next_aligned(7*3600).unwrap().hour(),7);
+        assert_eq!(UtcDatetime::UNIX_EPOCH.next_aligned(u32::MAX).unwrap(),UtcDatetime::new(1970,1,2,0,0,0).unwrap());
+        assert!(UtcDatetime::MIN.next_aligned(0).is_err());
+        assert!(UtcDatetime::MAX.next_aligned(60).is_err());
+    }
+
+    #[test]
+    fn test12(){
+        for start in (-2000..2500).step_by(37){
+            for end in (-2000..2500).step_by(101){
+                let leaps=(start.min(end)..start.max(end)).filter(|&y|leap_year(y)).count() as i64;
+                assert_eq!(leap_years_between(start,end),if start<=end {leaps} else {-leaps});
+                assert_eq!(days_between_years(start,end),days_from_civil(end as i64,1,1)-days_from_civil(start as i64,1,1));
+            }
+        }
+        assert_eq!(days_between((i32::MIN,1,1),(i32::MAX,12,31)).unwrap(),days_between_years(i32::MIN,i32::MAX)+364);
+        assert!(matches!(days_between((2021,0,1),(2021,1,1)),Err(IllegalTimeError::MonthNumberError)));
+        assert!(matches!(days_between((2021,1,1),(2021,1,0)),Err(IllegalTimeError::DayNumberError)));
+        assert_eq!(days_between((2020,2,29),(2021,2,28)).unwrap(),365);
+        assert_eq!(days_between_years(i32::MIN,i32::MAX),days_from_civil(i32::MAX as i64,1,1)-days_from_civil(i32::MIN as i64,1,1));
+    }
+
+    #[test]
+    fn test13(){
+        let last=UtcDatetime::new(65535,12,31,23,59,59).unwrap();
+        for a_date in [UtcDatetime::UNIX_EPOCH,UtcDatetime::new(0,1,1,0,0,0).unwrap(),last]{
+            assert_eq!(UtcDatetime::try_from(<(u16,u8,u8,u8,u8,u8)>::try_from(a_date).unwrap()).unwrap(),a_date);
+        }
+        assert_eq!(<(u16,u8,u8,u8,u8,u8)>::try_from(last).unwrap().0,u16::MAX);
+        assert!(matches!(<(u16,u8,u8,u8,u8,u8)>::try_from(UtcDatetime::MAX),Err(IllegalTimeError::YearNumberError)));
+        assert!(matches!(<(u16,u8,u8,u8,u8,u8)>::try_from(UtcDatetime::MIN),Err(IllegalTimeError::YearNumberError)));
+        assert!(matches!(UtcDatetime::try_from((2021,11,15,24,0,0)),Err(IllegalTimeError::HourNumberError)));
+    }
+
+    #[test]
+    fn test14(){
+        let epoch=UtcDatetime::new(2000,1,1,12,0,0).unwrap();
+        for a_date in [UtcDatetime::MIN,epoch,UtcDatetime::MAX]{
+            assert_eq!(UtcDatetime::from_seconds_since(&epoch,a_date.seconds_since(&epoch)).unwrap(),a_date);
+        }
+        assert_eq!(UtcDatetime::MIN.seconds_since(&UtcDatetime::MIN),0);
+        assert!(matches!(UtcDatetime::from_seconds_since(&UtcDatetime::MAX,1),Err(IllegalTimeError::YearNumberError)));
+        assert!(matches!(UtcDatetime::from_seconds_since(&epoch,i64::MAX),Err(IllegalTimeError::YearNumberError)));
+        assert!(matches!(UtcDatetime::from_seconds_since(&epoch,i64::MIN),Err(IllegalTimeError::YearNumberError)));
+    }
+}
\ No newline at end of file
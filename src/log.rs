@@ -0,0 +1,101 @@
+//! Timestamps for [`log`](::log) backends such as env_logger and fern, enabled with the `log` feature.
+//!
+//! [`timestamp`] reads the [`SystemClock`] and displays in ISO 8601 or with a [`FormatDescription`],
+//! [`display_record`] displays a whole record in the env_logger default layout.
+//! # Example
+//! ```
+//! use utc_datetime::log::{display_record, timestamp};
+//! // env_logger: builder.format(|buf,record|writeln!(buf,"{}",display_record(record)))
+//! // fern: dispatch.format(|out,message,record|out.finish(format_args!("{} {} {}",timestamp(),record.level(),message)))
+//! let record=log::Record::builder().level(log::Level::Warn).target("app").args(format_args!("low disk")).build();
+//! assert!(display_record(&record).to_string().ends_with("Z WARN  app] low disk"));
+//! ```
+use std::fmt;
+use ::log::Record;
+use crate::UtcDatetime;
+use crate::clock::{Clock, SystemClock};
+use crate::format::FormatDescription;
+
+/// Displays the time of a log line, created by [`timestamp`] or [`timestamp_from`]
+#[derive(Clone,Copy,Debug)]
+pub struct LogTimestamp<'a>{
+    datetime:UtcDatetime,
+    description:Option<&'a FormatDescription<'a>>,
+}
+
+/// Displays a record as `[2021-11-15T09:30:00Z INFO  target] message`, created by [`display_record`]
+#[derive(Clone,Copy,Debug)]
+pub struct RecordLine<'a,'r>{
+    timestamp:LogTimestamp<'a>,
+    record:&'r Record<'r>,
+}
+
+/// The current time from the [`SystemClock`]
+pub fn timestamp()->LogTimestamp<'static>{
+    timestamp_from(&SystemClock)
+}
+
+/// The current time from a [`Clock`]
+pub fn timestamp_from<C:Clock+?Sized>(clock:&C)->LogTimestamp<'static>{
+    LogTimestamp{datetime:clock.now(),description:None}
+}
+
+/// Display a record with the current time from the [`SystemClock`]
+pub fn display_record<'r>(record:&'r Record<'r>)->RecordLine<'static,'r>{
+    timestamp().record(record)
+}
+
+impl<'a> LogTimestamp<'a>{
+    /// Display with a format description instead of ISO 8601
+    pub fn with_format<'b>(self,description:&'b FormatDescription<'b>)->LogTimestamp<'b>{
+        LogTimestamp{datetime:self.datetime,description:Some(description)}
+    }
+
+    /// Display a record with this time
+    pub fn record<'r>(self,record:&'r Record<'r>)->RecordLine<'a,'r>{
+        RecordLine{timestamp:self,record}
+    }
+
+    /// The time being displayed
+    pub fn datetime(&self)->UtcDatetime{
+        self.datetime
+    }
+}
+
+impl fmt::Display for LogTimestamp<'_>{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        match self.description{
+            Some(description)=>self.datetime.format_with_into(description,f),
+            None=>write!(f,"{}",self.datetime.display_iso8601()),
+        }
+    }
+}
+
+impl fmt::Display for RecordLine<'_,'_>{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        write!(f,"[{} {:<5} {}] {}",self.timestamp,self.record.level(),self.record.target(),self.record.args())
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use ::log::{Level, Record};
+    use crate::UtcDatetime;
+    use crate::clock::Clock;
+    use crate::format::FormatDescription;
+    use super::timestamp_from;
+
+    struct Fixed;
+    impl Clock for Fixed{
+        fn now(&self)->UtcDatetime{UtcDatetime::new(2021,11,15,9,30,0).unwrap()}
+    }
+
+    #[test]
+    fn display(){
+        assert_eq!(timestamp_from(&Fixed).to_string(),"2021-11-15T09:30:00Z");
+        let description=FormatDescription::new("%Y/%m/%d %H:%M:%S").unwrap();
+        assert_eq!(timestamp_from(&Fixed).with_format(&description).to_string(),"2021/11/15 09:30:00");
+        let record=Record::builder().level(Level::Info).target("app").args(format_args!("started")).build();
+        assert_eq!(timestamp_from(&Fixed).record(&record).to_string(),"[2021-11-15T09:30:00Z INFO  app] started");
+    }
+}
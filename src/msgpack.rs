@@ -0,0 +1,131 @@
+//! The MessagePack timestamp extension (type -1), enabled with the `rmp` feature.
+//!
+//! [`UtcDatetime::write_msgpack`] picks the shortest of the 32, 64 and 96-bit forms,
+//! [`UtcDatetime::read_msgpack`] accepts all three and truncates nanoseconds.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+//! let bytes=a_date.to_msgpack();
+//! assert_eq!(bytes,[0xd6,0xff,0x61,0x92,0x28,0x98]);
+//! assert_eq!(UtcDatetime::read_msgpack(&mut &bytes[..]).unwrap(),a_date);
+//! ```
+use std::fmt;
+use rmp::decode::{read_ext_meta, RmpRead, RmpReadErr, ValueReadError};
+use rmp::encode::{write_ext_meta, RmpWrite, ValueWriteError};
+use crate::{IllegalTimeError, UtcDatetime};
+
+/// The MessagePack extension type of timestamps
+pub const TIMESTAMP_TYPE:i8=-1;
+
+/// The error of [`UtcDatetime::read_msgpack`]
+#[derive(Debug)]
+pub enum MsgpackError<E:RmpReadErr>{
+    /// Reading the input failed, or it is not an extension
+    Read(ValueReadError<E>),
+    /// The extension is not a timestamp
+    NotTimestamp,
+    /// The timestamp is outside [`UtcDatetime::MIN`] to [`UtcDatetime::MAX`], or has 10^9 nanoseconds or more
+    Time(IllegalTimeError),
+}
+
+impl<E:RmpReadErr> fmt::Display for MsgpackError<E>{
+    fn fmt(&self,f:&mut fmt::Formatter)->fmt::Result{
+        match self{
+            MsgpackError::Read(e)=>write!(f,"{:?}",e),
+            MsgpackError::NotTimestamp=>write!(f,"The MessagePack extension is not a timestamp"),
+            MsgpackError::Time(e)=>write!(f,"{}",e),
+        }
+    }
+}
+
+impl<E:RmpReadErr> std::error::Error for MsgpackError<E>{}
+
+impl<E:RmpReadErr> From<ValueReadError<E>> for MsgpackError<E>{
+    fn from(e:ValueReadError<E>)->MsgpackError<E>{
+        MsgpackError::Read(e)
+    }
+}
+
+impl UtcDatetime{
+    /// Write as a MessagePack timestamp, 6 bytes from 1970 until 2106, 10 bytes until 2514 and 15 bytes before 1970 or after 2514
+    pub fn write_msgpack<W:RmpWrite>(&self,wr:&mut W)->Result<(),ValueWriteError<W::Error>>{
+        let seconds=self.unix_seconds();
+        // 没有纳秒,秒数在u32范围内用timestamp 32,在34位以内用timestamp 64,否则(包括负数)用timestamp 96
+        if let Ok(seconds)=u32::try_from(seconds){
+            write_ext_meta(wr,4,TIMESTAMP_TYPE)?;
+            wr.write_bytes(&seconds.to_be_bytes()).map_err(ValueWriteError::InvalidDataWrite)
+        }else if (0..1<<34).contains(&seconds){
+            write_ext_meta(wr,8,TIMESTAMP_TYPE)?;
+            wr.write_bytes(&(seconds as u64).to_be_bytes()).map_err(ValueWriteError::InvalidDataWrite)
+        }else{
+            write_ext_meta(wr,12,TIMESTAMP_TYPE)?;
+            wr.write_bytes(&[0;4]).and_then(|_|wr.write_bytes(&seconds.to_be_bytes())).map_err(ValueWriteError::InvalidDataWrite)
+        }
+    }
+
+    /// Encode as a MessagePack timestamp, see [`UtcDatetime::write_msgpack`]
+    pub fn to_msgpack(&self)->Vec<u8>{
+        let mut bytes=Vec::with_capacity(15);
+        self.write_msgpack(&mut bytes).expect("writing to a Vec cannot fail");
+        bytes
+    }
+
+    /// Read a MessagePack timestamp in any of its three forms
+    pub fn read_msgpack<R:RmpRead>(rd:&mut R)->Result<UtcDatetime, MsgpackError<R::Error>>{
+        let meta=read_ext_meta(rd)?;
+        if meta.typeid!=TIMESTAMP_TYPE || !matches!(meta.size,4|8|12){
+            return Err(MsgpackError::NotTimestamp)
+        }
+        let mut data=[0;12];
+        let data=&mut data[..meta.size as usize];
+        rd.read_exact_buf(data).map_err(|e|MsgpackError::Read(ValueReadError::InvalidDataRead(e)))?;
+        let (nanoseconds,seconds)=match *data{
+            [a,b,c,d]=>(0,u32::from_be_bytes([a,b,c,d]) as i64),
+            [a,b,c,d,e,f,g,h]=>{
+                let n=u64::from_be_bytes([a,b,c,d,e,f,g,h]);
+                ((n>>34) as u32,(n&0x3_ffff_ffff) as i64)
+            },
+            [a,b,c,d,e,f,g,h,i,j,k,l]=>(u32::from_be_bytes([a,b,c,d]),i64::from_be_bytes([e,f,g,h,i,j,k,l])),
+            _=>unreachable!(),
+        };
+        if nanoseconds>=1_000_000_000{
+            return Err(MsgpackError::Time(IllegalTimeError::SecondNumberError))
+        }
+        UtcDatetime::from_unix_seconds(seconds).map_err(MsgpackError::Time)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::{IllegalTimeError, UtcDatetime};
+    use super::MsgpackError;
+
+    #[test]
+    fn timestamp_forms(){
+        assert_eq!(UtcDatetime::UNIX_EPOCH.to_msgpack(),[0xd6,0xff,0,0,0,0]);
+        for (dt,header) in [(UtcDatetime::new(2106,2,7,6,28,16).unwrap(),[0xd7,0xff]),(UtcDatetime::MAX,[0xc7,12]),(UtcDatetime::MIN,[0xc7,12]),(UtcDatetime::new(1969,12,31,23,59,59).unwrap(),[0xc7,12])]{
+            let bytes=dt.to_msgpack();
+            assert_eq!(&bytes[..2],header);
+            assert_eq!(UtcDatetime::read_msgpack(&mut &bytes[..]).unwrap(),dt);
+        }
+        let last_64=UtcDatetime::UNIX_EPOCH.add_duration(crate::Duration::seconds((1<<34)-1)).unwrap();
+        assert_eq!(last_64.to_msgpack().len(),10);
+        assert_eq!(UtcDatetime::new(2514,5,30,1,53,4).unwrap().to_msgpack().len(),15);
+        // timestamp 64,500毫秒
+        let with_nanos=(500_000_000u64<<34|1_636_968_600).to_be_bytes();
+        let bytes=[&[0xd7,0xff][..],&with_nanos].concat();
+        assert_eq!(UtcDatetime::read_msgpack(&mut &bytes[..]).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+        // timestamp 96
+        let bytes=[&[0xc7,12,0xff,0,0,0,0][..],&1_636_968_600i64.to_be_bytes()].concat();
+        assert_eq!(UtcDatetime::read_msgpack(&mut &bytes[..]).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+        let negative=[&[0xc7,12,0xff,0,0,0,0][..],&(-1i64).to_be_bytes()].concat();
+        assert_eq!(UtcDatetime::read_msgpack(&mut &negative[..]).unwrap(),UtcDatetime::new(1969,12,31,23,59,59).unwrap());
+        let too_early=[&[0xc7,12,0xff,0,0,0,0][..],&i64::MIN.to_be_bytes()].concat();
+        assert!(matches!(UtcDatetime::read_msgpack(&mut &too_early[..]),Err(MsgpackError::Time(IllegalTimeError::YearNumberError))));
+        assert!(matches!(UtcDatetime::read_msgpack(&mut &[0xd6,0x01,0,0,0,0][..]),Err(MsgpackError::NotTimestamp)));
+        assert!(matches!(UtcDatetime::read_msgpack(&mut &[0xd5,0xff,0,0][..]),Err(MsgpackError::NotTimestamp)));
+        assert!(matches!(UtcDatetime::read_msgpack(&mut &[0xd6,0xff,0][..]),Err(MsgpackError::Read(_))));
+        assert!(matches!(UtcDatetime::read_msgpack(&mut &[0xc0][..]),Err(MsgpackError::Read(_))));
+    }
+}
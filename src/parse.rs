@@ -0,0 +1,317 @@
+//! Options for [`UtcDatetime::from_string_with`], and format detection for [`UtcDatetime::parse_auto`].
+use crate::{IllegalTimeError, UtcDatetime};
+
+/// How [`UtcDatetime::from_string_with`] interprets a time string.
+///
+/// The default matches [`UtcDatetime::from_string`].
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// use utc_datetime::parse::ParseOptions;
+/// let options=ParseOptions::new().two_digit_year_pivot(70);
+/// assert_eq!(UtcDatetime::from_string_with("21-11-15 09:30:00",&options).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+/// assert_eq!(UtcDatetime::from_string_with("99-11-15 09:30:00",&options).unwrap(),UtcDatetime::new(1999,11,15,9,30,0).unwrap());
+/// assert_eq!(UtcDatetime::from_string("21-11-15 09:30:00").unwrap().year(),21);
+/// ```
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub struct ParseOptions{
+    mode:ParseMode,
+    two_digit_year_pivot:Option<u8>,
+    exact:bool,
+}
+
+/// How strictly the layout of a time string is checked
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// use utc_datetime::parse::{ParseMode, ParseOptions};
+/// let strict=ParseOptions::new().mode(ParseMode::Strict);
+/// assert!(UtcDatetime::from_string_with("2021-11-15T09:30:00",&strict).is_ok());
+/// assert!(UtcDatetime::from_string_with("2021-11-15 9:30:00",&strict).is_err());
+/// assert!(UtcDatetime::from_string_with("2021年11月15日 9点30分0秒",&strict).is_err());
+/// assert!(UtcDatetime::from_string("2021年11月15日 9点30分0秒").is_ok());
+/// ```
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Default)]
+pub enum ParseMode{
+    /// Any run of non-digits separates the six fields, which may have any width
+    #[default]
+    Lenient,
+    /// `YYYY-MM-DD HH:MM:SS` at the start of the string, with every field zero-padded to two digits.
+    ///
+    /// The date may also be separated by `/` and the time by `T`, the year may have two digits
+    /// if [`ParseOptions::two_digit_year_pivot`] is set.
+    Strict,
+}
+
+impl ParseOptions{
+    pub const fn new()->ParseOptions{
+        ParseOptions{mode:ParseMode::Lenient,two_digit_year_pivot:None,exact:false}
+    }
+
+    pub const fn mode(self,mode:ParseMode)->ParseOptions{
+        ParseOptions{mode,..self}
+    }
+
+    /// Interpret a year written with exactly two digits as 20YY if it is below `pivot`, otherwise as 19YY.
+    ///
+    /// A pivot of 70 matches POSIX `strptime`, a pivot of 100 or more always gives 20YY.
+    /// Without a pivot a two-digit year is taken literally as a year of the first century.
+    pub const fn two_digit_year_pivot(self,pivot:u8)->ParseOptions{
+        ParseOptions{two_digit_year_pivot:Some(pivot),..self}
+    }
+
+    /// Return TrailingCharactersError if anything follows the seconds,
+    /// instead of ignoring trailing non-digits.
+    pub const fn exact(self)->ParseOptions{
+        ParseOptions{exact:true,..self}
+    }
+}
+
+impl UtcDatetime{
+    /// Convert a string containing time to UtcDatetime like [`UtcDatetime::from_string`],
+    /// with the behavior configured by [`ParseOptions`].
+    pub fn from_string_with(time_str:&str,options:&ParseOptions)->Result<UtcDatetime, IllegalTimeError>{
+        // 字符串开头紧跟数字的负号表示负数年份,与Display的输出一致
+        let (negative,bytes)=match time_str.as_bytes(){
+            [b'-',rest@..] if rest.first().is_some_and(u8::is_ascii_digit)=>(true,rest),
+            bytes=>(false,bytes),
+        };
+        let (mut fields,year_digits,end)=match options.mode{
+            ParseMode::Lenient=>scan_lenient(bytes,options.exact)?,
+            ParseMode::Strict=>scan_strict(bytes,options.two_digit_year_pivot.is_some())?,
+        };
+        if options.exact && end<bytes.len(){
+            return Err(IllegalTimeError::TrailingCharactersError)
+        }
+        if let (2,Some(pivot),false)=(year_digits,options.two_digit_year_pivot,negative){
+            fields[0]+=if fields[0]<pivot as u32 {2000} else {1900};
+        }
+        from_fields(fields,negative)
+    }
+}
+
+// 返回六个字段,年份的位数和秒之后的位置
+fn scan_lenient(bytes:&[u8],stop_after_seconds:bool)->Result<([u32;6],usize,usize), IllegalTimeError>{
+	// 能转换的字符串的日期必须为阿拉伯数字，且顺序必须按照年,月,日,小时,分,秒的顺序
+	// 只扫描一遍字节,连续的ASCII数字组成一个字段,其他字节都视为分隔符
+	// 多字节UTF-8字符的每个字节都大于127,不会被当成数字
+    let mut fields=[0u32;6];
+    let mut year_digits=0;
+    let mut count=0;
+    let mut in_number=false;
+    let mut end=0;
+    for (i,&byte) in bytes.iter().enumerate(){
+        if !byte.is_ascii_digit(){
+            if stop_after_seconds && count==6{
+                break
+            }
+            in_number=false;
+            continue
+        }
+        if !in_number{
+            if count==6{
+                return Err(IllegalTimeError::TimeStringError)
+            }
+            count+=1;
+            in_number=true;
+        }
+        if count==1{
+            year_digits+=1;
+        }
+        // 数字过大时饱和,之后转换类型时报错
+        let field=&mut fields[count-1];
+        *field=field.saturating_mul(10).saturating_add((byte-b'0') as u32);
+        end=i+1;
+    }
+    if count!=6{
+        return Err(IllegalTimeError::TimeStringError)
+    }
+    Ok((fields,year_digits,end))
+}
+
+fn scan_strict(bytes:&[u8],allow_two_digit_year:bool)->Result<([u32;6],usize,usize), IllegalTimeError>{
+    let year_digits=match bytes.iter().take_while(|b|b.is_ascii_digit()).count(){
+        4=>4,
+        2 if allow_two_digit_year=>2,
+        _=>return Err(IllegalTimeError::TimeStringError)
+    };
+    let date_sep=*bytes.get(year_digits).ok_or(IllegalTimeError::TimeStringError)?;
+    if !matches!(date_sep,b'-'|b'/'){
+        return Err(IllegalTimeError::TimeStringError)
+    }
+    // 年份之后的布局是固定的:分隔符和两位数字交替出现
+    let separators=[date_sep,date_sep,b' ',b':',b':'];
+    let mut fields=[0u32;6];
+    fields[0]=bytes[..year_digits].iter().fold(0,|n,b|n*10+(b-b'0') as u32);
+    let mut rest=&bytes[year_digits..];
+    for (field,sep) in fields[1..].iter_mut().zip(separators){
+        match rest{
+            [s,d0,d1,tail@..] if (*s==sep || sep==b' ' && *s==b'T') && d0.is_ascii_digit() && d1.is_ascii_digit()=>{
+                *field=((d0-b'0')*10+(d1-b'0')) as u32;
+                rest=tail;
+            }
+            _=>return Err(IllegalTimeError::TimeStringError)
+        }
+    }
+    if rest.first().is_some_and(u8::is_ascii_digit){
+        return Err(IllegalTimeError::TimeStringError)
+    }
+    Ok((fields,year_digits,bytes.len()-rest.len()))
+}
+
+/// The format recognized by [`UtcDatetime::parse_auto`]
+#[derive(Clone,Copy,PartialEq,Eq,Hash,Debug)]
+pub enum DetectedFormat{
+    /// [`UtcDatetime::from_rfc3339`]
+    Rfc3339,
+    /// [`UtcDatetime::from_rfc2822`]
+    Rfc2822,
+    /// [`UtcDatetime::from_sql_datetime`]
+    Sql,
+    /// [`UtcDatetime::from_compact`]
+    Compact,
+    /// [`UtcDatetime::from_string`]
+    Lenient,
+}
+
+type Parser=fn(&str)->Result<UtcDatetime, IllegalTimeError>;
+
+impl UtcDatetime{
+    /// Parse a time string in any supported format and report which format matched.
+    ///
+    /// The formats are tried in the order of [`DetectedFormat`]: RFC 3339, RFC 2822, SQL,
+    /// compact and finally the lenient digit form of [`UtcDatetime::from_string`].
+    /// If none matches the error of [`UtcDatetime::from_string`] is returned.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// use utc_datetime::parse::DetectedFormat;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::parse_auto("2021-11-15T17:30:00+08:00").unwrap(),(a_date,DetectedFormat::Rfc3339));
+    /// assert_eq!(UtcDatetime::parse_auto("Mon, 15 Nov 2021 09:30:00 GMT").unwrap(),(a_date,DetectedFormat::Rfc2822));
+    /// assert_eq!(UtcDatetime::parse_auto("2021-11-15 09:30:00").unwrap(),(a_date,DetectedFormat::Sql));
+    /// assert_eq!(UtcDatetime::parse_auto("20211115T093000Z").unwrap(),(a_date,DetectedFormat::Compact));
+    /// assert!(UtcDatetime::parse_auto("2021年11月15日 9点30分").is_err());
+    /// assert_eq!(UtcDatetime::parse_auto("2021/11/15 9:30:0").unwrap(),(a_date,DetectedFormat::Lenient));
+    /// ```
+    pub fn parse_auto(time_str:&str)->Result<(UtcDatetime,DetectedFormat), IllegalTimeError>{
+        let time_str=time_str.trim();
+        let parsers:[(Parser,DetectedFormat);4]=[
+            (UtcDatetime::from_rfc3339,DetectedFormat::Rfc3339),
+            (UtcDatetime::from_rfc2822,DetectedFormat::Rfc2822),
+            (UtcDatetime::from_sql_datetime,DetectedFormat::Sql),
+            (UtcDatetime::from_compact,DetectedFormat::Compact),
+        ];
+        for (parser,format) in parsers{
+            if let Ok(dt)=parser(time_str){
+                return Ok((dt,format))
+            }
+        }
+        UtcDatetime::from_string(time_str).map(|dt|(dt,DetectedFormat::Lenient))
+    }
+}
+
+fn from_fields([year,month,day,hour,minute,second]:[u32;6],negative:bool)->Result<UtcDatetime, IllegalTimeError>{
+    let year=i32::try_from(year).map_err(|_|IllegalTimeError::YearNumberError)?;
+    UtcDatetime::new(
+        if negative {-year} else {year},
+        u8::try_from(month).map_err(|_|IllegalTimeError::MonthNumberError)?,
+        u8::try_from(day).map_err(|_|IllegalTimeError::DayNumberError)?,
+        u8::try_from(hour).map_err(|_|IllegalTimeError::HourNumberError)?,
+        u8::try_from(minute).map_err(|_|IllegalTimeError::MinuteNumberError)?,
+        u8::try_from(second).map_err(|_|IllegalTimeError::SecondNumberError)?,
+    )
+}
+
+#[cfg(test)]
+mod tests{
+    use super::{ParseMode, ParseOptions};
+    use crate::{IllegalTimeError, UtcDatetime};
+
+    #[test]
+    fn pivot_only_applies_to_two_digits(){
+        let options=ParseOptions::new().two_digit_year_pivot(70);
+        assert_eq!(UtcDatetime::from_string_with("69-1-1 0:0:0",&options).unwrap().year(),2069);
+        assert_eq!(UtcDatetime::from_string_with("70-1-1 0:0:0",&options).unwrap().year(),1970);
+        assert_eq!(UtcDatetime::from_string_with("05-1-1 0:0:0",&options).unwrap().year(),2005);
+        assert_eq!(UtcDatetime::from_string_with("0021-1-1 0:0:0",&options).unwrap().year(),21);
+        assert_eq!(UtcDatetime::from_string_with("5-1-1 0:0:0",&options).unwrap().year(),5);
+        let always=ParseOptions::new().two_digit_year_pivot(100);
+        assert_eq!(UtcDatetime::from_string_with("99-1-1 0:0:0",&always).unwrap().year(),2099);
+    }
+
+    #[test]
+    fn strict_layout(){
+        let strict=ParseOptions::new().mode(ParseMode::Strict);
+        let expected=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        assert_eq!(UtcDatetime::from_string_with("2021/11/15 09:30:00",&strict).unwrap(),expected);
+        assert_eq!(UtcDatetime::from_string_with("2021-11-15T09:30:00Z",&strict).unwrap(),expected);
+        for bad in ["999999 1 1 1 1 1","2021-11/15 09:30:00","2021-11-15 09:30:000","2021-11-15 09:30"," 2021-11-15 09:30:00","21-11-15 09:30:00"]{
+            assert!(UtcDatetime::from_string_with(bad,&strict).is_err(),"{}",bad);
+        }
+        assert!(matches!(UtcDatetime::from_string_with("2021-11-15T09:30:00Z",&strict.exact()),Err(IllegalTimeError::TrailingCharactersError)));
+        let pivot=strict.two_digit_year_pivot(70);
+        assert_eq!(UtcDatetime::from_string_with("21-11-15 09:30:00",&pivot).unwrap(),expected);
+    }
+
+    #[test]
+    fn negative_years(){
+        let strict=ParseOptions::new().mode(ParseMode::Strict);
+        for dt in [UtcDatetime::new(-44,3,15,12,0,0).unwrap(),UtcDatetime::new(-1,12,31,23,59,59).unwrap(),UtcDatetime::MIN]{
+            assert_eq!(UtcDatetime::from_string(&dt.to_string()).unwrap(),dt);
+            assert_eq!(UtcDatetime::from_string_exact(&dt.to_string()).unwrap(),dt);
+        }
+        assert_eq!(UtcDatetime::from_string_with("-0044-03-15T12:00:00",&strict).unwrap(),UtcDatetime::new(-44,3,15,12,0,0).unwrap());
+        assert_eq!(UtcDatetime::from_string_with("-69-1-1 0:0:0",&ParseOptions::new().two_digit_year_pivot(70)).unwrap().year(),-69);
+        // 只有字符串开头的负号才表示负数年份
+        assert_eq!(UtcDatetime::from_string("at -0044-03-15 12:00:00").unwrap().year(),44);
+        assert_eq!(UtcDatetime::from_string("--0044-03-15 12:00:00").unwrap().year(),44);
+        assert!(UtcDatetime::from_string("-1000000-1-1 0:0:0").is_err());
+    }
+
+    // 确定性的随机输入,混合多字节字符、非ASCII数字和合法字符串的片段
+    fn fuzz_inputs()->impl Iterator<Item=String>{
+        let pieces=["0","1","9","12","2021","-","+",":","T","Z"," ",".",",","(",")","[","]","!","年","月","日","点","分","秒","é","٣","１","\u{0}","\u{10FFFF}","Mon","Nov","GMT","+08:00","UTC","99999999999"];
+        let mut state:u64=0x9E3779B97F4A7C15;
+        (0..20_000).map(move |_|{
+            let mut s=String::new();
+            loop{
+                state^=state<<13;
+                state^=state>>7;
+                state^=state<<17;
+                if state.is_multiple_of(13){
+                    break s
+                }
+                s.push_str(pieces[(state>>8) as usize%pieces.len()]);
+            }
+        })
+    }
+
+    #[test]
+    fn arbitrary_utf8_never_panics(){
+        let strict=ParseOptions::new().mode(ParseMode::Strict).two_digit_year_pivot(70);
+        for s in fuzz_inputs(){
+            let _=UtcDatetime::from_string(&s);
+            let _=UtcDatetime::from_string_exact(&s);
+            let _=UtcDatetime::from_string_with(&s,&strict);
+            let _=UtcDatetime::parse_auto(&s);
+            let _=UtcDatetime::from_rfc9557_with_annotations(&s);
+            let _=UtcDatetime::from_touch_time(&s,2021);
+            let _=UtcDatetime::from_common_log(&s);
+            let _=UtcDatetime::from_git_date_with_offset(&s);
+        }
+        // 只有ASCII数字是数字,全角和阿拉伯-印度数字都是分隔符
+        assert_eq!(UtcDatetime::from_string("２０２１年2021-11-15 09:30:00").unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+        assert!(matches!(UtcDatetime::from_string("٢٠٢١-١١-١٥ ٠٩:٣٠:٠٠"),Err(IllegalTimeError::TimeStringError)));
+        assert!(UtcDatetime::from_string_exact("2021-11-15 09:30:00\u{130}").is_err());
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest!{
+        #[test]
+        fn proptest_never_panics(s in proptest::prelude::any::<String>()){
+            let _=UtcDatetime::from_string(&s);
+            let _=UtcDatetime::parse_auto(&s);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::ops::Neg;
+use crate::{days_from_civil, days_of_the_month, is_valid_date, IllegalTimeError, UtcDatetime};
+
+/// A calendar period of years, months and days, whose length in seconds depends on where it is applied
+///
+/// [`UtcDatetime::add_period`] first adds the years and months together; if the day does not exist
+/// in the resulting month it is clamped to the last day of that month. The days are added afterwards
+/// and the time of day is kept. Unlike [`crate::Duration`], adding one month to January 31 gives
+/// February 28 (or 29), and adding a month of days gives different results in different months.
+/// # Example
+/// ```
+/// use utc_datetime::{Period, UtcDatetime};
+/// let a_date=UtcDatetime::new(2021,1,31,9,30,0).unwrap();
+/// assert_eq!(a_date.add_period(&Period::months(1)).unwrap(),UtcDatetime::new(2021,2,28,9,30,0).unwrap());
+/// let p=Period{years:1,months:1,days:1};
+/// assert_eq!(a_date.add_period(&p).unwrap(),UtcDatetime::new(2022,3,1,9,30,0).unwrap());
+/// assert_eq!(a_date.add_period(&-p).unwrap(),UtcDatetime::new(2019,12,30,9,30,0).unwrap());
+/// ```
+#[derive(Clone,Copy,PartialEq,Eq,Hash,Debug,Default)]
+pub struct Period{
+    pub years:i32,
+    pub months:i32,
+    pub days:i32,
+}
+
+impl Period{
+    pub const ZERO:Period=Period{years:0,months:0,days:0};
+
+    pub const fn years(years:i32)->Period{
+        Period{years,months:0,days:0}
+    }
+
+    pub const fn months(months:i32)->Period{
+        Period{years:0,months,days:0}
+    }
+
+    pub const fn days(days:i32)->Period{
+        Period{years:0,months:0,days}
+    }
+}
+
+impl Neg for Period{
+    type Output=Period;
+
+    fn neg(self)->Period{
+        Period{years:-self.years,months:-self.months,days:-self.days}
+    }
+}
+
+impl UtcDatetime{
+    /// Add a calendar period, see [`Period`] for the rules.
+    ///
+    /// Returns YearNumberError if the result is outside the representable range.
+    pub fn add_period(&self,period:&Period)->Result<UtcDatetime, IllegalTimeError>{
+        let moved=self.add_months_clamped(period.years as i64*12+period.months as i64)?;
+        let day=days_from_civil(moved.year as i64,moved.month,moved.day)+period.days as i64;
+        UtcDatetime::from_unix_seconds(day*86_400+self.seconds_from_midnight() as i64)
+    }
+
+    /// Subtract a calendar period, the same as adding its negation
+    pub fn sub_period(&self,period:&Period)->Result<UtcDatetime, IllegalTimeError>{
+        self.add_period(&-*period)
+    }
+
+    // 加上若干个月,日期超出新月份的天数时取该月最后一天
+    pub(crate) fn add_months_clamped(&self,months:i64)->Result<UtcDatetime, IllegalTimeError>{
+        let total=self.year as i64*12+self.month as i64-1+months;
+        let year=i32::try_from(total.div_euclid(12)).map_err(|_|IllegalTimeError::YearNumberError)?;
+        let month=(total.rem_euclid(12)+1) as u8;
+        is_valid_date(year,month,1)?;
+        Ok(UtcDatetime{year,month,day:self.day.min(days_of_the_month(year,month)),..*self})
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::Period;
+    use crate::UtcDatetime;
+
+    #[test]
+    fn clamping(){
+        let leap_day=UtcDatetime::new(2020,2,29,0,0,0).unwrap();
+        assert_eq!(leap_day.add_period(&Period::years(1)).unwrap(),UtcDatetime::new(2021,2,28,0,0,0).unwrap());
+        assert_eq!(leap_day.add_period(&Period::years(4)).unwrap(),UtcDatetime::new(2024,2,29,0,0,0).unwrap());
+        assert_eq!(leap_day.sub_period(&Period::months(1)).unwrap(),UtcDatetime::new(2020,1,29,0,0,0).unwrap());
+        assert_eq!(leap_day.add_period(&Period{years:0,months:12,days:-365}).unwrap(),UtcDatetime::new(2020,2,29,0,0,0).unwrap());
+        assert_eq!(leap_day.add_period(&Period::ZERO).unwrap(),leap_day);
+        assert!(UtcDatetime::MIN.sub_period(&Period::days(1)).is_err());
+        assert!(UtcDatetime::MAX.add_period(&Period::months(1)).is_err());
+        assert!(leap_day.add_period(&Period::years(i32::MAX)).is_err());
+    }
+}
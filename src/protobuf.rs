@@ -0,0 +1,52 @@
+use prost_types::Timestamp;
+use crate::{IllegalTimeError, UtcDatetime};
+
+/// Convert to the protobuf well-known `Timestamp`, `nanos` is always 0
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// let ts:prost_types::Timestamp=UtcDatetime::new(2021,11,15,9,30,0).unwrap().into();
+/// assert_eq!((ts.seconds,ts.nanos),(1636968600,0));
+/// ```
+impl From<UtcDatetime> for Timestamp{
+    fn from(dt:UtcDatetime)->Timestamp{
+        Timestamp{seconds:dt.unix_seconds(),nanos:0}
+    }
+}
+
+/// Convert from the protobuf well-known `Timestamp`, rounding down to the whole second.
+///
+/// `nanos` outside `0..1_000_000_000` is carried into the seconds as `Timestamp::normalize` would.
+/// Returns YearNumberError outside [`UtcDatetime::MIN`] to [`UtcDatetime::MAX`].
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// let ts=prost_types::Timestamp{seconds:1636968600,nanos:999_999_999};
+/// assert_eq!(UtcDatetime::try_from(ts).unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+/// ```
+impl TryFrom<Timestamp> for UtcDatetime{
+    type Error=IllegalTimeError;
+
+    fn try_from(ts:Timestamp)->Result<UtcDatetime, IllegalTimeError>{
+        let seconds=ts.seconds.checked_add(ts.nanos.div_euclid(1_000_000_000) as i64).ok_or(IllegalTimeError::YearNumberError)?;
+        UtcDatetime::from_unix_seconds(seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use prost_types::Timestamp;
+    use crate::UtcDatetime;
+
+    #[test]
+    fn timestamp(){
+        for dt in [UtcDatetime::MIN,UtcDatetime::new(2021,11,15,9,30,0).unwrap(),UtcDatetime::MAX]{
+            assert_eq!(UtcDatetime::try_from(Timestamp::from(dt)).unwrap(),dt);
+        }
+        assert_eq!(UtcDatetime::try_from(Timestamp{seconds:1,nanos:-1}).unwrap(),UtcDatetime::UNIX_EPOCH);
+        assert_eq!(UtcDatetime::try_from(Timestamp{seconds:0,nanos:2_000_000_000}).unwrap(),UtcDatetime::from_timestamp(2));
+        assert_eq!(UtcDatetime::try_from(Timestamp{seconds:0,nanos:-1}).unwrap(),UtcDatetime::new(1969,12,31,23,59,59).unwrap());
+        assert!(UtcDatetime::try_from(Timestamp{seconds:UtcDatetime::MIN.unix_seconds(),nanos:-1}).is_err());
+        assert!(UtcDatetime::try_from(Timestamp{seconds:i64::MAX,nanos:i32::MAX}).is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::UtcDatetime;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDateAccess, PyDateTime, PyTimeAccess, PyTzInfo, PyTzInfoAccess};
+use pyo3::{Borrowed, Bound, PyAny, PyErr, Python};
+
+// 转换为带tzinfo=timezone.utc的datetime.datetime
+impl<'py> IntoPyObject<'py> for &UtcDatetime{
+    type Target=PyDateTime;
+    type Output=Bound<'py,PyDateTime>;
+    type Error=PyErr;
+
+    fn into_pyobject(self,py:Python<'py>)->Result<Self::Output,Self::Error>{
+        let utc=PyTzInfo::utc(py)?;
+        PyDateTime::new(py,self.year,self.month,self.day,self.hour,self.minute,self.second,0,Some(&utc))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for UtcDatetime{
+    type Target=PyDateTime;
+    type Output=Bound<'py,PyDateTime>;
+    type Error=PyErr;
+
+    fn into_pyobject(self,py:Python<'py>)->Result<Self::Output,Self::Error>{
+        (&self).into_pyobject(py)
+    }
+}
+
+// 只接受带时区的datetime,先转换到UTC,微秒部分被舍去
+impl FromPyObject<'_,'_> for UtcDatetime{
+    type Error=PyErr;
+
+    fn extract(ob:Borrowed<'_,'_,PyAny>)->Result<Self,Self::Error>{
+        let dt=ob.cast::<PyDateTime>()?;
+        if dt.get_tzinfo().is_none(){
+            return Err(PyTypeError::new_err("expected a datetime with non-None tzinfo"))
+        }
+        let utc=dt.call_method1("astimezone",(PyTzInfo::utc(ob.py())?,))?;
+        let utc=utc.cast::<PyDateTime>()?;
+        UtcDatetime::new(utc.get_year(),utc.get_month(),utc.get_day(),utc.get_hour(),utc.get_minute(),utc.get_second())
+            .map_err(|e|PyValueError::new_err(format!("{:?}",e)))
+    }
+}
@@ -0,0 +1,37 @@
+use crate::UtcDatetime;
+use core::ops::Range;
+use rand::distr::{Distribution, StandardUniform};
+use rand::{Rng, RngExt};
+
+// 在时间戳范围内(1970-01-01 00:00:00到2106-02-07 06:28:15)均匀分布
+impl Distribution<UtcDatetime> for StandardUniform{
+    fn sample<R:Rng+?Sized>(&self,rng:&mut R)->UtcDatetime{
+        UtcDatetime::from_timestamp(rng.random())
+    }
+}
+
+impl UtcDatetime{
+    /// Return a random UtcDatetime uniformly distributed in `start..end`.
+    ///
+    /// Panics if the range is empty.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let start=UtcDatetime::new(2021,11,15,0,0,0).unwrap();
+    /// let end=UtcDatetime::new(2021,11,16,0,0,0).unwrap();
+    /// let dt=UtcDatetime::gen_range(&mut rand::rng(),start..end);
+    /// assert!(start<=dt && dt<end);
+    /// assert!(UtcDatetime::gen_range(&mut rand::rng(),UtcDatetime::MIN..UtcDatetime::UNIX_EPOCH)<UtcDatetime::UNIX_EPOCH);
+    ///
+    /// // 不限制范围时使用StandardUniform分布
+    /// use rand::RngExt;
+    /// let any:UtcDatetime=rand::rng().random();
+    /// assert!(any.timestamp().is_ok());
+    /// ```
+    pub fn gen_range<R:Rng+?Sized>(rng:&mut R,range:Range<UtcDatetime>)->UtcDatetime{
+        let start=range.start.unix_seconds();
+        let end=range.end.unix_seconds();
+        assert!(start<end,"cannot sample empty range");
+        UtcDatetime::from_unix_seconds(rng.random_range(start..end)).expect("a second inside the range is valid")
+    }
+}
@@ -0,0 +1,320 @@
+//! A subset of RFC 5545 recurrence rules.
+//!
+//! Supported parts are `FREQ` (`DAILY`, `WEEKLY`, `MONTHLY` or `YEARLY`), `INTERVAL`, `BYDAY`,
+//! `BYMONTHDAY`, `COUNT` and `UNTIL`. Weeks start on Monday. `BYDAY` may have an ordinal such as
+//! `-1FR` only with `MONTHLY` or `YEARLY`. Occurrences keep the time of day of the start,
+//! and the start itself is only an occurrence if it matches the rule.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use utc_datetime::rrule::RecurrenceRule;
+//! let rule=RecurrenceRule::parse("RRULE:FREQ=MONTHLY;BYDAY=-1FR;COUNT=3").unwrap();
+//! let start=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+//! let dates:Vec<_>=rule.occurrences(start).map(|dt|dt.to_string()).collect();
+//! assert_eq!(dates,["2021-11-26 09:30:00","2021-12-31 09:30:00","2022-01-28 09:30:00"]);
+//! ```
+use crate::format::{FormatDescription, FormatItem};
+use crate::{civil_from_days, days_from_civil, days_of_the_month, IllegalTimeError, UtcDatetime};
+
+const WEEKDAY_NAMES:[&str;7]=["SU","MO","TU","WE","TH","FR","SA"];
+const UNTIL_FORMAT:FormatDescription=FormatDescription::from_items(&[
+    FormatItem::Year,FormatItem::Month,FormatItem::Day,FormatItem::Literal("T"),
+    FormatItem::Hour,FormatItem::Minute,FormatItem::Second,
+]);
+const UNTIL_DATE_FORMAT:FormatDescription=FormatDescription::from_items(&[FormatItem::Year,FormatItem::Month,FormatItem::Day]);
+// 日历每400年重复一次,这么久都没有匹配的日期就不会再有了
+const SEARCH_YEARS:i64=400;
+
+#[derive(Clone,Copy,PartialEq,Eq,Debug)]
+enum Frequency{
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A parsed recurrence rule
+#[derive(Clone,PartialEq,Eq,Debug)]
+pub struct RecurrenceRule{
+    frequency:Frequency,
+    interval:u32,
+    // (序号,星期),序号为0表示每一个,星期天为0
+    by_day:Vec<(i8,u8)>,
+    by_month_day:Vec<i8>,
+    count:Option<u32>,
+    until:Option<UtcDatetime>,
+}
+
+impl RecurrenceRule{
+    /// Parse a rule such as `FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20211231T235959Z`, with or without the `RRULE:` prefix.
+    ///
+    /// A date-only `UNTIL` includes the whole day. Returns TimeStringError for an unsupported or malformed rule.
+    pub fn parse(rule:&str)->Result<RecurrenceRule, IllegalTimeError>{
+        let rule=rule.trim();
+        let rule=rule.strip_prefix("RRULE:").unwrap_or(rule);
+        let mut frequency=None;
+        let mut parsed=RecurrenceRule{frequency:Frequency::Daily,interval:1,by_day:Vec::new(),by_month_day:Vec::new(),count:None,until:None};
+        for part in rule.split(';'){
+            let (name,value)=part.split_once('=').ok_or(IllegalTimeError::TimeStringError)?;
+            match name{
+                "FREQ"=>frequency=Some(match value{
+                    "DAILY"=>Frequency::Daily,
+                    "WEEKLY"=>Frequency::Weekly,
+                    "MONTHLY"=>Frequency::Monthly,
+                    "YEARLY"=>Frequency::Yearly,
+                    _=>return Err(IllegalTimeError::TimeStringError)
+                }),
+                "INTERVAL"=>parsed.interval=value.parse().ok().filter(|&n|n>0).ok_or(IllegalTimeError::TimeStringError)?,
+                "COUNT"=>parsed.count=Some(value.parse().map_err(|_|IllegalTimeError::TimeStringError)?),
+                "UNTIL"=>parsed.until=Some(parse_until(value)?),
+                "BYDAY"=>parsed.by_day=value.split(',').map(parse_weekday).collect::<Result<_,_>>()?,
+                "BYMONTHDAY"=>parsed.by_month_day=value.split(',').map(|s|{
+                    s.parse::<i8>().ok().filter(|n|(1..=31).contains(&n.unsigned_abs())).ok_or(IllegalTimeError::TimeStringError)
+                }).collect::<Result<_,_>>()?,
+                _=>return Err(IllegalTimeError::TimeStringError)
+            }
+        }
+        parsed.frequency=frequency.ok_or(IllegalTimeError::TimeStringError)?;
+        let ordinals_allowed=matches!(parsed.frequency,Frequency::Monthly|Frequency::Yearly);
+        if !ordinals_allowed && parsed.by_day.iter().any(|&(n,_)|n!=0){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        Ok(parsed)
+    }
+
+    /// Iterate over the occurrences on or after `start`, in chronological order
+    pub fn occurrences(&self,start:UtcDatetime)->Occurrences<'_>{
+        Occurrences{rule:self,start,period:0,pending:Vec::new(),emitted:0,last_hit_year:start.year as i64,done:false}
+    }
+
+    // 第period个周期内的候选日期(距1970年1月1日的天数),返回None表示超出范围
+    fn candidates(&self,start:&UtcDatetime,period:i64,out:&mut Vec<i64>)->Option<i64>{
+        let start_day=days_from_civil(start.year as i64,start.month,start.day);
+        let step=period*self.interval as i64;
+        let year=match self.frequency{
+            Frequency::Daily=>{
+                let day=start_day+step;
+                out.push(day);
+                civil_from_days(day).0
+            }
+            Frequency::Weekly=>{
+                // 星期一为一周的第一天
+                let monday=start_day-(3+start_day).rem_euclid(7)+step*7;
+                if self.by_day.is_empty(){
+                    out.push(monday+(start_day-monday).rem_euclid(7));
+                }else{
+                    out.extend(self.by_day.iter().map(|&(_,weekday)|monday+(weekday as i64+6)%7));
+                }
+                civil_from_days(monday).0
+            }
+            Frequency::Monthly=>{
+                let months=start.year as i64*12+start.month as i64-1+step;
+                // 公元前的月份也要向下取整
+                let year=in_range(months.div_euclid(12))?;
+                self.expand_month(year,(months.rem_euclid(12)+1) as u8,start.day,out);
+                year
+            }
+            Frequency::Yearly=>{
+                let year=in_range(start.year as i64+step)?;
+                if self.by_day.is_empty() && self.by_month_day.is_empty(){
+                    if start.day<=days_of_the_month(year as i32,start.month){
+                        out.push(days_from_civil(year,start.month,start.day));
+                    }
+                }else if self.by_month_day.is_empty(){
+                    self.expand_by_day(days_from_civil(year,1,1),days_from_civil(year,12,31),out);
+                }else{
+                    (1..=12).for_each(|month|self.expand_month(year,month,start.day,out));
+                }
+                year
+            }
+        };
+        if year>UtcDatetime::MAX_YEAR as i64{
+            return None
+        }
+        // 在不展开日期的频率下,BYDAY和BYMONTHDAY用来过滤
+        if self.frequency!=Frequency::Weekly && self.frequency!=Frequency::Daily{
+            return Some(year)
+        }
+        out.retain(|&day|{
+            let weekday=(4+day).rem_euclid(7) as u8;
+            (self.by_day.is_empty() || self.by_day.iter().any(|&(_,w)|w==weekday))
+                && (self.by_month_day.is_empty() || self.month_day_matches(day))
+        });
+        Some(year)
+    }
+
+    fn expand_month(&self,year:i64,month:u8,start_day:u8,out:&mut Vec<i64>){
+        let first=days_from_civil(year,month,1);
+        let length=days_of_the_month(year as i32,month) as i64;
+        if self.by_month_day.is_empty() && self.by_day.is_empty(){
+            if start_day as i64<=length{
+                out.push(first+start_day as i64-1);
+            }
+        }else if self.by_month_day.is_empty(){
+            self.expand_by_day(first,first+length-1,out);
+        }else{
+            let mut by_day=Vec::new();
+            self.expand_by_day(first,first+length-1,&mut by_day);
+            for &n in &self.by_month_day{
+                let day=if n>0 {n as i64} else {length+1+n as i64};
+                // 同时有BYDAY时取交集
+                if (1..=length).contains(&day) && (self.by_day.is_empty() || by_day.contains(&(first+day-1))){
+                    out.push(first+day-1);
+                }
+            }
+        }
+    }
+
+    // 在[first,last]内展开BYDAY,有序号时只取第n个(负数从后往前数)
+    fn expand_by_day(&self,first:i64,last:i64,out:&mut Vec<i64>){
+        for &(n,weekday) in &self.by_day{
+            let first_match=first+(weekday as i64-(4+first).rem_euclid(7)).rem_euclid(7);
+            let last_match=last-((4+last).rem_euclid(7)-weekday as i64).rem_euclid(7);
+            match n{
+                0=>out.extend((first_match..=last_match).step_by(7)),
+                1..=i8::MAX=>out.extend(Some(first_match+(n as i64-1)*7).filter(|&d|d<=last)),
+                _=>out.extend(Some(last_match-(-(n as i64)-1)*7).filter(|&d|d>=first)),
+            }
+        }
+    }
+
+    fn month_day_matches(&self,day:i64)->bool{
+        let (year,month,day_of_month)=civil_from_days(day);
+        let length=days_of_the_month(year as i32,month) as i8;
+        self.by_month_day.iter().any(|&n|n==day_of_month as i8 || n<0 && length+1+n==day_of_month as i8)
+    }
+}
+
+impl core::str::FromStr for RecurrenceRule{
+    type Err=IllegalTimeError;
+
+    fn from_str(s:&str)->Result<RecurrenceRule, IllegalTimeError>{
+        RecurrenceRule::parse(s)
+    }
+}
+
+// 超出UtcDatetime范围的年份返回None,迭代到此结束
+fn in_range(year:i64)->Option<i64>{
+    i32::try_from(year).ok()
+        .filter(|year|(UtcDatetime::MIN_YEAR..=UtcDatetime::MAX_YEAR).contains(year))
+        .map(i64::from)
+}
+
+fn parse_weekday(s:&str)->Result<(i8,u8), IllegalTimeError>{
+    let split=s.len().checked_sub(2).filter(|&i|s.is_char_boundary(i)).ok_or(IllegalTimeError::TimeStringError)?;
+    let (ordinal,name)=s.split_at(split);
+    let weekday=WEEKDAY_NAMES.iter().position(|&w|w==name).ok_or(IllegalTimeError::TimeStringError)? as u8;
+    let ordinal=match ordinal{
+        ""=>0,
+        _=>ordinal.parse::<i8>().ok().filter(|n|(1..=53).contains(&n.unsigned_abs())).ok_or(IllegalTimeError::TimeStringError)?,
+    };
+    Ok((ordinal,weekday))
+}
+
+fn parse_until(s:&str)->Result<UtcDatetime, IllegalTimeError>{
+    if let Some(datetime)=s.strip_suffix('Z').filter(|s|s.len()==15){
+        return UtcDatetime::parse(datetime,&UNTIL_FORMAT)
+    }
+    UtcDatetime::parse(s,&UNTIL_DATE_FORMAT)
+        .map(|date|UtcDatetime{hour:23,minute:59,second:59,..date})
+        .map_err(|_|IllegalTimeError::TimeStringError)
+}
+
+/// Iterator over the occurrences of a [`RecurrenceRule`], created by [`RecurrenceRule::occurrences`]
+#[derive(Clone,Debug)]
+pub struct Occurrences<'a>{
+    rule:&'a RecurrenceRule,
+    start:UtcDatetime,
+    period:i64,
+    // 当前周期内还没有返回的日期,倒序存放
+    pending:Vec<i64>,
+    emitted:u32,
+    last_hit_year:i64,
+    done:bool,
+}
+
+impl Iterator for Occurrences<'_>{
+    type Item=UtcDatetime;
+
+    fn next(&mut self)->Option<UtcDatetime>{
+        if self.rule.count.is_some_and(|count|self.emitted>=count){
+            self.done=true;
+        }
+        while !self.done{
+            if let Some(day)=self.pending.pop(){
+                let time_of_day=self.start.hour as i64*3600+self.start.minute as i64*60+self.start.second as i64;
+                let Ok(dt)=UtcDatetime::from_unix_seconds(day*24*60*60+time_of_day) else{
+                    continue
+                };
+                if dt<self.start{
+                    continue
+                }
+                if self.rule.until.is_some_and(|until|dt>until){
+                    break
+                }
+                self.emitted+=1;
+                self.last_hit_year=dt.year as i64;
+                return Some(dt)
+            }
+            match self.rule.candidates(&self.start,self.period,&mut self.pending){
+                Some(year) if year<=self.last_hit_year+SEARCH_YEARS=>{
+                    self.period+=1;
+                    self.pending.sort_unstable_by(|a,b|b.cmp(a));
+                    self.pending.dedup();
+                }
+                _=>break
+            }
+        }
+        self.done=true;
+        self.pending.clear();
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use super::RecurrenceRule;
+    use crate::UtcDatetime;
+
+    fn dates(rule:&str,start:UtcDatetime,limit:usize)->Vec<String>{
+        let rule=RecurrenceRule::parse(rule).unwrap();
+        rule.occurrences(start).take(limit).map(|dt|dt.to_string()).collect()
+    }
+
+    #[test]
+    fn occurrences(){
+        let start=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        assert_eq!(dates("FREQ=WEEKLY;INTERVAL=2;BYDAY=WE,MO;COUNT=4",start,10),
+            ["2021-11-15 09:30:00","2021-11-17 09:30:00","2021-11-29 09:30:00","2021-12-01 09:30:00"]);
+        assert_eq!(dates("FREQ=DAILY;BYMONTHDAY=1,-1;UNTIL=20220101",start,10),
+            ["2021-11-30 09:30:00","2021-12-01 09:30:00","2021-12-31 09:30:00","2022-01-01 09:30:00"]);
+        assert_eq!(dates("FREQ=MONTHLY;BYMONTHDAY=13;BYDAY=FR",start,2),["2022-05-13 09:30:00","2023-01-13 09:30:00"]);
+        assert_eq!(dates("FREQ=MONTHLY",UtcDatetime::new(2021,1,31,0,0,0).unwrap(),3),
+            ["2021-01-31 00:00:00","2021-03-31 00:00:00","2021-05-31 00:00:00"]);
+        assert_eq!(dates("FREQ=YEARLY;BYDAY=1MO",start,2),["2022-01-03 09:30:00","2023-01-02 09:30:00"]);
+        assert_eq!(dates("FREQ=YEARLY",UtcDatetime::new(2020,2,29,0,0,0).unwrap(),2),["2020-02-29 00:00:00","2024-02-29 00:00:00"]);
+        assert_eq!(dates("FREQ=DAILY;UNTIL=20211116T093000Z",start,10),["2021-11-15 09:30:00","2021-11-16 09:30:00"]);
+        assert!(dates("FREQ=MONTHLY;INTERVAL=12;BYMONTHDAY=30",UtcDatetime::new(2021,2,1,0,0,0).unwrap(),1).is_empty());
+        for bad in ["FREQ=HOURLY","INTERVAL=2","FREQ=DAILY;BYDAY=1MO","FREQ=WEEKLY;BYDAY=XX","FREQ=DAILY;INTERVAL=0","FREQ=DAILY;UNTIL=2021"]{
+            assert!(RecurrenceRule::parse(bad).is_err(),"{}",bad);
+        }
+    }
+    #[test]
+    fn before_year_zero(){
+        let start=UtcDatetime::new(-1,2,1,0,0,0).unwrap();
+        assert_eq!(dates("FREQ=MONTHLY;COUNT=3",start,10),["-0001-02-01 00:00:00","-0001-03-01 00:00:00","-0001-04-01 00:00:00"]);
+        assert_eq!(dates("FREQ=MONTHLY;INTERVAL=11;COUNT=2",start,10),["-0001-02-01 00:00:00","0000-01-01 00:00:00"]);
+        assert_eq!(dates("FREQ=YEARLY;COUNT=3",start,10),["-0001-02-01 00:00:00","0000-02-01 00:00:00","0001-02-01 00:00:00"]);
+        assert_eq!(dates("FREQ=YEARLY;BYDAY=-1SU",UtcDatetime::MIN,1),["-999999-12-30 00:00:00"]);
+    }
+
+    #[test]
+    fn ends_at_max_year(){
+        let start=UtcDatetime::new(999_999,11,1,0,0,0).unwrap();
+        assert_eq!(dates("FREQ=MONTHLY",start,10),["999999-11-01 00:00:00","999999-12-01 00:00:00"]);
+        assert_eq!(dates("FREQ=YEARLY;INTERVAL=2",UtcDatetime::new(999_997,3,1,0,0,0).unwrap(),10),["999997-03-01 00:00:00","999999-03-01 00:00:00"]);
+        assert_eq!(dates("FREQ=MONTHLY;BYMONTHDAY=-1",start,10),["999999-11-30 00:00:00","999999-12-31 00:00:00"]);
+        assert_eq!(dates("FREQ=DAILY",UtcDatetime::new(999_999,12,30,12,0,0).unwrap(),10),["999999-12-30 12:00:00","999999-12-31 12:00:00"]);
+        assert_eq!(dates("FREQ=WEEKLY;BYDAY=FR",start,100).len(),9);
+    }
+}
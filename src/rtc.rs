@@ -0,0 +1,265 @@
+use crate::{IllegalTimeError, UtcDatetime};
+
+// 两位十进制数与BCD码的转换,非法的BCD码返回None
+fn to_bcd(n:u8)->u8{
+    (n/10)<<4 | (n%10)
+}
+
+fn from_bcd(bcd:u8)->Option<u8>{
+    let (high,low)=(bcd>>4,bcd&0xf);
+    if high>9 || low>9{
+        return None
+    }
+    Some(high*10+low)
+}
+
+impl UtcDatetime{
+    /// Convert to the seven BCD time registers of a DS3231-style RTC chip.
+    ///
+    /// | register | 0 | 1 | 2 | 3 | 4 | 5 | 6 |
+    /// |----------|---|---|---|---|---|---|---|
+    /// | field | second | minute | hour (24-hour) | weekday, 1 is Sunday | day | month, bit 7 is the century | year 00-99 |
+    ///
+    /// The century bit is set for 2100 to 2199. The PCF8563 orders its registers differently,
+    /// see [`UtcDatetime::to_pcf8563_registers`].
+    /// Returns YearNumberError for a year outside 2000 to 2199.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_bcd_registers().unwrap(),[0x00,0x30,0x09,0x02,0x15,0x11,0x21]);
+    /// ```
+    pub fn to_bcd_registers(&self)->Result<[u8;7], IllegalTimeError>{
+        if !(2000..2200).contains(&self.year){
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        let century=if self.year>=2100 {0x80} else {0};
+        Ok([
+            to_bcd(self.second),
+            to_bcd(self.minute),
+            to_bcd(self.hour),
+            self.weekday()+1,
+            to_bcd(self.day),
+            century | to_bcd(self.month),
+            to_bcd((self.year%100) as u8),
+        ])
+    }
+
+    /// Create a UtcDatetime from the registers described in [`UtcDatetime::to_bcd_registers`].
+    ///
+    /// The weekday register is ignored, and the 12-hour mode (bit 6 of the hour, bit 5 for PM) is understood.
+    /// Returns the error of the first field that is not valid BCD or out of range.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::from_bcd_registers([0x00,0x30,0x09,0x02,0x15,0x11,0x21]).unwrap();
+    /// assert_eq!(a_date,UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+    /// // 9:30 PM in 12-hour mode
+    /// assert_eq!(UtcDatetime::from_bcd_registers([0x00,0x30,0x69,0x02,0x15,0x11,0x21]).unwrap().hour(),21);
+    /// ```
+    pub fn from_bcd_registers(registers:[u8;7])->Result<UtcDatetime, IllegalTimeError>{
+        let [second,minute,hour,_,day,month,year]=registers;
+        let second=from_bcd(second&0x7f).ok_or(IllegalTimeError::SecondNumberError)?;
+        let minute=from_bcd(minute&0x7f).ok_or(IllegalTimeError::MinuteNumberError)?;
+        let hour=if hour&0x40!=0{
+            // 12小时制,12点表示0点或中午
+            let h=from_bcd(hour&0x1f).filter(|h|(1..=12).contains(h)).ok_or(IllegalTimeError::HourNumberError)?;
+            h%12+if hour&0x20!=0 {12} else {0}
+        }else{
+            from_bcd(hour&0x3f).ok_or(IllegalTimeError::HourNumberError)?
+        };
+        let day=from_bcd(day&0x3f).ok_or(IllegalTimeError::DayNumberError)?;
+        let century=if month&0x80!=0 {2100} else {2000};
+        let month=from_bcd(month&0x1f).ok_or(IllegalTimeError::MonthNumberError)?;
+        let year=from_bcd(year).ok_or(IllegalTimeError::YearNumberError)?;
+        UtcDatetime::new(century+year as i32,month,day,hour,minute,second)
+    }
+
+    /// Convert to the seven BCD time registers of a PCF8563-style RTC chip, starting at register 02h.
+    ///
+    /// | register | 0 | 1 | 2 | 3 | 4 | 5 | 6 |
+    /// |----------|---|---|---|---|---|---|---|
+    /// | field | second | minute | hour (24-hour) | day | weekday, 0 is Sunday | month, bit 7 is the century | year 00-99 |
+    ///
+    /// As in the datasheet, the century bit is set for 1900 to 1999.
+    /// Returns YearNumberError for a year outside 1900 to 2099.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_pcf8563_registers().unwrap(),[0x00,0x30,0x09,0x15,0x01,0x11,0x21]);
+    /// ```
+    pub fn to_pcf8563_registers(&self)->Result<[u8;7], IllegalTimeError>{
+        if !(1900..2100).contains(&self.year){
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        let century=if self.year<2000 {0x80} else {0};
+        Ok([
+            to_bcd(self.second),
+            to_bcd(self.minute),
+            to_bcd(self.hour),
+            to_bcd(self.day),
+            self.weekday(),
+            century | to_bcd(self.month),
+            to_bcd((self.year%100) as u8),
+        ])
+    }
+
+    /// Create a UtcDatetime from the registers described in [`UtcDatetime::to_pcf8563_registers`].
+    ///
+    /// The weekday register and the voltage-low flag (bit 7 of the second) are ignored.
+    /// Returns the error of the first field that is not valid BCD or out of range.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::from_pcf8563_registers([0x80,0x30,0x09,0x15,0x01,0x11,0x21]).unwrap();
+    /// assert_eq!(a_date,UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+    /// ```
+    pub fn from_pcf8563_registers(registers:[u8;7])->Result<UtcDatetime, IllegalTimeError>{
+        let [second,minute,hour,day,_,month,year]=registers;
+        let second=from_bcd(second&0x7f).ok_or(IllegalTimeError::SecondNumberError)?;
+        let minute=from_bcd(minute&0x7f).ok_or(IllegalTimeError::MinuteNumberError)?;
+        let hour=from_bcd(hour&0x3f).ok_or(IllegalTimeError::HourNumberError)?;
+        let day=from_bcd(day&0x3f).ok_or(IllegalTimeError::DayNumberError)?;
+        let century=if month&0x80!=0 {1900} else {2000};
+        let month=from_bcd(month&0x1f).ok_or(IllegalTimeError::MonthNumberError)?;
+        let year=from_bcd(year).ok_or(IllegalTimeError::YearNumberError)?;
+        UtcDatetime::new(century+year as i32,month,day,hour,minute,second)
+    }
+}
+
+/// The error of [`UtcDateTimeAccess`], enabled with the `embedded` feature
+#[cfg(feature = "embedded")]
+#[derive(Debug)]
+pub enum RtcError<E>{
+    /// The driver failed
+    Device(E),
+    /// The RTC holds a time that UtcDatetime cannot represent, or the other way around
+    Time(IllegalTimeError),
+}
+
+/// Read and set an [`rtcc`] driver as UtcDatetime, enabled with the `embedded` feature.
+///
+/// Implemented for every [`rtcc::DateTimeAccess`], the RTC is assumed to run on UTC.
+/// The crate still depends on `std`, so this works on targets with a standard library
+/// such as ESP-IDF or embedded Linux, not on bare-metal `no_std` firmware.
+/// # Example
+/// ```
+/// use utc_datetime::{UtcDateTimeAccess, UtcDatetime};
+/// use utc_datetime::RtcError;
+/// fn sync<R:UtcDateTimeAccess>(rtc:&mut R,network_time:UtcDatetime)->Result<(),RtcError<R::Error>>{
+///     rtc.set_utc_datetime(&network_time)
+/// }
+/// ```
+#[cfg(feature = "embedded")]
+pub trait UtcDateTimeAccess:rtcc::DateTimeAccess{
+    /// Read the current time, fractions of a second are truncated
+    fn utc_datetime(&mut self)->Result<UtcDatetime, RtcError<Self::Error>>;
+
+    /// Set the current time, returns [`RtcError::Time`] if the year is outside the range of chrono
+    fn set_utc_datetime(&mut self,datetime:&UtcDatetime)->Result<(), RtcError<Self::Error>>;
+}
+
+#[cfg(feature = "embedded")]
+impl<R:rtcc::DateTimeAccess+?Sized> UtcDateTimeAccess for R{
+    fn utc_datetime(&mut self)->Result<UtcDatetime, RtcError<Self::Error>>{
+        UtcDatetime::try_from(self.datetime().map_err(RtcError::Device)?).map_err(RtcError::Time)
+    }
+
+    fn set_utc_datetime(&mut self,datetime:&UtcDatetime)->Result<(), RtcError<Self::Error>>{
+        let datetime=rtcc::NaiveDateTime::try_from(*datetime).map_err(RtcError::Time)?;
+        self.set_datetime(&datetime).map_err(RtcError::Device)
+    }
+}
+
+#[cfg(feature = "embedded")]
+impl TryFrom<UtcDatetime> for rtcc::NaiveDateTime{
+    type Error=IllegalTimeError;
+
+    /// Returns YearNumberError for a year outside the range of chrono, about ±262000
+    fn try_from(dt:UtcDatetime)->Result<rtcc::NaiveDateTime, IllegalTimeError>{
+        // 日期已经校验过,只有年份可能超出chrono的范围
+        rtcc::NaiveDate::from_ymd_opt(dt.year,dt.month as u32,dt.day as u32)
+            .and_then(|date|date.and_hms_opt(dt.hour as u32,dt.minute as u32,dt.second as u32))
+            .ok_or(IllegalTimeError::YearNumberError)
+    }
+}
+
+#[cfg(feature = "embedded")]
+impl TryFrom<rtcc::NaiveDateTime> for UtcDatetime{
+    type Error=IllegalTimeError;
+
+    /// Fractions of a second are truncated, a leap second becomes 23:59:59
+    fn try_from(dt:rtcc::NaiveDateTime)->Result<UtcDatetime, IllegalTimeError>{
+        use rtcc::{Datelike, Timelike};
+        UtcDatetime::new(dt.year(),dt.month() as u8,dt.day() as u8,dt.hour() as u8,dt.minute() as u8,dt.second() as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::{IllegalTimeError, UtcDatetime};
+
+    #[test]
+    fn bcd_registers(){
+        for ts in (946_684_800..7_258_118_400i64).step_by(9_999_991){
+            let dt=UtcDatetime::UNIX_EPOCH.add_duration(crate::Duration::seconds(ts)).unwrap();
+            let registers=dt.to_bcd_registers().unwrap();
+            assert_eq!(UtcDatetime::from_bcd_registers(registers).unwrap(),dt);
+        }
+        assert_eq!(UtcDatetime::new(2150,1,1,0,0,0).unwrap().to_bcd_registers().unwrap()[5],0x81);
+        assert!(UtcDatetime::new(1999,12,31,0,0,0).unwrap().to_bcd_registers().is_err());
+        assert!(UtcDatetime::new(2200,1,1,0,0,0).unwrap().to_bcd_registers().is_err());
+        assert_eq!(UtcDatetime::from_bcd_registers([0,0,0x52,1,1,1,0]).unwrap().hour(),0);
+        assert_eq!(UtcDatetime::from_bcd_registers([0,0,0x72,1,1,1,0]).unwrap().hour(),12);
+        assert!(matches!(UtcDatetime::from_bcd_registers([0x0a,0,0,1,1,1,0]),Err(IllegalTimeError::SecondNumberError)));
+        assert!(matches!(UtcDatetime::from_bcd_registers([0,0,0x24,1,1,1,0]),Err(IllegalTimeError::HourNumberError)));
+        assert!(matches!(UtcDatetime::from_bcd_registers([0,0,0,1,0x30,2,0]),Err(IllegalTimeError::DayNumberError)));
+    }
+
+    #[test]
+    fn pcf8563_registers(){
+        for ts in (-2_208_988_800..4_102_444_800i64).step_by(9_999_991){
+            let dt=UtcDatetime::UNIX_EPOCH.add_duration(crate::Duration::seconds(ts)).unwrap();
+            let registers=dt.to_pcf8563_registers().unwrap();
+            assert_eq!(UtcDatetime::from_pcf8563_registers(registers).unwrap(),dt);
+        }
+        // 日在星期之前,与DS3231相反
+        let dt=UtcDatetime::new(1999,12,31,23,59,59).unwrap();
+        assert_eq!(dt.to_pcf8563_registers().unwrap(),[0x59,0x59,0x23,0x31,0x05,0x92,0x99]);
+        assert_eq!(UtcDatetime::from_pcf8563_registers([0x59,0x59,0x23,0x31,0x05,0x92,0x99]).unwrap(),dt);
+        assert!(UtcDatetime::new(1899,12,31,0,0,0).unwrap().to_pcf8563_registers().is_err());
+        assert!(UtcDatetime::new(2100,1,1,0,0,0).unwrap().to_pcf8563_registers().is_err());
+        assert!(matches!(UtcDatetime::from_pcf8563_registers([0,0,0,0x31,0,0x02,0x21]),Err(IllegalTimeError::DayNumberError)));
+        assert!(matches!(UtcDatetime::from_pcf8563_registers([0,0,0,1,0,0x13,0x21]),Err(IllegalTimeError::MonthNumberError)));
+        assert!(matches!(UtcDatetime::from_pcf8563_registers([0,0,0x24,1,1,1,0]),Err(IllegalTimeError::HourNumberError)));
+    }
+
+    #[cfg(feature = "embedded")]
+    #[test]
+    fn rtcc_driver(){
+        use crate::{RtcError, UtcDateTimeAccess};
+        struct Registers([u8;7]);
+        impl rtcc::DateTimeAccess for Registers{
+            type Error=();
+            fn datetime(&mut self)->Result<rtcc::NaiveDateTime,()>{
+                UtcDatetime::from_bcd_registers(self.0).and_then(TryInto::try_into).map_err(|_|())
+            }
+            fn set_datetime(&mut self,datetime:&rtcc::NaiveDateTime)->Result<(),()>{
+                self.0=UtcDatetime::try_from(*datetime).and_then(|dt|dt.to_bcd_registers()).map_err(|_|())?;
+                Ok(())
+            }
+        }
+        let mut rtc=Registers([0;7]);
+        let dt=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        rtc.set_utc_datetime(&dt).unwrap();
+        assert_eq!(rtc.0,dt.to_bcd_registers().unwrap());
+        assert_eq!(rtc.utc_datetime().unwrap(),dt);
+        assert!(matches!(rtc.set_utc_datetime(&UtcDatetime::new(1999,1,1,0,0,0).unwrap()),Err(RtcError::Device(()))));
+        assert!(matches!(rtc.set_utc_datetime(&UtcDatetime::MIN),Err(RtcError::Time(IllegalTimeError::YearNumberError))));
+        rtc.0[0]=0xff;
+        assert!(matches!(rtc.utc_datetime(),Err(RtcError::Device(()))));
+        let bce=rtcc::NaiveDate::from_ymd_opt(-44,3,15).unwrap().and_hms_opt(0,0,0).unwrap();
+        assert_eq!(rtcc::NaiveDateTime::try_from(UtcDatetime::try_from(bce).unwrap()).unwrap(),bce);
+    }
+}
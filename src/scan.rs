@@ -0,0 +1,109 @@
+//! Find datetimes embedded in larger text, such as log lines.
+//!
+//! A datetime is recognised in the form `YYYY-MM-DD HH:MM:SS`, where the date separator
+//! may also be `/` and the date and time may also be separated by `T`.
+//! It must not be directly preceded or followed by another digit.
+//! Substrings that look like a datetime but are not a valid date are skipped.
+//! # Example
+//! ```
+//! use utc_datetime::{scan, UtcDatetime};
+//! let log="[2021-11-15 09:30:00] start\n[2021-11-15T09:30:05] done";
+//! let found:Vec<_>=scan::find_iter(log).collect();
+//! assert_eq!(found.len(),2);
+//! assert_eq!(found[0].range,1..20);
+//! assert_eq!(found[1].datetime,UtcDatetime::new(2021,11,15,9,30,5).unwrap());
+//! ```
+use core::ops::Range;
+use crate::UtcDatetime;
+
+// "YYYY-MM-DD HH:MM:SS"的长度
+const LEN:usize=19;
+
+/// A datetime found by [`find_iter`] or [`find_iter_bytes`]
+#[derive(Clone,PartialEq,Debug)]
+pub struct Match{
+    /// Byte range of the datetime in the scanned text
+    pub range:Range<usize>,
+    /// The parsed datetime
+    pub datetime:UtcDatetime,
+}
+
+/// Iterator over the datetimes in a text, created by [`find_iter`] or [`find_iter_bytes`]
+#[derive(Clone,Debug)]
+pub struct FindIter<'a>{
+    bytes:&'a [u8],
+    pos:usize,
+}
+
+/// Iterate over all datetimes in `text`, in order of appearance.
+pub fn find_iter(text:&str)->FindIter<'_>{
+    find_iter_bytes(text.as_bytes())
+}
+
+/// Iterate over all datetimes in `bytes`, which need not be valid UTF-8.
+/// # Example
+/// ```
+/// use utc_datetime::scan;
+/// let line=b"\xff2020/02/02 02:02:02\xff";
+/// assert_eq!(scan::find_iter_bytes(line).next().unwrap().range,1..20);
+/// ```
+pub fn find_iter_bytes(bytes:&[u8])->FindIter<'_>{
+    FindIter{bytes,pos:0}
+}
+
+impl Iterator for FindIter<'_>{
+    type Item=Match;
+
+    fn next(&mut self)->Option<Match>{
+        while self.pos+LEN<=self.bytes.len(){
+            let start=self.pos;
+            if let Some(datetime)=match_at(self.bytes,start){
+                self.pos=start+LEN;
+                return Some(Match{range:start..start+LEN,datetime})
+            }
+            self.pos+=1;
+        }
+        self.pos=self.bytes.len();
+        None
+    }
+}
+
+// 检查从start开始的19个字节是否为合法的时间,且前后不与其他数字相连
+fn match_at(bytes:&[u8],start:usize)->Option<UtcDatetime>{
+    if start>0 && bytes[start-1].is_ascii_digit(){
+        return None
+    }
+    if bytes.get(start+LEN).is_some_and(|b|b.is_ascii_digit()){
+        return None
+    }
+    let s=&bytes[start..start+LEN];
+    let date_sep=s[4];
+    if !matches!(date_sep,b'-'|b'/') || s[7]!=date_sep || !matches!(s[10],b' '|b'T') || s[13]!=b':' || s[16]!=b':'{
+        return None
+    }
+    let number=|range:Range<usize>|->Option<i32>{
+        s[range].iter().try_fold(0,|n,b|b.is_ascii_digit().then(||n*10+(b-b'0') as i32))
+    };
+    UtcDatetime::new(
+        number(0..4)?,
+        number(5..7)? as u8,
+        number(8..10)? as u8,
+        number(11..13)? as u8,
+        number(14..16)? as u8,
+        number(17..19)? as u8,
+    ).ok()
+}
+
+#[cfg(test)]
+mod tests{
+    use super::find_iter;
+
+    #[test]
+    fn skips_invalid_and_embedded(){
+        let text="12021-11-15 09:30:00 2021-13-15 09:30:00 2021-11-15 09:30:000 2021-11-15-09:30:00 2021-02-28 23:59:59";
+        let found:Vec<_>=find_iter(text).collect();
+        assert_eq!(found.len(),1);
+        assert_eq!(&text[found[0].range.clone()],"2021-02-28 23:59:59");
+        assert_eq!(find_iter("2021-11-15 09:30").count(),0);
+    }
+}
@@ -0,0 +1,53 @@
+//! JSON Schema and OpenAPI descriptions, enabled with the `schemars` and `utoipa` features.
+//!
+//! UtcDatetime is described as `{"type":"string","format":"date-time"}`, which matches the default serde representation.
+use crate::UtcDatetime;
+
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// let schema=schemars::schema_for!(UtcDatetime);
+/// assert_eq!((schema.get("type").unwrap(),schema.get("format").unwrap()),(&"string".into(),&"date-time".into()));
+/// ```
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for UtcDatetime{
+    fn inline_schema()->bool{
+        true
+    }
+
+    fn schema_name()->std::borrow::Cow<'static,str>{
+        "UtcDatetime".into()
+    }
+
+    fn schema_id()->std::borrow::Cow<'static,str>{
+        "utc_datetime::UtcDatetime".into()
+    }
+
+    fn json_schema(_:&mut schemars::SchemaGenerator)->schemars::Schema{
+        schemars::json_schema!({
+            "type":"string",
+            "format":"date-time",
+        })
+    }
+}
+
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// use utoipa::PartialSchema;
+/// let schema=serde_json::to_value(UtcDatetime::schema()).unwrap();
+/// assert_eq!(schema,serde_json::json!({"type":"string","format":"date-time"}));
+/// ```
+#[cfg(feature = "utoipa")]
+impl utoipa::PartialSchema for UtcDatetime{
+    fn schema()->utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>{
+        use utoipa::openapi::schema::{KnownFormat, ObjectBuilder, SchemaFormat, Type};
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .format(Some(SchemaFormat::KnownFormat(KnownFormat::DateTime)))
+            .into()
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl utoipa::ToSchema for UtcDatetime{}
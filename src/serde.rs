@@ -0,0 +1,198 @@
+//! Serde support, enabled with the `serde` feature.
+//!
+//! UtcDatetime serializes as an ISO 8601 string such as `"2021-11-15T09:30:00Z"` and
+//! deserializes from any string accepted by [`UtcDatetime::from_string`].
+//! The modules below select another representation for a single field:
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Event{
+//!     at:UtcDatetime,
+//!     #[serde(with = "utc_datetime::serde::ts_seconds")]
+//!     created:UtcDatetime,
+//!     #[serde(with = "utc_datetime::serde::ts_millis")]
+//!     updated:UtcDatetime,
+//! }
+//! let dt=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+//! let json=serde_json::to_string(&Event{at:dt,created:dt,updated:dt}).unwrap();
+//! assert_eq!(json,r#"{"at":"2021-11-15T09:30:00Z","created":1636968600,"updated":1636968600000}"#);
+//! let event:Event=serde_json::from_str(&json).unwrap();
+//! assert_eq!((event.at,event.created,event.updated),(dt,dt,dt));
+//! ```
+use std::fmt;
+use ::serde::de::{self, Deserializer, Visitor};
+use ::serde::ser::Serializer;
+use ::serde::{Deserialize, Serialize};
+use crate::UtcDatetime;
+
+impl Serialize for UtcDatetime{
+    fn serialize<S:Serializer>(&self,serializer:S)->Result<S::Ok,S::Error>{
+        serializer.collect_str(&self.display_iso8601())
+    }
+}
+
+struct StringVisitor(fn(&str)->Result<UtcDatetime,crate::IllegalTimeError>);
+
+impl Visitor<'_> for StringVisitor{
+    type Value=UtcDatetime;
+
+    fn expecting(&self,f:&mut fmt::Formatter)->fmt::Result{
+        f.write_str("a datetime string")
+    }
+
+    fn visit_str<E:de::Error>(self,v:&str)->Result<UtcDatetime,E>{
+        (self.0)(v).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for UtcDatetime{
+    fn deserialize<D:Deserializer<'de>>(deserializer:D)->Result<Self,D::Error>{
+        deserializer.deserialize_str(StringVisitor(UtcDatetime::from_string))
+    }
+}
+
+/// Serialize as the number of seconds since January 1, 1970
+pub mod ts_seconds{
+    use ::serde::{de, Deserialize, Deserializer, Serializer};
+    use crate::UtcDatetime;
+
+    pub fn serialize<S:Serializer>(dt:&UtcDatetime,serializer:S)->Result<S::Ok,S::Error>{
+        serializer.serialize_i64(dt.unix_seconds())
+    }
+
+    pub fn deserialize<'de,D:Deserializer<'de>>(deserializer:D)->Result<UtcDatetime,D::Error>{
+        UtcDatetime::from_unix_seconds(i64::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// Serialize as the number of milliseconds since January 1, 1970,
+/// milliseconds are truncated when deserializing
+pub mod ts_millis{
+    use ::serde::{de, Deserialize, Deserializer, Serializer};
+    use crate::UtcDatetime;
+
+    pub fn serialize<S:Serializer>(dt:&UtcDatetime,serializer:S)->Result<S::Ok,S::Error>{
+        serializer.serialize_i64(dt.unix_seconds()*1000)
+    }
+
+    pub fn deserialize<'de,D:Deserializer<'de>>(deserializer:D)->Result<UtcDatetime,D::Error>{
+        let millis=i64::deserialize(deserializer)?;
+        UtcDatetime::from_unix_seconds(millis.div_euclid(1000)).map_err(de::Error::custom)
+    }
+}
+
+/// Serialize as `2021-11-15T09:30:00Z` and only accept that shape when deserializing,
+/// the `Z` may be omitted and `T` may be a space
+pub mod iso8601{
+    use ::serde::{Deserializer, Serializer};
+    use super::StringVisitor;
+    use crate::parse::{ParseMode, ParseOptions};
+    use crate::{IllegalTimeError, UtcDatetime};
+
+    pub fn serialize<S:Serializer>(dt:&UtcDatetime,serializer:S)->Result<S::Ok,S::Error>{
+        serializer.collect_str(&dt.display_iso8601())
+    }
+
+    fn parse(s:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let options=ParseOptions::new().mode(ParseMode::Strict).exact();
+        UtcDatetime::from_string_with(s.strip_suffix('Z').unwrap_or(s),&options)
+    }
+
+    pub fn deserialize<'de,D:Deserializer<'de>>(deserializer:D)->Result<UtcDatetime,D::Error>{
+        deserializer.deserialize_str(StringVisitor(parse))
+    }
+}
+
+/// Serialize as an ISO 8601 string, and deserialize from either seconds since 1970 or
+/// any string accepted by [`UtcDatetime::from_string`], depending on the token in the input.
+///
+/// Fractional seconds are truncated. This needs a self-describing format such as JSON.
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// #[derive(serde::Deserialize)]
+/// struct Event(#[serde(with = "utc_datetime::serde::number_or_string")] UtcDatetime);
+/// let dt=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+/// assert_eq!(serde_json::from_str::<Event>("1636968600").unwrap().0,dt);
+/// assert_eq!(serde_json::from_str::<Event>(r#""2021-11-15T09:30:00Z""#).unwrap().0,dt);
+/// ```
+pub mod number_or_string{
+    use std::fmt;
+    use ::serde::de::{self, Deserializer, Visitor};
+    use ::serde::Serializer;
+    use crate::UtcDatetime;
+
+    struct NumberOrStringVisitor;
+
+    impl Visitor<'_> for NumberOrStringVisitor{
+        type Value=UtcDatetime;
+
+        fn expecting(&self,f:&mut fmt::Formatter)->fmt::Result{
+            f.write_str("seconds since 1970 or a datetime string")
+        }
+
+        fn visit_i64<E:de::Error>(self,v:i64)->Result<UtcDatetime,E>{
+            UtcDatetime::from_unix_seconds(v).map_err(E::custom)
+        }
+
+        fn visit_u64<E:de::Error>(self,v:u64)->Result<UtcDatetime,E>{
+            let seconds=i64::try_from(v).map_err(|_|E::custom(crate::IllegalTimeError::YearNumberError))?;
+            self.visit_i64(seconds)
+        }
+
+        fn visit_f64<E:de::Error>(self,v:f64)->Result<UtcDatetime,E>{
+            if !(UtcDatetime::MIN.unix_seconds() as f64..UtcDatetime::MAX.unix_seconds() as f64+1.0).contains(&v){
+                return Err(E::custom(crate::IllegalTimeError::YearNumberError))
+            }
+            // 小数部分向下取整,-0.5是1969-12-31 23:59:59
+            self.visit_i64(v.floor() as i64)
+        }
+
+        fn visit_str<E:de::Error>(self,v:&str)->Result<UtcDatetime,E>{
+            UtcDatetime::from_string(v).map_err(E::custom)
+        }
+    }
+
+    pub fn serialize<S:Serializer>(dt:&UtcDatetime,serializer:S)->Result<S::Ok,S::Error>{
+        serializer.collect_str(&dt.display_iso8601())
+    }
+
+    pub fn deserialize<'de,D:Deserializer<'de>>(deserializer:D)->Result<UtcDatetime,D::Error>{
+        deserializer.deserialize_any(NumberOrStringVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::UtcDatetime;
+
+    #[derive(::serde::Serialize,::serde::Deserialize)]
+    struct Iso(#[serde(with = "super::iso8601")] UtcDatetime);
+
+    #[derive(::serde::Serialize,::serde::Deserialize)]
+    struct Flexible(#[serde(with = "super::number_or_string")] UtcDatetime);
+
+    #[test]
+    fn field_helpers(){
+        let dt=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        assert_eq!(serde_json::from_str::<UtcDatetime>(r#""2021年11月15日 9点30分0秒""#).unwrap(),dt);
+        assert_eq!(serde_json::to_string(&Iso(dt)).unwrap(),r#""2021-11-15T09:30:00Z""#);
+        assert_eq!(serde_json::from_str::<Iso>(r#""2021-11-15 09:30:00""#).unwrap().0,dt);
+        assert!(serde_json::from_str::<Iso>(r#""2021年11月15日 9点30分0秒""#).is_err());
+        assert!(serde_json::from_str::<UtcDatetime>("1636968600").is_err());
+        assert_eq!(serde_json::to_string(&Flexible(dt)).unwrap(),r#""2021-11-15T09:30:00Z""#);
+        assert_eq!(serde_json::from_str::<Flexible>("1636968600.9").unwrap().0,dt);
+        assert_eq!(serde_json::from_str::<Flexible>(r#""2021年11月15日 9点30分0秒""#).unwrap().0,dt);
+        let before_1970=UtcDatetime::new(1969,12,31,23,59,59).unwrap();
+        assert_eq!(serde_json::from_str::<Flexible>("-1").unwrap().0,before_1970);
+        assert_eq!(serde_json::from_str::<Flexible>("-0.5").unwrap().0,before_1970);
+        let bce=UtcDatetime::new(-44,3,15,12,0,0).unwrap();
+        assert_eq!(serde_json::to_string(&bce).unwrap(),r#""-0044-03-15T12:00:00Z""#);
+        assert_eq!(serde_json::from_str::<UtcDatetime>(&serde_json::to_string(&bce).unwrap()).unwrap(),bce);
+        assert_eq!(serde_json::from_str::<Iso>(r#""-0044-03-15T12:00:00Z""#).unwrap().0,bce);
+        for json in ["-31619087596801","18446744073709551615","-1e300","1e300","true","null",r#""x""#]{
+            assert!(serde_json::from_str::<Flexible>(json).is_err(),"{}",json);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Range queries over slices sorted by time, using binary search.
+//!
+//! The slices must be sorted in ascending order, otherwise the results are unspecified but memory safe,
+//! as with [`slice::partition_point`].
+//! # Example
+//! ```
+//! use utc_datetime::{UtcDatetime, UtcInterval};
+//! use utc_datetime::sorted::{partition_point_at, slice_between, slice_in};
+//! let hours:Vec<UtcDatetime>=(0..24).map(|h|UtcDatetime::new(2021,11,15,h,0,0).unwrap()).collect();
+//! let nine=UtcDatetime::new(2021,11,15,9,0,0).unwrap();
+//! let noon=UtcDatetime::new(2021,11,15,12,0,0).unwrap();
+//! assert_eq!(slice_between(&hours,nine,noon),&hours[9..12]);
+//! assert_eq!(slice_in(&hours,UtcInterval::new(nine,noon)).len(),3);
+//! assert_eq!(partition_point_at(&hours,UtcDatetime::new(2021,11,15,9,30,0).unwrap()),10);
+//! ```
+use crate::{UtcDatetime, UtcInterval};
+
+/// The index of the first element at or after `datetime`, or the length if there is none
+pub fn partition_point_at(sorted:&[UtcDatetime],datetime:UtcDatetime)->usize{
+    sorted.partition_point(|dt|*dt<datetime)
+}
+
+/// The elements at or after `start` and before `end`, empty if `end` is not after `start`
+pub fn slice_between(sorted:&[UtcDatetime],start:UtcDatetime,end:UtcDatetime)->&[UtcDatetime]{
+    slice_between_by_key(sorted,start,end,|dt|*dt)
+}
+
+/// The elements within `interval`, see [`slice_between`]
+pub fn slice_in(sorted:&[UtcDatetime],interval:UtcInterval)->&[UtcDatetime]{
+    slice_between(sorted,interval.start,interval.end)
+}
+
+/// Like [`slice_between`] for records sorted by the time returned by `key`
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// use utc_datetime::sorted::slice_between_by_key;
+/// let events=[(UtcDatetime::new(2021,11,15,9,0,0).unwrap(),"start"),(UtcDatetime::new(2021,11,15,10,0,0).unwrap(),"stop")];
+/// let found=slice_between_by_key(&events,UtcDatetime::new(2021,11,15,9,30,0).unwrap(),UtcDatetime::MAX,|e|e.0);
+/// assert_eq!(found,&events[1..]);
+/// ```
+pub fn slice_between_by_key<T>(sorted:&[T],start:UtcDatetime,end:UtcDatetime,mut key:impl FnMut(&T)->UtcDatetime)->&[T]{
+    let from=sorted.partition_point(|item|key(item)<start);
+    let to=from+sorted[from..].partition_point(|item|key(item)<end);
+    &sorted[from..to]
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::UtcDatetime;
+    use super::{partition_point_at, slice_between};
+
+    #[test]
+    fn bounds(){
+        let dates=[UtcDatetime::MIN,UtcDatetime::from_timestamp(60),UtcDatetime::from_timestamp(60),UtcDatetime::MAX];
+        assert_eq!(partition_point_at(&dates,UtcDatetime::from_timestamp(60)),1);
+        assert_eq!(partition_point_at(&dates,UtcDatetime::MAX),3);
+        assert_eq!(partition_point_at(&[],UtcDatetime::MAX),0);
+        assert_eq!(slice_between(&dates,UtcDatetime::from_timestamp(60),UtcDatetime::MAX),&dates[1..3]);
+        assert_eq!(slice_between(&dates,UtcDatetime::MIN,UtcDatetime::MAX).len(),3);
+        assert!(slice_between(&dates,UtcDatetime::MAX,UtcDatetime::MIN).is_empty());
+        assert!(slice_between(&dates,UtcDatetime::from_timestamp(1),UtcDatetime::from_timestamp(60)).is_empty());
+    }
+}
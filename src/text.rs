@@ -0,0 +1,856 @@
+// 固定布局的文本格式,每种格式的解析都要求整个字符串完全匹配
+use crate::display::Year;
+use crate::{IllegalTimeError, UtcDatetime};
+
+// 读取固定位置上的十进制数字
+pub(crate) fn number(bytes:&[u8],start:usize,len:usize)->Result<u32, IllegalTimeError>{
+    let digits=bytes.get(start..start+len).ok_or(IllegalTimeError::TimeStringError)?;
+    if !digits.iter().all(u8::is_ascii_digit){
+        return Err(IllegalTimeError::TimeStringError)
+    }
+    Ok(digits.iter().fold(0,|n,b|n*10+(b-b'0') as u32))
+}
+
+const MONTH_NAMES:[&str;12]=["Jan","Feb","Mar","Apr","May","Jun","Jul","Aug","Sep","Oct","Nov","Dec"];
+
+// 按weekday()的顺序,星期天是0
+const WEEKDAY_NAMES:[&str;7]=["Sunday","Monday","Tuesday","Wednesday","Thursday","Friday","Saturday"];
+
+// 英文月份缩写,不区分大小写
+fn month_from_name(name:&[u8])->Result<u8, IllegalTimeError>{
+    MONTH_NAMES.iter()
+        .position(|m|m.as_bytes().eq_ignore_ascii_case(name))
+        .map(|i|i as u8+1)
+        .ok_or(IllegalTimeError::TimeStringError)
+}
+
+// 英文星期全称或三个字母的缩写,不区分大小写
+fn weekday_from_name(name:&[u8])->Result<u8, IllegalTimeError>{
+    WEEKDAY_NAMES.iter()
+        .position(|w|w.as_bytes().eq_ignore_ascii_case(name) || w.as_bytes()[..3].eq_ignore_ascii_case(name))
+        .map(|i|i as u8)
+        .ok_or(IllegalTimeError::TimeStringError)
+}
+
+// `+HHMM`或`-HHMM`形式的时区偏移,返回秒数
+pub(crate) fn offset_seconds(bytes:&[u8])->Result<i64, IllegalTimeError>{
+    let sign=match bytes.first(){
+        Some(b'+')=>1,
+        Some(b'-')=>-1,
+        _=>return Err(IllegalTimeError::TimeStringError)
+    };
+    let (hours,minutes)=(number(bytes,1,2)?,number(bytes,3,2)?);
+    if bytes.len()!=5 || hours>23 || minutes>59{
+        return Err(IllegalTimeError::TimeStringError)
+    }
+    Ok(sign*(hours*3600+minutes*60) as i64)
+}
+
+// touch和date的`[CC]YY`年份,两位年份69到99是19YY,00到68是20YY
+fn shell_year(digits:&[u8])->Result<i32, IllegalTimeError>{
+    let year=number(digits,0,digits.len())? as i32;
+    Ok(match digits.len(){
+        2=>year+if year>=69 {1900} else {2000},
+        _=>year,
+    })
+}
+
+// 拆出touch和date末尾可选的`.ss`,返回(其余的数字,秒)
+fn shell_seconds(time_str:&str)->Result<(&[u8],u8), IllegalTimeError>{
+    let (digits,second)=match time_str.split_once('.'){
+        Some((digits,second)) if second.len()==2=>(digits,number(second.as_bytes(),0,2)? as u8),
+        Some(_)=>return Err(IllegalTimeError::TimeStringError),
+        None=>(time_str,0),
+    };
+    Ok((digits.as_bytes(),second))
+}
+
+// 跳过秒后面可选的`.数字`小数秒,小数秒被舍去
+pub(crate) fn skip_fraction(rest:&[u8])->Result<&[u8], IllegalTimeError>{
+    match rest{
+        [b'.',tail@..]=>{
+            let digits=tail.iter().take_while(|b|b.is_ascii_digit()).count();
+            if digits==0{
+                return Err(IllegalTimeError::TimeStringError)
+            }
+            Ok(&tail[digits..])
+        }
+        _=>Ok(rest)
+    }
+}
+
+// 减去时区偏移,得到UTC时间
+pub(crate) fn to_utc(local:UtcDatetime,offset:i64)->Result<UtcDatetime, IllegalTimeError>{
+    UtcDatetime::from_unix_seconds(local.unix_seconds()-offset)
+}
+
+// 删除RFC 2822的注释(可以嵌套,反斜杠转义下一个字符)、冒号两侧和逗号前的空白,逗号后补一个空白
+fn strip_comments(time_str:&str)->Result<String, IllegalTimeError>{
+    let mut cleaned=String::with_capacity(time_str.len());
+    let mut depth=0;
+    let mut after_colon=false;
+    let mut chars=time_str.chars();
+    while let Some(c)=chars.next(){
+        match c{
+            '\\' if depth>0=>{chars.next();}
+            '('=>depth+=1,
+            ')' if depth==0=>return Err(IllegalTimeError::TimeStringError),
+            ')'=>{
+                depth-=1;
+                if depth==0 && !after_colon{
+                    cleaned.push(' ');
+                }
+            }
+            _ if depth>0=>{}
+            ':'|','=>{
+                cleaned.truncate(cleaned.trim_end().len());
+                cleaned.push(c);
+                if c==','{
+                    cleaned.push(' ');
+                }
+                after_colon=c==':';
+            }
+            c if c.is_ascii_whitespace() && after_colon=>{}
+            c=>{
+                cleaned.push(c);
+                after_colon=false;
+            }
+        }
+    }
+    if depth>0{
+        return Err(IllegalTimeError::TimeStringError)
+    }
+    Ok(cleaned)
+}
+
+impl UtcDatetime{
+    /// Format as `YYYYMMDDTHHMMSSZ`, the ISO 8601 basic format without colons,
+    /// which is safe to use in filenames and object storage keys
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_compact(),"20211115T093000Z");
+    /// ```
+    pub fn to_compact(&self)->String{
+        self.display_compact().to_string()
+    }
+
+    /// Parse `YYYYMMDDTHHMMSSZ` or `YYYYMMDD_HHMMSS`.
+    ///
+    /// Returns TimeStringError if the string has any other layout.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_compact("20211115T093000Z").unwrap(),a_date);
+    /// assert_eq!(UtcDatetime::from_compact("20211115_093000").unwrap(),a_date);
+    /// assert!(UtcDatetime::from_compact("20211115T093000").is_err());
+    /// ```
+    pub fn from_compact(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let bytes=time_str.as_bytes();
+        match bytes{
+            [_,_,_,_,_,_,_,_,b'T',_,_,_,_,_,_,b'Z']|[_,_,_,_,_,_,_,_,b'_',_,_,_,_,_,_]=>{}
+            _=>return Err(IllegalTimeError::TimeStringError)
+        }
+        UtcDatetime::new(
+            number(bytes,0,4)? as i32,
+            number(bytes,4,2)? as u8,
+            number(bytes,6,2)? as u8,
+            number(bytes,9,2)? as u8,
+            number(bytes,11,2)? as u8,
+            number(bytes,13,2)? as u8,
+        )
+    }
+
+    /// Format as the `touch -t` argument `CCYYMMDDhhmm.ss`
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_touch_time(),"202111150930.00");
+    /// ```
+    pub fn to_touch_time(&self)->String{
+        format!("{}{:02}{:02}{:02}{:02}.{:02}",Year(self.year),self.month,self.day,self.hour,self.minute,self.second)
+    }
+
+    /// Parse the POSIX `touch -t` form `[[CC]YY]MMDDhhmm[.ss]`.
+    ///
+    /// Without a year `this_year` is used, a two-digit year 69 to 99 is 19YY and 00 to 68 is 20YY.
+    /// The time is read as UTC. Returns TimeStringError if the string has any other layout.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_touch_time("202111150930",1999).unwrap(),a_date);
+    /// assert_eq!(UtcDatetime::from_touch_time("2111150930.00",1999).unwrap(),a_date);
+    /// assert_eq!(UtcDatetime::from_touch_time("11150930",2021).unwrap(),a_date);
+    /// ```
+    pub fn from_touch_time(time_str:&str,this_year:i32)->Result<UtcDatetime, IllegalTimeError>{
+        let (digits,second)=shell_seconds(time_str)?;
+        let (year,rest)=match digits.len(){
+            8=>(this_year,digits),
+            10|12=>{
+                let (year,rest)=digits.split_at(digits.len()-8);
+                (shell_year(year)?,rest)
+            }
+            _=>return Err(IllegalTimeError::TimeStringError)
+        };
+        UtcDatetime::new(year,number(rest,0,2)? as u8,number(rest,2,2)? as u8,number(rest,4,2)? as u8,number(rest,6,2)? as u8,second)
+    }
+
+    /// Parse the argument of `date` when setting the clock, `MMDDhhmm[[CC]YY][.ss]` with the year last.
+    ///
+    /// The years are read as in [`UtcDatetime::from_touch_time`].
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_date_set_time("111509302021.00",1999).unwrap(),a_date);
+    /// assert_eq!(UtcDatetime::from_date_set_time("1115093021",1999).unwrap(),a_date);
+    /// ```
+    pub fn from_date_set_time(time_str:&str,this_year:i32)->Result<UtcDatetime, IllegalTimeError>{
+        let (digits,second)=shell_seconds(time_str)?;
+        let year=match digits.len(){
+            8=>this_year,
+            10|12=>shell_year(&digits[8..])?,
+            _=>return Err(IllegalTimeError::TimeStringError)
+        };
+        UtcDatetime::new(year,number(digits,0,2)? as u8,number(digits,2,2)? as u8,number(digits,4,2)? as u8,number(digits,6,2)? as u8,second)
+    }
+
+    /// Format as a SQL datetime literal `YYYY-MM-DD HH:MM:SS`, as stored in MySQL and SQLite text columns
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_sql_datetime(),"2021-11-15 09:30:00");
+    /// ```
+    pub fn to_sql_datetime(&self)->String{
+        self.to_string()
+    }
+
+    /// Parse a SQL datetime literal `YYYY-MM-DD HH:MM:SS`, optionally followed by
+    /// a fraction of 1 to 9 digits which is truncated.
+    ///
+    /// Returns TimeStringError if the string has any other layout.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_sql_datetime("2021-11-15 09:30:00").unwrap(),a_date);
+    /// assert_eq!(UtcDatetime::from_sql_datetime("2021-11-15 09:30:00.999999").unwrap(),a_date);
+    /// assert!(UtcDatetime::from_sql_datetime("2021-11-15T09:30:00").is_err());
+    /// ```
+    pub fn from_sql_datetime(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let bytes=time_str.as_bytes();
+        if bytes.len()<19 || (bytes[4],bytes[7],bytes[10],bytes[13],bytes[16])!=(b'-',b'-',b' ',b':',b':'){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        match &bytes[19..]{
+            []=>{}
+            [b'.',fraction @ ..] if (1..=9).contains(&fraction.len()) && fraction.iter().all(u8::is_ascii_digit)=>{}
+            _=>return Err(IllegalTimeError::TimeStringError)
+        }
+        UtcDatetime::new(
+            number(bytes,0,4)? as i32,
+            number(bytes,5,2)? as u8,
+            number(bytes,8,2)? as u8,
+            number(bytes,11,2)? as u8,
+            number(bytes,14,2)? as u8,
+            number(bytes,17,2)? as u8,
+        )
+    }
+
+    /// Format as in the Apache and nginx common log format, `15/Nov/2021:09:30:00 +0000`
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(format!("[{}]",a_date.to_common_log()),"[15/Nov/2021:09:30:00 +0000]");
+    /// ```
+    pub fn to_common_log(&self)->String{
+        format!("{:02}/{}/{}:{:02}:{:02}:{:02} +0000",
+            self.day,MONTH_NAMES[self.month as usize-1],Year(self.year),self.hour,self.minute,self.second)
+    }
+
+    /// Parse a common log format timestamp such as `15/Nov/2021:09:30:00 +0100`,
+    /// optionally enclosed in square brackets, and convert it to UTC.
+    ///
+    /// Returns TimeStringError if the string has any other layout.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_common_log("[15/Nov/2021:09:30:00 +0000]").unwrap(),a_date);
+    /// assert_eq!(UtcDatetime::from_common_log("15/Nov/2021:17:30:00 +0800").unwrap(),a_date);
+    /// ```
+    pub fn from_common_log(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let time_str=time_str.strip_prefix('[').and_then(|s|s.strip_suffix(']')).unwrap_or(time_str);
+        let bytes=time_str.as_bytes();
+        if bytes.len()!=26 || (bytes[2],bytes[6],bytes[11],bytes[14],bytes[17],bytes[20])!=(b'/',b'/',b':',b':',b':',b' '){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        let local=UtcDatetime::new(
+            number(bytes,7,4)? as i32,
+            month_from_name(&bytes[3..6])?,
+            number(bytes,0,2)? as u8,
+            number(bytes,12,2)? as u8,
+            number(bytes,15,2)? as u8,
+            number(bytes,18,2)? as u8,
+        )?;
+        to_utc(local,offset_seconds(&bytes[21..])?)
+    }
+
+    /// Format as Git's raw date `<seconds since 1970> +0000`, as in `git log --date=raw`
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_git_date(),"1636968600 +0000");
+    /// ```
+    pub fn to_git_date(&self)->String{
+        format!("{} +0000",self.unix_seconds())
+    }
+
+    /// Format as Git's raw date with the offset of the author or committer in seconds east of UTC.
+    ///
+    /// The seconds are the same for every offset, only the `±HHMM` suffix changes.
+    /// Returns HourNumberError if the offset is a day or more, and MinuteNumberError if it is not whole minutes.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_git_date_with_offset(8*3600).unwrap(),"1636968600 +0800");
+    /// assert_eq!(a_date.to_git_date_with_offset(-(5*3600+1800)).unwrap(),"1636968600 -0530");
+    /// ```
+    pub fn to_git_date_with_offset(&self,offset:i32)->Result<String, IllegalTimeError>{
+        let minutes=offset.unsigned_abs()/60;
+        if minutes>=24*60{
+            return Err(IllegalTimeError::HourNumberError)
+        }
+        if offset%60!=0{
+            return Err(IllegalTimeError::MinuteNumberError)
+        }
+        let sign=if offset<0 {'-'} else {'+'};
+        Ok(format!("{} {}{:02}{:02}",self.unix_seconds(),sign,minutes/60,minutes%60))
+    }
+
+    /// Parse Git's raw date such as `1580608922 +0800`, the offset does not change the instant.
+    ///
+    /// The seconds are negative before 1970, as written by [`UtcDatetime::to_git_date`].
+    /// Returns TimeStringError if the string has any other layout,
+    /// and YearNumberError if the time is outside the years -999999 to 999999.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2020,2,2,2,2,2).unwrap();
+    /// assert_eq!(UtcDatetime::from_git_date("1580608922 +0800").unwrap(),a_date);
+    /// ```
+    pub fn from_git_date(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::from_git_date_with_offset(time_str).map(|(datetime,_)|datetime)
+    }
+
+    /// Parse Git's raw date and also return the original offset in seconds east of UTC
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let (a_date,offset)=UtcDatetime::from_git_date_with_offset("1580608922 -0130").unwrap();
+    /// assert_eq!((a_date,offset),(UtcDatetime::new(2020,2,2,2,2,2).unwrap(),-5400));
+    /// assert_eq!(a_date.to_git_date_with_offset(offset).unwrap(),"1580608922 -0130");
+    /// ```
+    pub fn from_git_date_with_offset(time_str:&str)->Result<(UtcDatetime,i32), IllegalTimeError>{
+        let (seconds,offset)=time_str.split_once(' ').ok_or(IllegalTimeError::TimeStringError)?;
+        let digits=seconds.strip_prefix('-').unwrap_or(seconds);
+        if digits.is_empty() || !digits.bytes().all(|b|b.is_ascii_digit()){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        let seconds=seconds.parse().map_err(|_|IllegalTimeError::YearNumberError)?;
+        let offset=offset_seconds(offset.as_bytes())?;
+        Ok((UtcDatetime::from_unix_seconds(seconds)?,offset as i32))
+    }
+
+    /// Format as RFC 850, the obsolete HTTP date format still found in cookie `Expires` attributes,
+    /// such as `Monday, 15-Nov-21 09:30:00 GMT`.
+    ///
+    /// The year only has two digits, so YearNumberError is returned outside 1970 to 2069,
+    /// the years that [`UtcDatetime::from_rfc850`] reads back.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_rfc850().unwrap(),"Monday, 15-Nov-21 09:30:00 GMT");
+    /// assert!(UtcDatetime::new(2070,1,1,0,0,0).unwrap().to_rfc850().is_err());
+    /// ```
+    pub fn to_rfc850(&self)->Result<String, IllegalTimeError>{
+        if !(1970..=2069).contains(&self.year){
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        Ok(format!("{}, {:02}-{}-{:02} {:02}:{:02}:{:02} GMT",
+            WEEKDAY_NAMES[self.weekday() as usize],self.day,MONTH_NAMES[self.month as usize-1],
+            self.year%100,self.hour,self.minute,self.second))
+    }
+
+    /// Parse an RFC 850 date such as `Monday, 15-Nov-21 09:30:00 GMT`.
+    ///
+    /// As browsers do for cookies, this also accepts an abbreviated weekday and a four-digit year,
+    /// and a two-digit year below 70 means 20YY, otherwise 19YY.
+    /// Returns TimeStringError if the layout is wrong or the weekday does not match the date.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_rfc850("Monday, 15-Nov-21 09:30:00 GMT").unwrap(),a_date);
+    /// assert_eq!(UtcDatetime::from_rfc850("Mon, 15-Nov-2021 09:30:00 GMT").unwrap(),a_date);
+    /// assert!(UtcDatetime::from_rfc850("Tuesday, 15-Nov-21 09:30:00 GMT").is_err());
+    /// ```
+    pub fn from_rfc850(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let (weekday,rest)=time_str.split_once(", ").ok_or(IllegalTimeError::TimeStringError)?;
+        let weekday=weekday_from_name(weekday.as_bytes())?;
+        let bytes=rest.as_bytes();
+        // 两位或四位年份
+        let year_digits=match bytes.len(){
+            22=>2,
+            24=>4,
+            _=>return Err(IllegalTimeError::TimeStringError)
+        };
+        let time=&bytes[7+year_digits..];
+        if (bytes[2],bytes[6],time[0],time[3],time[6])!=(b'-',b'-',b' ',b':',b':') || &time[9..]!=b" GMT"{
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        let mut year=number(bytes,7,year_digits)?;
+        if year_digits==2{
+            year+=if year<70 {2000} else {1900};
+        }
+        let dt=UtcDatetime::new(
+            year as i32,
+            month_from_name(&bytes[3..6])?,
+            number(bytes,0,2)? as u8,
+            number(time,1,2)? as u8,
+            number(time,4,2)? as u8,
+            number(time,7,2)? as u8,
+        )?;
+        if dt.weekday()!=weekday{
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        Ok(dt)
+    }
+
+    /// Parse an RFC 3339 timestamp such as `2021-11-15T09:30:00.25+08:00` and convert it to UTC.
+    ///
+    /// The separator may also be `t` or a space and the offset `z`, fractional seconds are truncated.
+    /// Returns TimeStringError if the layout is wrong.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_rfc3339("2021-11-15T09:30:00Z").unwrap(),a_date);
+    /// assert_eq!(UtcDatetime::from_rfc3339("2021-11-15T17:30:00.999+08:00").unwrap(),a_date);
+    /// assert!(UtcDatetime::from_rfc3339("2021-11-15T09:30:00").is_err());
+    /// ```
+    pub fn from_rfc3339(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let bytes=time_str.as_bytes();
+        if bytes.len()<20 || (bytes[4],bytes[7],bytes[13],bytes[16])!=(b'-',b'-',b':',b':') || !matches!(bytes[10],b'T'|b't'|b' '){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        let rest=skip_fraction(&bytes[19..])?;
+        let offset=match rest{
+            [b'Z'|b'z']=>0,
+            [sign,_,_,b':',_,_]=>offset_seconds(&[*sign,rest[1],rest[2],rest[4],rest[5]])?,
+            _=>return Err(IllegalTimeError::TimeStringError)
+        };
+        let local=UtcDatetime::new(
+            number(bytes,0,4)? as i32,
+            number(bytes,5,2)? as u8,
+            number(bytes,8,2)? as u8,
+            number(bytes,11,2)? as u8,
+            number(bytes,14,2)? as u8,
+            number(bytes,17,2)? as u8,
+        )?;
+        to_utc(local,offset)
+    }
+
+    /// Parse ISO 8601 with a signed year, `±YYYYYY-MM-DDTHH:MM:SSZ` as written by
+    /// [`UtcDatetime::display_iso8601_expanded`], or the four-digit form `±YYYY-MM-DDTHH:MM:SSZ`.
+    ///
+    /// The sign is required, and a negative zero year such as `-000000` is not allowed.
+    /// Returns TimeStringError if the string has any other layout.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(10000,1,1,0,0,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_iso8601_expanded("+010000-01-01T00:00:00Z").unwrap(),a_date);
+    /// let bce=UtcDatetime::new(-1,1,1,0,0,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_iso8601_expanded("-000001-01-01T00:00:00Z").unwrap(),bce);
+    /// assert_eq!(UtcDatetime::from_iso8601_expanded("-0001-01-01T00:00:00Z").unwrap(),bce);
+    /// ```
+    pub fn from_iso8601_expanded(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let bytes=time_str.as_bytes();
+        let year_digits=match bytes.len(){
+            21=>4,
+            23=>6,
+            _=>return Err(IllegalTimeError::TimeStringError)
+        };
+        let (sign,rest)=bytes.split_at(1);
+        let (year,rest)=rest.split_at(year_digits);
+        if (rest[0],rest[3],rest[6],rest[9],rest[12],rest[15])!=(b'-',b'-',b'T',b':',b':',b'Z'){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        let year=number(year,0,year_digits)? as i32;
+        let year=match sign{
+            b"+"=>year,
+            b"-" if year!=0=>-year,
+            _=>return Err(IllegalTimeError::TimeStringError)
+        };
+        UtcDatetime::new(
+            year,
+            number(rest,1,2)? as u8,
+            number(rest,4,2)? as u8,
+            number(rest,7,2)? as u8,
+            number(rest,10,2)? as u8,
+            number(rest,13,2)? as u8,
+        )
+    }
+
+    /// Format as RFC 2822, the date format of email and HTTP headers, such as `Mon, 15 Nov 2021 09:30:00 +0000`
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_rfc2822(),"Mon, 15 Nov 2021 09:30:00 +0000");
+    /// ```
+    pub fn to_rfc2822(&self)->String{
+        format!("{}, {:02} {} {} {:02}:{:02}:{:02} +0000",
+            &WEEKDAY_NAMES[self.weekday() as usize][..3],self.day,MONTH_NAMES[self.month as usize-1],
+            Year(self.year),self.hour,self.minute,self.second)
+    }
+
+    /// Parse an RFC 2822 date such as `Mon, 15 Nov 2021 17:30:00 +0800` and convert it to UTC.
+    ///
+    /// The weekday and the seconds are optional, and the obsolete forms found in mail archives are accepted:
+    /// two-digit years (below 50 means 20YY) and three-digit years (1900 is added), the zones `UT`,
+    /// `GMT` and the US zones such as `EST` in any case, comments such as `(UTC)` anywhere,
+    /// and whitespace around the colons and before the comma.
+    /// Military and other alphabetic zones are read as `+0000`, as RFC 2822 recommends.
+    /// Returns TimeStringError if the layout is wrong or the weekday does not match the date.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(UtcDatetime::from_rfc2822("Mon, 15 Nov 2021 17:30:00 +0800").unwrap(),a_date);
+    /// assert_eq!(UtcDatetime::from_rfc2822("15 Nov 21 04:30 EST").unwrap(),a_date);
+    /// assert_eq!(UtcDatetime::from_rfc2822("Mon (Monday) , 15 Nov 121 09 : 30 : 00 (a comment) cet").unwrap(),a_date);
+    /// ```
+    pub fn from_rfc2822(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let cleaned=strip_comments(time_str)?;
+        let mut tokens=cleaned.split_ascii_whitespace().peekable();
+        let weekday=match tokens.peek(){
+            Some(token) if token.ends_with(',')=>{
+                let weekday=weekday_from_name(&token.as_bytes()[..token.len()-1])?;
+                tokens.next();
+                Some(weekday)
+            }
+            _=>None
+        };
+        let mut next=||tokens.next().map(str::as_bytes).ok_or(IllegalTimeError::TimeStringError);
+        let day=match next()?{
+            day@([_]|[_,_])=>number(day,0,day.len())?,
+            _=>return Err(IllegalTimeError::TimeStringError)
+        };
+        let month=month_from_name(next()?)?;
+        let year=match next()?{
+            year@[_,_]=>{
+                let year=number(year,0,2)?;
+                year+if year<50 {2000} else {1900}
+            }
+            year@[_,_,_]=>number(year,0,3)?+1900,
+            year@([_,_,_,_]|[_,_,_,_,_])=>number(year,0,year.len())?,
+            _=>return Err(IllegalTimeError::TimeStringError)
+        };
+        let time=next()?;
+        let second=match time{
+            [_,_,b':',_,_]=>0,
+            [_,_,b':',_,_,b':',_,_]=>number(time,6,2)?,
+            _=>return Err(IllegalTimeError::TimeStringError)
+        };
+        let zone=next()?.to_ascii_uppercase();
+        let offset=match zone.as_slice(){
+            b"UT"|b"GMT"|b"Z"=>0,
+            b"EDT"=>-4*3600,
+            b"EST"|b"CDT"=>-5*3600,
+            b"CST"|b"MDT"=>-6*3600,
+            b"MST"|b"PDT"=>-7*3600,
+            b"PST"=>-8*3600,
+            // 军事时区和其他字母时区的含义不可靠,按+0000处理
+            zone if (1..=5).contains(&zone.len()) && zone.iter().all(u8::is_ascii_alphabetic)=>0,
+            zone=>offset_seconds(zone)?,
+        };
+        if tokens.next().is_some(){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        let local=UtcDatetime::new(
+            i32::try_from(year).map_err(|_|IllegalTimeError::YearNumberError)?,
+            month,
+            day as u8,
+            number(time,0,2)? as u8,
+            number(time,3,2)? as u8,
+            second as u8,
+        )?;
+        if weekday.is_some_and(|weekday|weekday!=local.weekday()){
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        to_utc(local,offset)
+    }
+
+    /// Format as an ASN.1 UTCTime `YYMMDDHHMMSSZ`, as in X.509 certificate validity.
+    ///
+    /// Returns YearNumberError before 1950 and after 2049, when RFC 5280 requires GeneralizedTime instead.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+    /// assert_eq!(a_date.to_asn1_utc_time().unwrap(),"211115093000Z");
+    /// ```
+    pub fn to_asn1_utc_time(&self)->Result<String, IllegalTimeError>{
+        if !(1950..=2049).contains(&self.year){
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        Ok(format!("{:02}{:02}{:02}{:02}{:02}{:02}Z",self.year%100,self.month,self.day,self.hour,self.minute,self.second))
+    }
+
+    /// Parse an ASN.1 UTCTime `YYMMDDHHMMSSZ` with the RFC 5280 pivot: 50 to 99 are 19YY, 00 to 49 are 20YY.
+    ///
+    /// Returns TimeStringError for any other layout.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// assert_eq!(UtcDatetime::from_asn1_utc_time("491231235959Z").unwrap().year(),2049);
+    /// assert_eq!(UtcDatetime::from_asn1_utc_time("500101000000Z").unwrap().year(),1950);
+    /// ```
+    pub fn from_asn1_utc_time(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let bytes=time_str.as_bytes();
+        if bytes.len()!=13 || bytes[12]!=b'Z'{
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        let year=number(bytes,0,2)?;
+        UtcDatetime::new(
+            (year+if year<50 {2000} else {1900}) as i32,
+            number(bytes,2,2)? as u8,
+            number(bytes,4,2)? as u8,
+            number(bytes,6,2)? as u8,
+            number(bytes,8,2)? as u8,
+            number(bytes,10,2)? as u8,
+        )
+    }
+
+    /// Format as an ASN.1 GeneralizedTime `YYYYMMDDHHMMSSZ`.
+    ///
+    /// Returns YearNumberError before 0 and after 9999.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::new(2050,1,1,0,0,0).unwrap();
+    /// assert_eq!(a_date.to_asn1_generalized_time().unwrap(),"20500101000000Z");
+    /// ```
+    pub fn to_asn1_generalized_time(&self)->Result<String, IllegalTimeError>{
+        if !(0..=9999).contains(&self.year){
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        Ok(format!("{:04}{:02}{:02}{:02}{:02}{:02}Z",self.year,self.month,self.day,self.hour,self.minute,self.second))
+    }
+
+    /// Parse an ASN.1 GeneralizedTime `YYYYMMDDHHMMSSZ`, without fractional seconds as RFC 5280 requires.
+    ///
+    /// Returns TimeStringError if the string has any other layout.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let a_date=UtcDatetime::from_asn1_generalized_time("99991231235959Z").unwrap();
+    /// assert_eq!(a_date,UtcDatetime::new(9999,12,31,23,59,59).unwrap());
+    /// ```
+    pub fn from_asn1_generalized_time(time_str:&str)->Result<UtcDatetime, IllegalTimeError>{
+        let bytes=time_str.as_bytes();
+        if bytes.len()!=15 || bytes[14]!=b'Z'{
+            return Err(IllegalTimeError::TimeStringError)
+        }
+        UtcDatetime::new(
+            number(bytes,0,4)? as i32,
+            number(bytes,4,2)? as u8,
+            number(bytes,6,2)? as u8,
+            number(bytes,8,2)? as u8,
+            number(bytes,10,2)? as u8,
+            number(bytes,12,2)? as u8,
+        )
+    }
+
+    /// Format an X.509 validity time as RFC 5280 requires: UTCTime from 1950 through 2049, GeneralizedTime otherwise.
+    ///
+    /// Returns YearNumberError before 0 and after 9999.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// assert_eq!(UtcDatetime::new(2049,12,31,23,59,59).unwrap().to_x509_time().unwrap(),"491231235959Z");
+    /// assert_eq!(UtcDatetime::new(2050,1,1,0,0,0).unwrap().to_x509_time().unwrap(),"20500101000000Z");
+    /// ```
+    pub fn to_x509_time(&self)->Result<String, IllegalTimeError>{
+        if (1950..2050).contains(&self.year) {self.to_asn1_utc_time()} else {self.to_asn1_generalized_time()}
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::{IllegalTimeError, UtcDatetime};
+
+    #[test]
+    fn compact_round_trip(){
+        for dt in [UtcDatetime::new(0,1,1,0,0,0).unwrap(),UtcDatetime::new(9999,12,31,23,59,59).unwrap()]{
+            assert_eq!(UtcDatetime::from_compact(&dt.to_compact()).unwrap(),dt);
+        }
+        assert_eq!(UtcDatetime::new(-44,3,15,12,0,0).unwrap().to_compact(),"-00440315T120000Z");
+        assert!(UtcDatetime::from_compact("2021111_5093000Z").is_err());
+        assert!(UtcDatetime::from_compact("20211115T0930+0Z").is_err());
+        assert!(UtcDatetime::from_compact("20211315_093000").is_err());
+    }
+
+    #[test]
+    fn touch_time(){
+        let dt=UtcDatetime::new(2021,11,15,9,30,59).unwrap();
+        assert_eq!(UtcDatetime::from_touch_time(&dt.to_touch_time(),1999).unwrap(),dt);
+        assert_eq!(UtcDatetime::from_touch_time("7001010000",2021).unwrap(),UtcDatetime::UNIX_EPOCH);
+        assert_eq!(UtcDatetime::from_touch_time("000012310123.59",2021).unwrap(),UtcDatetime::new(0,12,31,1,23,59).unwrap());
+        assert_eq!(UtcDatetime::from_touch_time("6812312359.59",2021).unwrap(),UtcDatetime::new(2068,12,31,23,59,59).unwrap());
+        assert_eq!(UtcDatetime::from_date_set_time("0101000070",2021).unwrap(),UtcDatetime::UNIX_EPOCH);
+        for s in ["1115093","2021111509300","111509.30","11150930.5","11150930.","11150930.0a","+1115093"]{
+            assert!(matches!(UtcDatetime::from_touch_time(s,2021),Err(IllegalTimeError::TimeStringError)),"{}",s);
+            assert!(matches!(UtcDatetime::from_date_set_time(s,2021),Err(IllegalTimeError::TimeStringError)),"{}",s);
+        }
+        assert!(matches!(UtcDatetime::from_touch_time("13150930",2021),Err(IllegalTimeError::MonthNumberError)));
+        assert!(UtcDatetime::from_touch_time("11150930.60",2021).is_err());
+    }
+
+    #[test]
+    fn sql_datetime(){
+        let dt=UtcDatetime::new(2021,11,15,9,30,59).unwrap();
+        assert_eq!(UtcDatetime::from_sql_datetime(&dt.to_sql_datetime()).unwrap(),dt);
+        assert_eq!(UtcDatetime::from_sql_datetime("2021-11-15 09:30:59.5").unwrap(),dt);
+        assert_eq!(UtcDatetime::from_sql_datetime("2021-11-15 09:30:59.123456789").unwrap(),dt);
+        for s in ["2021-11-15 09:30:59.","2021-11-15 09:30:59.1234567890","2021-11-15 09:30:59Z","2021-11-15 9:30:59","2021-11-15"]{
+            assert!(UtcDatetime::from_sql_datetime(s).is_err(),"{}",s);
+        }
+    }
+
+    #[test]
+    fn common_log(){
+        let dt=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        assert_eq!(UtcDatetime::from_common_log(&dt.to_common_log()).unwrap(),dt);
+        assert_eq!(UtcDatetime::from_common_log("[15/nov/2021:04:00:00 -0530]").unwrap(),dt);
+        assert_eq!(UtcDatetime::from_common_log("01/Jan/1970:08:00:00 +0800").unwrap(),UtcDatetime::UNIX_EPOCH);
+        assert_eq!(UtcDatetime::from_common_log("01/Jan/0000:07:59:59 +0800").unwrap(),UtcDatetime::new(-1,12,31,23,59,59).unwrap());
+        for s in ["[15/Nov/2021:09:30:00 +0000","15/Nov/2021:09:30:00","15/Nov/2021:09:30:00 +2400","15/Now/2021:09:30:00 +0000","15-Nov-2021:09:30:00 +0000"]{
+            assert!(UtcDatetime::from_common_log(s).is_err(),"{}",s);
+        }
+    }
+
+    #[test]
+    fn git_date(){
+        let before_epoch=UtcDatetime::new(1969,12,31,23,59,59).unwrap();
+        for dt in [UtcDatetime::UNIX_EPOCH,UtcDatetime::MAX,before_epoch,UtcDatetime::MIN]{
+            assert_eq!(UtcDatetime::from_git_date(&dt.to_git_date()).unwrap(),dt);
+        }
+        assert_eq!(before_epoch.to_git_date_with_offset(3600).unwrap(),"-1 +0100");
+        assert_eq!(UtcDatetime::from_git_date_with_offset("-1 +0100").unwrap(),(before_epoch,3600));
+        assert!(matches!(UtcDatetime::from_git_date("-31619087596801 +0000"),Err(IllegalTimeError::YearNumberError)));
+        let epoch=UtcDatetime::UNIX_EPOCH;
+        assert_eq!(epoch.to_git_date_with_offset(-59*60).unwrap(),"0 -0059");
+        assert_eq!(UtcDatetime::from_git_date_with_offset("0 -2359").unwrap(),(epoch,-86340));
+        assert!(matches!(epoch.to_git_date_with_offset(86400),Err(IllegalTimeError::HourNumberError)));
+        assert!(matches!(epoch.to_git_date_with_offset(-30),Err(IllegalTimeError::MinuteNumberError)));
+        assert!(matches!(UtcDatetime::from_git_date("99999999999999999999 +0000"),Err(IllegalTimeError::YearNumberError)));
+        for s in ["1580608922","1580608922 0800","1580608922  +0800","+1580608922 +0800","- +0000","--1 +0000"," +0000","1580608922 +08:00"]{
+            assert!(matches!(UtcDatetime::from_git_date(s),Err(IllegalTimeError::TimeStringError)),"{}",s);
+        }
+    }
+
+    #[test]
+    fn rfc850(){
+        let dt=UtcDatetime::new(2069,12,31,23,59,59).unwrap();
+        assert_eq!(dt.to_rfc850().unwrap(),"Tuesday, 31-Dec-69 23:59:59 GMT");
+        assert_eq!(UtcDatetime::from_rfc850(&dt.to_rfc850().unwrap()).unwrap(),dt);
+        assert_eq!(UtcDatetime::from_rfc850(&UtcDatetime::UNIX_EPOCH.to_rfc850().unwrap()).unwrap(),UtcDatetime::UNIX_EPOCH);
+        for year in [-44,-1,1969,2070]{
+            let dt=UtcDatetime::new(year,3,15,0,0,0).unwrap();
+            assert!(matches!(dt.to_rfc850(),Err(IllegalTimeError::YearNumberError)),"{}",year);
+        }
+        assert_eq!(UtcDatetime::from_rfc850("thursday, 01-jan-70 00:00:00 GMT").unwrap(),UtcDatetime::UNIX_EPOCH);
+        for s in ["Monday 15-Nov-21 09:30:00 GMT","Monday, 15-Nov-21 09:30:00 UTC","Monday, 15-Nov-021 09:30:00 GMT","Mo, 15-Nov-21 09:30:00 GMT"]{
+            assert!(UtcDatetime::from_rfc850(s).is_err(),"{}",s);
+        }
+    }
+
+    #[test]
+    fn rfc3339(){
+        let dt=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        assert_eq!(UtcDatetime::from_rfc3339(&format!("{:#}",dt)).unwrap(),dt);
+        assert_eq!(UtcDatetime::from_rfc3339("2021-11-15 09:30:00.5z").unwrap(),dt);
+        assert_eq!(UtcDatetime::from_rfc3339("2021-11-15t04:00:00-05:30").unwrap(),dt);
+        for s in ["2021-11-15T09:30:00.Z","2021-11-15T09:30:00+0800","2021-11-15_09:30:00Z","2021-11-15T09:30:00Z "]{
+            assert!(UtcDatetime::from_rfc3339(s).is_err(),"{}",s);
+        }
+    }
+
+    #[test]
+    fn iso8601_expanded(){
+        for dt in [UtcDatetime::UNIX_EPOCH,UtcDatetime::new(9999,12,31,23,59,59).unwrap(),UtcDatetime::new(65535,12,31,23,59,59).unwrap()]{
+            assert_eq!(UtcDatetime::from_iso8601_expanded(&dt.display_iso8601_expanded().to_string()).unwrap(),dt);
+        }
+        assert_eq!(UtcDatetime::MAX.display_iso8601_expanded().to_string(),"+999999-12-31T23:59:59Z");
+        assert_eq!(UtcDatetime::from_iso8601_expanded("+001969-12-31T23:59:59Z").unwrap().year(),1969);
+        for (dt,s) in [((-1,1,1),"-000001-01-01T00:00:00Z"),((-44,3,15),"-000044-03-15T00:00:00Z"),((0,2,29),"+000000-02-29T00:00:00Z"),((-999_999,1,1),"-999999-01-01T00:00:00Z")]{
+            let dt=UtcDatetime::new(dt.0,dt.1,dt.2,0,0,0).unwrap();
+            assert_eq!(dt.display_iso8601_expanded().to_string(),s);
+            assert_eq!(UtcDatetime::from_iso8601_expanded(s).unwrap(),dt);
+        }
+        // 四位数的带符号年份
+        assert_eq!(UtcDatetime::from_iso8601_expanded("-0001-12-31T23:59:59Z").unwrap(),UtcDatetime::new(-1,12,31,23,59,59).unwrap());
+        assert_eq!(UtcDatetime::from_iso8601_expanded("+2021-11-15T09:30:00Z").unwrap(),UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+        assert_eq!(UtcDatetime::from_iso8601_expanded("+0000-01-01T00:00:00Z").unwrap(),UtcDatetime::new(0,1,1,0,0,0).unwrap());
+        for s in ["-000000-01-01T00:00:00Z","-0000-01-01T00:00:00Z","002021-11-15T09:30:00Z","2021-11-15T09:30:00Z","+02021-11-15T09:30:00Z","+002021-11-15T09:30:00+00:00","+002021-11-15 09:30:00Z"," 002021-11-15T09:30:00Z","+-00001-01-01T00:00:00Z","-+0001-01-01T00:00:00Z"]{
+            assert!(matches!(UtcDatetime::from_iso8601_expanded(s),Err(IllegalTimeError::TimeStringError)),"{}",s);
+        }
+    }
+
+    #[test]
+    fn rfc2822(){
+        let dt=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        assert_eq!(UtcDatetime::from_rfc2822(&dt.to_rfc2822()).unwrap(),dt);
+        assert_eq!(UtcDatetime::from_rfc2822("Mon,  15 Nov 2021 09:30:00 GMT (Coordinated Universal Time)").unwrap(),dt);
+        assert_eq!(UtcDatetime::from_rfc2822("5 Nov 99 01:30 -0800").unwrap(),UtcDatetime::new(1999,11,5,9,30,0).unwrap());
+        assert_eq!(UtcDatetime::from_rfc2822("15 Nov 2021 09:(hours)30:00 z").unwrap(),dt);
+        assert_eq!(UtcDatetime::from_rfc2822("(sent) Mon,15 Nov 2021 09:30:00 +0000 (UTC (nested \\) quoted))").unwrap(),dt);
+        assert_eq!(UtcDatetime::from_rfc2822("15 Nov 2021 04:30:00 est").unwrap(),dt);
+        assert_eq!(UtcDatetime::from_rfc2822("15 Nov 2021 09:30:00 A").unwrap(),dt);
+        for s in ["Tue, 15 Nov 2021 09:30:00 +0000","Mon 15 Nov 2021 09:30:00 +0000","15 Nov 2021 09:30:00","15 Nov 2021 9:30:00 +0000",
+            "15 Nov 2021 09:30:00 +0000 extra","15 Nov 2021 09:30:00 +0000 (open","15 Nov 2021 09:30:00 +0000)","15 Nov 2021 09:30:00 SIXLTR","15 Nov 2021 09:30:00 X1"]{
+            assert!(UtcDatetime::from_rfc2822(s).is_err(),"{}",s);
+        }
+    }
+
+    #[test]
+    fn asn1(){
+        for dt in [UtcDatetime::new(1950,1,1,0,0,0).unwrap(),UtcDatetime::new(2049,12,31,23,59,59).unwrap()]{
+            assert_eq!(UtcDatetime::from_asn1_utc_time(&dt.to_asn1_utc_time().unwrap()).unwrap(),dt);
+            assert_eq!(UtcDatetime::from_asn1_generalized_time(&dt.to_asn1_generalized_time().unwrap()).unwrap(),dt);
+        }
+        assert!(UtcDatetime::new(2050,1,1,0,0,0).unwrap().to_asn1_utc_time().is_err());
+        assert!(UtcDatetime::new(10000,1,1,0,0,0).unwrap().to_x509_time().is_err());
+        assert!(UtcDatetime::new(1949,12,31,0,0,0).unwrap().to_asn1_utc_time().is_err());
+        assert_eq!(UtcDatetime::new(1949,12,31,0,0,0).unwrap().to_x509_time().unwrap(),"19491231000000Z");
+        assert!(UtcDatetime::new(-1,1,1,0,0,0).unwrap().to_asn1_generalized_time().is_err());
+        for s in ["2111150930Z","211115093000","211115093000+0000","2111150930000Z"]{
+            assert!(UtcDatetime::from_asn1_utc_time(s).is_err(),"{}",s);
+        }
+        for s in ["20211115093000.5Z","20211115093000","211115093000Z","20211315093000Z"]{
+            assert!(UtcDatetime::from_asn1_generalized_time(s).is_err(),"{}",s);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{days_of_the_month, IllegalTimeError, UtcDatetime};
+use libc::c_int;
+
+impl UtcDatetime{
+    /// Convert to the broken-down time `libc::tm`, as filled in by `gmtime_r`.
+    ///
+    /// `tm_wday` and `tm_yday` are computed, `tm_isdst` is always 0.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let tm=UtcDatetime::new(2021,11,15,9,30,0).unwrap().to_tm();
+    /// assert_eq!((tm.tm_year,tm.tm_mon,tm.tm_mday),(121,10,15));
+    /// assert_eq!((tm.tm_wday,tm.tm_yday),(1,318));
+    /// ```
+    pub fn to_tm(&self)->libc::tm{
+        // tm在部分平台上还有tm_gmtoff,tm_zone等字段,先全部置零
+        let mut tm:libc::tm=unsafe{core::mem::zeroed()};
+        tm.tm_sec=self.second as c_int;
+        tm.tm_min=self.minute as c_int;
+        tm.tm_hour=self.hour as c_int;
+        tm.tm_mday=self.day as c_int;
+        // tm_mon从0开始,tm_year从1900开始
+        tm.tm_mon=self.month as c_int-1;
+        tm.tm_year=self.year as c_int-1900;
+        tm.tm_wday=self.weekday() as c_int;
+        let mut yday=self.day as c_int-1;
+        for i in 1..self.month{
+            yday+=days_of_the_month(self.year,i) as c_int;
+        }
+        tm.tm_yday=yday;
+        tm.tm_isdst=0;
+        tm
+    }
+
+    /// Create a UtcDatetime from a broken-down UTC time `libc::tm`.
+    ///
+    /// Only the year, month, day, hour, minute and second fields are read,
+    /// and they must already be normalized.
+    /// # Example
+    /// ```
+    /// use utc_datetime::UtcDatetime;
+    /// let dt=UtcDatetime::new(2020,2,29,23,59,59).unwrap();
+    /// assert_eq!(UtcDatetime::from_tm(&dt.to_tm()).unwrap(),dt);
+    /// ```
+    pub fn from_tm(tm:&libc::tm)->Result<UtcDatetime, IllegalTimeError>{
+        let year=i32::try_from(tm.tm_year as i64+1900).map_err(|_|IllegalTimeError::YearNumberError)?;
+        let month=u8::try_from(tm.tm_mon as i64+1).map_err(|_|IllegalTimeError::MonthNumberError)?;
+        let day=u8::try_from(tm.tm_mday).map_err(|_|IllegalTimeError::DayNumberError)?;
+        let hour=u8::try_from(tm.tm_hour).map_err(|_|IllegalTimeError::HourNumberError)?;
+        let minute=u8::try_from(tm.tm_min).map_err(|_|IllegalTimeError::MinuteNumberError)?;
+        let second=u8::try_from(tm.tm_sec).map_err(|_|IllegalTimeError::SecondNumberError)?;
+        UtcDatetime::new(year,month,day,hour,minute,second)
+    }
+}
+
+#[cfg(all(test,unix))]
+mod tests{
+    use crate::UtcDatetime;
+
+    #[test]
+    fn matches_gmtime(){
+        let dt=UtcDatetime::new(2024,12,31,23,59,58).unwrap();
+        let t=dt.timestamp().unwrap() as libc::time_t;
+        let mut expected:libc::tm=unsafe{core::mem::zeroed()};
+        unsafe{libc::gmtime_r(&t,&mut expected)};
+        let tm=dt.to_tm();
+        assert_eq!(
+            (tm.tm_year,tm.tm_mon,tm.tm_mday,tm.tm_hour,tm.tm_min,tm.tm_sec,tm.tm_wday,tm.tm_yday),
+            (expected.tm_year,expected.tm_mon,expected.tm_mday,expected.tm_hour,expected.tm_min,expected.tm_sec,expected.tm_wday,expected.tm_yday)
+        );
+        assert_eq!(UtcDatetime::from_tm(&expected).unwrap(),dt);
+    }
+}
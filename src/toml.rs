@@ -0,0 +1,104 @@
+//! Conversions with TOML datetimes, enabled with the `toml` feature.
+//!
+//! An offset date-time is converted to UTC, a local date-time is read as UTC and a local date as midnight UTC.
+//! With the `serde` feature [`serialize`] and [`deserialize`] let a field be written as a native TOML datetime.
+//! # Example
+//! ```
+//! use utc_datetime::UtcDatetime;
+//! use toml_datetime::Datetime;
+//! let a_date=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+//! let literal:Datetime="2021-11-15T17:30:00+08:00".parse().unwrap();
+//! assert_eq!(UtcDatetime::try_from(literal).unwrap(),a_date);
+//! assert_eq!(Datetime::try_from(a_date).unwrap().to_string(),"2021-11-15T09:30:00Z");
+//! ```
+use toml_datetime::{Date, Datetime, Offset, Time};
+use crate::{IllegalTimeError, UtcDatetime};
+#[cfg(feature = "serde")]
+use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Convert to an offset date-time with the offset `Z`.
+///
+/// TOML years have four digits, returns YearNumberError for years before 0 or after 9999.
+impl TryFrom<UtcDatetime> for Datetime{
+    type Error=IllegalTimeError;
+
+    fn try_from(dt:UtcDatetime)->Result<Datetime, IllegalTimeError>{
+        if !(0..=9999).contains(&dt.year){
+            return Err(IllegalTimeError::YearNumberError)
+        }
+        Ok(Datetime{
+            date:Some(Date{year:dt.year as u16,month:dt.month,day:dt.day}),
+            time:Some(Time{hour:dt.hour,minute:dt.minute,second:dt.second,nanosecond:0}),
+            offset:Some(Offset::Z),
+        })
+    }
+}
+
+/// Convert a TOML datetime, truncating fractions of a second.
+///
+/// Returns TimeStringError for a local time without a date.
+impl TryFrom<Datetime> for UtcDatetime{
+    type Error=IllegalTimeError;
+
+    fn try_from(datetime:Datetime)->Result<UtcDatetime, IllegalTimeError>{
+        let date=datetime.date.ok_or(IllegalTimeError::TimeStringError)?;
+        let time=datetime.time.unwrap_or(Time{hour:0,minute:0,second:0,nanosecond:0});
+        let local=UtcDatetime::new(date.year as i32,date.month,date.day,time.hour,time.minute,time.second)?;
+        let offset=match datetime.offset{
+            Some(Offset::Custom{minutes})=>minutes as i64*60,
+            Some(Offset::Z)|None=>0,
+        };
+        UtcDatetime::from_unix_seconds(local.unix_seconds()-offset)
+    }
+}
+
+/// Serialize as a native TOML datetime, for `#[serde(with = "utc_datetime::toml")]`
+/// # Example
+/// ```
+/// use utc_datetime::UtcDatetime;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Config{
+///     #[serde(with = "utc_datetime::toml")]
+///     start:UtcDatetime,
+/// }
+/// let config:Config=toml::from_str("start = 2021-11-15T09:30:00Z").unwrap();
+/// assert_eq!(config.start,UtcDatetime::new(2021,11,15,9,30,0).unwrap());
+/// assert_eq!(toml::to_string(&config).unwrap(),"start = 2021-11-15T09:30:00Z\n");
+/// ```
+#[cfg(feature = "serde")]
+pub fn serialize<S:Serializer>(dt:&UtcDatetime,serializer:S)->Result<S::Ok,S::Error>{
+    Datetime::try_from(*dt).map_err(::serde::ser::Error::custom)?.serialize(serializer)
+}
+
+/// Deserialize from a native TOML datetime, see [`serialize`]
+#[cfg(feature = "serde")]
+pub fn deserialize<'de,D:Deserializer<'de>>(deserializer:D)->Result<UtcDatetime,D::Error>{
+    UtcDatetime::try_from(Datetime::deserialize(deserializer)?).map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests{
+    use toml_datetime::Datetime;
+    use crate::{IllegalTimeError, UtcDatetime};
+
+    fn parse(literal:&str)->Result<UtcDatetime, IllegalTimeError>{
+        UtcDatetime::try_from(literal.parse::<Datetime>().unwrap())
+    }
+
+    #[test]
+    fn literals(){
+        for dt in [UtcDatetime::new(0,1,1,0,0,0).unwrap(),UtcDatetime::UNIX_EPOCH,UtcDatetime::new(9999,12,31,23,59,59).unwrap()]{
+            assert_eq!(UtcDatetime::try_from(Datetime::try_from(dt).unwrap()).unwrap(),dt);
+        }
+        for dt in [UtcDatetime::new(-1,12,31,23,59,59).unwrap(),UtcDatetime::new(10000,1,1,0,0,0).unwrap()]{
+            assert!(matches!(Datetime::try_from(dt),Err(IllegalTimeError::YearNumberError)));
+        }
+        let dt=UtcDatetime::new(2021,11,15,9,30,0).unwrap();
+        assert_eq!(parse("2021-11-15T09:30:00.999").unwrap(),dt);
+        assert_eq!(parse("2021-11-15 04:00:00-05:30").unwrap(),dt);
+        assert_eq!(parse("2021-11-15").unwrap(),UtcDatetime::new(2021,11,15,0,0,0).unwrap());
+        assert!(matches!(parse("09:30:00"),Err(IllegalTimeError::TimeStringError)));
+        assert_eq!(parse("1970-01-01T00:00:00+00:01").unwrap(),UtcDatetime::new(1969,12,31,23,59,0).unwrap());
+        assert_eq!(parse("0000-01-01T00:00:00+00:01").unwrap(),UtcDatetime::new(-1,12,31,23,59,0).unwrap());
+    }
+}
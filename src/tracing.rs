@@ -0,0 +1,82 @@
+//! A [`tracing-subscriber`](tracing_subscriber) timer, enabled with the `tracing` feature.
+//!
+//! [`UtcTimer`] prints the time of each event in ISO 8601 by default, or with a [`FormatDescription`].
+//! # Example
+//! ```
+//! use utc_datetime::format::FormatDescription;
+//! use utc_datetime::tracing::UtcTimer;
+//! let subscriber=tracing_subscriber::fmt().with_timer(UtcTimer::new()).finish();
+//! let custom=UtcTimer::with_format(FormatDescription::new("%Y-%m-%d %H:%M:%S").unwrap());
+//! ```
+use std::fmt;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+use crate::clock::{Clock, SystemClock};
+use crate::format::FormatDescription;
+
+/// Formats the time of tracing events from a [`Clock`]
+#[derive(Clone,Debug,Default)]
+pub struct UtcTimer<C=SystemClock>{
+    clock:C,
+    description:Option<FormatDescription<'static>>,
+}
+
+impl UtcTimer{
+    /// A timer that reads the [`SystemClock`] and prints in ISO 8601
+    pub fn new()->UtcTimer{
+        UtcTimer::default()
+    }
+
+    /// A timer that reads the [`SystemClock`] and prints with a format description
+    pub fn with_format(description:FormatDescription<'static>)->UtcTimer{
+        UtcTimer{clock:SystemClock,description:Some(description)}
+    }
+}
+
+impl<C:Clock> UtcTimer<C>{
+    /// A timer that reads another clock and prints in ISO 8601
+    pub fn with_clock(clock:C)->UtcTimer<C>{
+        UtcTimer{clock,description:None}
+    }
+
+    /// Print with a format description instead of ISO 8601
+    pub fn format(self,description:FormatDescription<'static>)->UtcTimer<C>{
+        UtcTimer{description:Some(description),..self}
+    }
+}
+
+impl<C:Clock> FormatTime for UtcTimer<C>{
+    fn format_time(&self,w:&mut Writer<'_>)->fmt::Result{
+        let now=self.clock.now();
+        match &self.description{
+            Some(description)=>now.format_with_into(description,w),
+            None=>write!(w,"{}",now.display_iso8601()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use tracing_subscriber::fmt::format::Writer;
+    use tracing_subscriber::fmt::time::FormatTime;
+    use crate::UtcDatetime;
+    use crate::clock::Clock;
+    use crate::format::FormatDescription;
+    use super::UtcTimer;
+
+    struct Fixed;
+    impl Clock for Fixed{
+        fn now(&self)->UtcDatetime{UtcDatetime::new(2021,11,15,9,30,0).unwrap()}
+    }
+
+    #[test]
+    fn format_time(){
+        let mut s=String::new();
+        UtcTimer::with_clock(Fixed).format_time(&mut Writer::new(&mut s)).unwrap();
+        assert_eq!(s,"2021-11-15T09:30:00Z");
+        s.clear();
+        let timer=UtcTimer::with_clock(Fixed).format(FormatDescription::new("%d/%m/%Y %H:%M").unwrap());
+        timer.format_time(&mut Writer::new(&mut s)).unwrap();
+        assert_eq!(s,"15/11/2021 09:30");
+    }
+}
@@ -0,0 +1,91 @@
+//! Tumbling and sliding windows for stream processing.
+//!
+//! Windows begin at `start` and every `slide` after it, as long as they begin before `end`.
+//! The last windows are cut off at `end`.
+//! # Example
+//! ```
+//! use utc_datetime::{Duration, UtcDatetime};
+//! use utc_datetime::windows::{sliding, tumbling};
+//! let start=UtcDatetime::new(2021,11,15,9,0,0).unwrap();
+//! let end=UtcDatetime::new(2021,11,15,10,30,0).unwrap();
+//! let hours:Vec<String>=tumbling(start,end,Duration::hours(1)).map(|w|format!("{}..{}",w.start.hour(),w.end.minute())).collect();
+//! assert_eq!(hours,["9..0","10..30"]);
+//! assert_eq!(sliding(start,end,Duration::hours(1),Duration::minutes(30)).count(),3);
+//! ```
+use crate::{Duration, UtcDatetime, UtcInterval};
+
+/// Iterator over back-to-back windows, created by [`tumbling`]
+#[derive(Clone,Debug)]
+pub struct Tumbling(Sliding);
+
+/// Iterator over overlapping or spaced windows, created by [`sliding`]
+#[derive(Clone,Debug)]
+pub struct Sliding{
+    next:i64,
+    end:i64,
+    width:i64,
+    slide:i64,
+}
+
+/// Windows of `width` that follow each other without gaps.
+///
+/// Panics if `width` is not positive.
+pub fn tumbling(start:UtcDatetime,end:UtcDatetime,width:Duration)->Tumbling{
+    Tumbling(sliding(start,end,width,width))
+}
+
+/// Windows of `width` that begin every `slide`, they overlap if `slide` is shorter than `width`.
+///
+/// Panics if `width` or `slide` is not positive.
+pub fn sliding(start:UtcDatetime,end:UtcDatetime,width:Duration,slide:Duration)->Sliding{
+    assert!(width.whole_seconds()>0 && slide.whole_seconds()>0,"window width and slide must be positive");
+    Sliding{next:start.unix_seconds(),end:end.unix_seconds(),width:width.whole_seconds(),slide:slide.whole_seconds()}
+}
+
+impl Iterator for Sliding{
+    type Item=UtcInterval;
+
+    fn next(&mut self)->Option<UtcInterval>{
+        if self.next>=self.end{
+            return None
+        }
+        let start=UtcDatetime::from_unix_seconds(self.next).ok()?;
+        let end=UtcDatetime::from_unix_seconds(self.next.saturating_add(self.width).min(self.end)).ok()?;
+        self.next=self.next.saturating_add(self.slide);
+        Some(UtcInterval::new(start,end))
+    }
+}
+
+impl Iterator for Tumbling{
+    type Item=UtcInterval;
+
+    fn next(&mut self)->Option<UtcInterval>{
+        self.0.next()
+    }
+}
+
+#[cfg(test)]
+mod tests{
+    use crate::{Duration, UtcDatetime};
+    use super::{sliding, tumbling};
+
+    #[test]
+    fn windows(){
+        let windows:Vec<_>=tumbling(UtcDatetime::MIN,UtcDatetime::MAX,Duration::seconds(i64::MAX)).collect();
+        assert_eq!(windows.len(),1);
+        assert_eq!((windows[0].start,windows[0].end),(UtcDatetime::MIN,UtcDatetime::MAX));
+        assert_eq!(tumbling(UtcDatetime::MAX,UtcDatetime::MIN,Duration::seconds(1)).count(),0);
+        let day=UtcDatetime::new(1970,1,2,0,0,0).unwrap();
+        assert_eq!(tumbling(UtcDatetime::UNIX_EPOCH,day,Duration::hours(1)).count(),24);
+        assert!(tumbling(UtcDatetime::UNIX_EPOCH,day,Duration::hours(1)).all(|w|w.duration()==Duration::hours(1)));
+        let spaced:Vec<_>=sliding(UtcDatetime::UNIX_EPOCH,day,Duration::hours(1),Duration::hours(6)).map(|w|w.start.hour()).collect();
+        assert_eq!(spaced,[0,6,12,18]);
+        assert_eq!(sliding(UtcDatetime::UNIX_EPOCH,day,Duration::hours(2),Duration::hours(1)).last().unwrap().duration(),Duration::hours(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_width(){
+        tumbling(UtcDatetime::MIN,UtcDatetime::MAX,Duration::ZERO);
+    }
+}